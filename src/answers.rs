@@ -0,0 +1,276 @@
+//! The store of expected answers used to verify that refactoring hasn't changed any results.
+//!
+//! Answers are kept in `answers.toml` in the input directory (i.e. `res/answers.toml` by
+//! default), with a table for each day and input set, and a key for each part. The default input
+//! set's table is just the day, named input sets are nested under it, e.g.
+//!
+//! ```toml
+//! [day-1]
+//! part-1 = "514579"
+//! part-2 = "241861950"
+//!
+//! [day-1.alice]
+//! part-1 = "1010"
+//! ```
+//!
+//! Only this subset of TOML is supported. Answers are stored as strings as they are compared with
+//! the displayed answer, but plain integers are also accepted to make hand editing easier.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use regex::Regex;
+use error::{Error, Result};
+
+/// The file name of the answers store within the input directory.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Identifies an answer: the day, the input set (`None` for the default input set) and the part
+/// number.
+type Key = (usize, Option<String>, usize);
+
+/// The expected answers for each day, input set and part.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct Answers {
+    answers: BTreeMap<Key, String>,
+}
+
+impl Answers {
+    /// An empty store.
+    pub fn new() -> Answers {
+        Answers::default()
+    }
+
+    /// Load the answers stored at `path`. If the file doesn't exist there are no answers yet, so
+    /// the store is empty.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Answers> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(contents) => Answers::parse(&contents)
+                .map_err(|err| Error { message: format!("{} in '{}'", err.message, path.display()), ..err }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::new()),
+            Err(err) => Err(Error::new(format!("Failed to read '{}': {}", path.display(), err))),
+        }
+    }
+
+    /// Write the answers to `path`, replacing the existing file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_toml())
+            .map_err(|err| Error::new(format!("Failed to write '{}': {}", path.display(), err)))
+    }
+
+    /// Parse answers from the supported subset of TOML.
+    ///
+    /// # Examples
+    /// ```
+    /// # use advent_of_code_2020::answers::Answers;
+    /// let answers = Answers::parse("[day-1]\npart-1 = \"514579\"\n\n[day-1.alice]\npart-2 = 1010").unwrap();
+    ///
+    /// assert_eq!(Some("514579"), answers.get(1, None, 1));
+    /// assert_eq!(Some("1010"), answers.get(1, Some("alice"), 2));
+    /// assert_eq!(None, answers.get(1, None, 2));
+    /// ```
+    pub fn parse(input: &str) -> Result<Answers> {
+        let table_re = Regex::new(r#"^\[day-(\d+)(?:\.(?:([A-Za-z0-9_-]+)|"((?:[^"\\]|\\.)*)"))?\]$"#)
+            .expect("Invalid Regex");
+        let key_re = Regex::new(r"^part-(\d+)\s*=\s*(.*)$").expect("Invalid Regex");
+
+        let mut answers = Answers::new();
+        let mut table: Option<(usize, Option<String>)> = None;
+
+        for (index, raw_line) in input.lines().enumerate() {
+            let line = raw_line.trim();
+            let error = |message: &str| Error::invalid(raw_line, message).on_line(index + 1, raw_line);
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(cap) = table_re.captures(line) {
+                let day = cap[1].parse().map_err(|_| error("Invalid day"))?;
+                let name = match (cap.get(2), cap.get(3)) {
+                    (Some(bare), _) => Some(bare.as_str().to_string()),
+                    (None, Some(quoted)) => Some(unescape(quoted.as_str())),
+                    (None, None) => None,
+                };
+                table = Some((day, name));
+            } else if let Some(cap) = key_re.captures(line) {
+                let (day, name) = match &table {
+                    Some(table) => table.clone(),
+                    None => return Err(error("Expected a [day-N] table before any answers")),
+                };
+                let part = match cap[1].parse() {
+                    Ok(part @ 1..=2) => part,
+                    _ => return Err(error("Expected part-1 or part-2")),
+                };
+                let answer = match parse_value(&cap[2]) {
+                    Some(answer) => answer,
+                    None => return Err(error("Expected the answer to be a string or an integer")),
+                };
+                answers.answers.insert((day, name, part), answer);
+            } else {
+                return Err(error("Expected a [day-N] table or a part-N = \"answer\" key"));
+            }
+        }
+
+        Ok(answers)
+    }
+
+    /// Format the answers as TOML, in the same format [`Answers::parse`] reads.
+    ///
+    /// # Examples
+    /// ```
+    /// # use advent_of_code_2020::answers::Answers;
+    /// let mut answers = Answers::new();
+    /// answers.insert(7, Some("bob"), 2, "6260");
+    /// answers.insert(7, None, 1, "172");
+    ///
+    /// assert_eq!("[day-7]\npart-1 = \"172\"\n\n[day-7.bob]\npart-2 = \"6260\"\n", answers.to_toml());
+    /// ```
+    pub fn to_toml(&self) -> String {
+        let mut output = String::new();
+        let mut table: Option<(usize, &Option<String>)> = None;
+
+        for ((day, name, part), answer) in &self.answers {
+            if table != Some((*day, name)) {
+                if table.is_some() {
+                    output.push('\n');
+                }
+                match name {
+                    Some(name) => output.push_str(&format!("[day-{}.{}]\n", day, format_name(name))),
+                    None => output.push_str(&format!("[day-{}]\n", day)),
+                }
+                table = Some((*day, name));
+            }
+
+            output.push_str(&format!("part-{} = {}\n", part, quote(answer)));
+        }
+
+        output
+    }
+
+    /// The expected answer for a day, input set and part, if one has been recorded.
+    pub fn get(&self, day: usize, input_set: Option<&str>, part: usize) -> Option<&str> {
+        self.answers.get(&(day, input_set.map(|name| name.to_string()), part)).map(|answer| answer.as_str())
+    }
+
+    /// Record the expected answer for a day, input set and part, replacing any existing answer.
+    pub fn insert(&mut self, day: usize, input_set: Option<&str>, part: usize, answer: &str) {
+        self.answers.insert((day, input_set.map(|name| name.to_string()), part), answer.to_string());
+    }
+
+    /// The number of answers stored.
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    /// Are there no answers stored?
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+/// Parse a TOML value, either a basic string or an integer.
+fn parse_value(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        Some(unescape(&value[1..value.len() - 1]))
+    } else if value.parse::<i64>().is_ok() {
+        Some(value.to_string())
+    } else {
+        None
+    }
+}
+
+/// Input set names are written as bare keys if possible, otherwise quoted.
+fn format_name(name: &str) -> String {
+    if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        name.to_string()
+    } else {
+        quote(name)
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!(
+        "\"{}\"",
+        text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\t', "\\t")
+    )
+}
+
+fn unescape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(char) = chars.next() {
+        match (char, chars.clone().next()) {
+            ('\\', Some(escaped)) => {
+                chars.next();
+                output.push(match escaped {
+                    'n' => '\n',
+                    't' => '\t',
+                    other => other,
+                })
+            }
+            (char, _) => output.push(char),
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use answers::Answers;
+
+    #[test]
+    fn can_parse() {
+        let input = "# Expected answers
+[day-1]
+part-1 = \"514579\"
+part-2 = 241861950
+
+[day-1.\"alice's\"]
+part-1 = \"a \\\"quoted\\\" answer\"
+
+[day-13]
+  part-2 = \"1068781\"   ";
+
+        let answers = Answers::parse(input).unwrap();
+
+        assert_eq!(4, answers.len());
+        assert_eq!(Some("514579"), answers.get(1, None, 1));
+        assert_eq!(Some("241861950"), answers.get(1, None, 2));
+        assert_eq!(Some("a \"quoted\" answer"), answers.get(1, Some("alice's"), 1));
+        assert_eq!(Some("1068781"), answers.get(13, None, 2));
+        assert_eq!(None, answers.get(13, None, 1));
+    }
+
+    #[test]
+    fn rejects_invalid_answers() {
+        assert_eq!(Some(1), Answers::parse("part-1 = \"1\"").unwrap_err().line);
+        assert_eq!(Some(2), Answers::parse("[day-1]\npart-3 = \"1\"").unwrap_err().line);
+        assert_eq!(Some(3), Answers::parse("[day-1]\n\npart-1 = unquoted").unwrap_err().line);
+        assert_eq!(Some(1), Answers::parse("[day-1.a.b]").unwrap_err().line);
+    }
+
+    #[test]
+    fn can_round_trip() {
+        let mut answers = Answers::new();
+        answers.insert(2, None, 1, "422");
+        answers.insert(2, Some("team.b"), 1, "line 1\nline \"2\"");
+        answers.insert(10, None, 2, "49607173328384");
+        answers.insert(2, None, 2, "451");
+
+        let toml = answers.to_toml();
+        assert_eq!(
+            "[day-2]\npart-1 = \"422\"\npart-2 = \"451\"\n\n\
+             [day-2.\"team.b\"]\npart-1 = \"line 1\\nline \\\"2\\\"\"\n\n\
+             [day-10]\npart-2 = \"49607173328384\"\n",
+            toml
+        );
+        assert_eq!(Ok(answers), Answers::parse(&toml));
+    }
+}
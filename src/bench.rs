@@ -0,0 +1,382 @@
+//! Benchmarking each day's solution.
+//!
+//! A single run is too noisy to tell whether an optimisation helped, so [`bench`] runs a day a
+//! number of times after some warm-up runs, timing parsing separately from each part, and
+//! summarises the samples as [`Stats`].
+//!
+//! The medians can be saved as a [`Baseline`] and later runs compared against it, flagging any
+//! step that has become slower by more than a threshold as a regression. Baselines are stored as
+//! tab separated values with a header row, see [`Baseline::to_tsv`].
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use error::{Error, Result};
+use solution::{DaySolution, Part};
+
+/// The defaults for [`BenchOptions`].
+pub const DEFAULT_WARMUP: usize = 1;
+pub const DEFAULT_ITERATIONS: usize = 10;
+pub const DEFAULT_THRESHOLD: u32 = 10;
+
+/// The column names printed as the first row of a saved [`Baseline`].
+pub const BASELINE_HEADER: &str = "day\tinput_set\tstep\tmedian_ns";
+
+/// How to run the benchmarks.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct BenchOptions {
+    /// The number of untimed runs before the timed ones.
+    pub warmup: usize,
+    /// The number of timed runs.
+    pub iterations: usize,
+    /// A baseline to compare the results with.
+    pub baseline: Option<String>,
+    /// Where to save the results as a new baseline.
+    pub save_baseline: Option<String>,
+    /// How much slower, as a percentage of the baseline median, a step can be before it is
+    /// reported as a regression.
+    pub threshold: u32,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            warmup: DEFAULT_WARMUP,
+            iterations: DEFAULT_ITERATIONS,
+            baseline: None,
+            save_baseline: None,
+            threshold: DEFAULT_THRESHOLD,
+        }
+    }
+}
+
+/// A part of a day's solution that is timed separately.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+pub enum Step {
+    Parse,
+    Part(usize),
+}
+
+impl Step {
+    fn from_part(part: Part) -> Step {
+        match part {
+            Part::One => Step::Part(1),
+            Part::Two => Step::Part(2),
+        }
+    }
+
+    fn key(&self) -> String {
+        match self {
+            Step::Parse => "parse".to_string(),
+            Step::Part(part) => format!("part-{}", part),
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Step> {
+        match key {
+            "parse" => Some(Step::Parse),
+            "part-1" => Some(Step::Part(1)),
+            "part-2" => Some(Step::Part(2)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Parse => write!(f, "parse"),
+            Step::Part(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+/// Summary statistics for a set of timings.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// The sample standard deviation, zero if there is only one sample.
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Summarise `samples`, which must not be empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::time::Duration;
+    /// # use advent_of_code_2020::bench::Stats;
+    /// let samples: Vec<Duration> = [4, 1, 3, 2].iter().map(|&ms| Duration::from_millis(ms)).collect();
+    /// let stats = Stats::from_samples(&samples);
+    ///
+    /// assert_eq!(Duration::from_millis(1), stats.min);
+    /// assert_eq!(Duration::from_micros(2500), stats.median);
+    /// assert_eq!(Duration::from_micros(2500), stats.mean);
+    /// ```
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let count = sorted.len();
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        } else {
+            sorted[count / 2]
+        };
+
+        let mean = sorted.iter().sum::<Duration>() / count as u32;
+        let variance = if count > 1 {
+            sorted.iter()
+                .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+                .sum::<f64>() / (count - 1) as f64
+        } else {
+            0.0
+        };
+
+        Stats { min: sorted[0], median, mean, stddev: Duration::from_secs_f64(variance.sqrt()) }
+    }
+}
+
+/// The benchmark results for one day against one input.
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    pub steps: Vec<(Step, Stats)>,
+}
+
+/// Benchmark a day's solution against `input`, running each of `parts` as well as parsing.
+pub fn bench(solution: &dyn DaySolution, input: &str, parts: &[Part], warmup: usize, iterations: usize) -> Result<BenchResult> {
+    if iterations == 0 {
+        return Err(Error::new("At least one iteration is needed to benchmark"));
+    }
+
+    for _ in 0..warmup {
+        solution.run_timed(input, parts)?;
+    }
+
+    let mut samples: BTreeMap<Step, Vec<Duration>> = BTreeMap::new();
+    for _ in 0..iterations {
+        let run = solution.run_timed(input, parts)?;
+        samples.entry(Step::Parse).or_default().push(run.parse);
+        for answer in run.answers {
+            samples.entry(Step::from_part(answer.part)).or_default().push(answer.elapsed);
+        }
+    }
+
+    Ok(BenchResult {
+        steps: samples.into_iter().map(|(step, samples)| (step, Stats::from_samples(&samples))).collect()
+    })
+}
+
+/// How a step's median compares to its baseline.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Comparison {
+    /// There's no baseline for the step.
+    Missing,
+    /// Within the threshold of the baseline, with the change as a percentage.
+    Unchanged(f64),
+    /// Faster than the baseline by more than the threshold.
+    Improved(f64),
+    /// Slower than the baseline by more than the threshold.
+    Regressed(f64),
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Comparison::Missing => write!(f, "no baseline"),
+            Comparison::Unchanged(change) => write!(f, "{:+.1}%", change),
+            Comparison::Improved(change) => write!(f, "{:+.1}% improved", change),
+            Comparison::Regressed(change) => write!(f, "{:+.1}% REGRESSED", change),
+        }
+    }
+}
+
+/// The median timings of a previous benchmark, keyed by day, input set, and step.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct Baseline {
+    medians: BTreeMap<(usize, Option<String>, Step), Duration>,
+}
+
+impl Baseline {
+    /// An empty baseline.
+    pub fn new() -> Baseline {
+        Baseline::default()
+    }
+
+    /// Load a baseline saved by [`Baseline::save`].
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Baseline> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|err| Error::new(format!("Failed to read '{}': {}", path.display(), err)))?;
+
+        Baseline::parse(&contents)
+            .map_err(|err| Error { message: format!("{} in '{}'", err.message, path.display()), ..err })
+    }
+
+    /// Save the baseline to `path`, replacing the existing file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_tsv())
+            .map_err(|err| Error::new(format!("Failed to write '{}': {}", path.display(), err)))
+    }
+
+    /// Parse a baseline from the format written by [`Baseline::to_tsv`].
+    pub fn parse(input: &str) -> Result<Baseline> {
+        let mut baseline = Baseline::new();
+
+        for (index, line) in input.lines().enumerate() {
+            if line.is_empty() || line == BASELINE_HEADER {
+                continue;
+            }
+
+            let error = |message: &str| Error::invalid(line, message).on_line(index + 1, line);
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() != 4 {
+                return Err(error("Expected day, input set, step and median separated by tabs"));
+            }
+
+            let day = fields[0].parse().map_err(|_| error("Invalid day"))?;
+            let name = Some(fields[1].to_string()).filter(|name| !name.is_empty());
+            let step = Step::from_key(fields[2]).ok_or_else(|| error("Expected parse, part-1 or part-2"))?;
+            let median = fields[3].parse().map_err(|_| error("Invalid median"))?;
+
+            baseline.medians.insert((day, name, step), Duration::from_nanos(median));
+        }
+
+        Ok(baseline)
+    }
+
+    /// Format the baseline as tab separated values, with a header row.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::time::Duration;
+    /// # use advent_of_code_2020::bench::{Baseline, Step};
+    /// let mut baseline = Baseline::new();
+    /// baseline.insert(15, None, Step::Part(2), Duration::from_millis(800));
+    /// baseline.insert(15, None, Step::Parse, Duration::from_nanos(1200));
+    ///
+    /// assert_eq!(
+    ///     "day\tinput_set\tstep\tmedian_ns\n15\t\tparse\t1200\n15\t\tpart-2\t800000000\n",
+    ///     baseline.to_tsv()
+    /// );
+    /// ```
+    pub fn to_tsv(&self) -> String {
+        let mut output = format!("{}\n", BASELINE_HEADER);
+
+        for ((day, name, step), median) in &self.medians {
+            output.push_str(&format!(
+                "{}\t{}\t{}\t{}\n",
+                day,
+                name.as_deref().unwrap_or(""),
+                step.key(),
+                median.as_nanos()
+            ));
+        }
+
+        output
+    }
+
+    /// Record the median for a step, replacing any existing one.
+    pub fn insert(&mut self, day: usize, input_set: Option<&str>, step: Step, median: Duration) {
+        self.medians.insert((day, input_set.map(|name| name.to_string()), step), median);
+    }
+
+    /// Compare a median with the baseline for that step, `threshold` is a percentage of the
+    /// baseline.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::time::Duration;
+    /// # use advent_of_code_2020::bench::{Baseline, Comparison, Step};
+    /// let mut baseline = Baseline::new();
+    /// baseline.insert(1, None, Step::Part(1), Duration::from_millis(100));
+    ///
+    /// let compare = |ms| baseline.compare(1, None, Step::Part(1), Duration::from_millis(ms), 10);
+    /// assert!(matches!(compare(105), Comparison::Unchanged(_)));
+    /// assert!(matches!(compare(120), Comparison::Regressed(_)));
+    /// assert!(matches!(compare(50), Comparison::Improved(_)));
+    /// assert_eq!(Comparison::Missing, baseline.compare(1, None, Step::Parse, Duration::from_millis(1), 10));
+    /// ```
+    pub fn compare(&self, day: usize, input_set: Option<&str>, step: Step, median: Duration, threshold: u32) -> Comparison {
+        let baseline = match self.medians.get(&(day, input_set.map(|name| name.to_string()), step)) {
+            Some(baseline) => baseline.as_secs_f64(),
+            None => return Comparison::Missing,
+        };
+
+        let change = if baseline > 0.0 {
+            (median.as_secs_f64() - baseline) / baseline * 100.0
+        } else {
+            0.0
+        };
+
+        if change > threshold as f64 {
+            Comparison::Regressed(change)
+        } else if change < -(threshold as f64) {
+            Comparison::Improved(change)
+        } else {
+            Comparison::Unchanged(change)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use bench::{bench, Baseline, Stats, Step};
+    use day_1::Day1;
+    use solution::Part;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    #[test]
+    fn can_calculate_stats() {
+        let stats = Stats::from_samples(&millis(&[2, 4, 4, 4, 5, 5, 7, 9]));
+
+        assert_eq!(Duration::from_millis(2), stats.min);
+        assert_eq!(Duration::from_micros(4500), stats.median);
+        assert_eq!(Duration::from_millis(5), stats.mean);
+        assert_eq!(2138, stats.stddev.as_micros());
+
+        assert_eq!(
+            Stats {
+                min: Duration::from_millis(3),
+                median: Duration::from_millis(3),
+                mean: Duration::from_millis(3),
+                stddev: Duration::from_millis(0),
+            },
+            Stats::from_samples(&millis(&[3]))
+        );
+    }
+
+    #[test]
+    fn can_bench() {
+        let input = "1721\n979\n366\n299\n675\n1456";
+        let result = bench(&Day1, input, &[Part::Two], 1, 3).unwrap();
+
+        assert_eq!(
+            vec!(Step::Parse, Step::Part(2)),
+            result.steps.iter().map(|(step, _)| *step).collect::<Vec<Step>>()
+        );
+        assert!(bench(&Day1, input, &[Part::One], 0, 0).is_err());
+        assert!(bench(&Day1, "1\n2", &[Part::One], 0, 1).is_err());
+    }
+
+    #[test]
+    fn can_round_trip_baseline() {
+        let mut baseline = Baseline::new();
+        baseline.insert(3, None, Step::Part(1), Duration::from_nanos(1234));
+        baseline.insert(3, Some("alice"), Step::Parse, Duration::from_micros(56));
+        baseline.insert(12, None, Step::Part(2), Duration::from_millis(7));
+
+        assert_eq!(Ok(baseline.clone()), Baseline::parse(&baseline.to_tsv()));
+        assert_eq!(Some(2), Baseline::parse("day\tinput_set\tstep\tmedian_ns\n3\t\tpart-3\t1").unwrap_err().line);
+        assert!(Baseline::parse("3\tparse\t1").is_err());
+    }
+}
@@ -0,0 +1,588 @@
+//! Command line handling for the day runner.
+//!
+//! The runner can be driven entirely from arguments so that it can be scripted, e.g.
+//!
+//! - `advent-of-code-2020 run 7` - run both parts of day 7
+//! - `advent-of-code-2020 run 1..=17` - run an inclusive range of days
+//! - `advent-of-code-2020 run --all --part 2` - run only part two of every day
+//! - `advent-of-code-2020 run 3 --input res/other-day-3-input` - run day 3 against another file
+//! - `advent-of-code-2020 run 3 --input -` - run day 3 against input piped to stdin
+//! - `advent-of-code-2020 run --all --all-sets` - run every day against every input set found, see
+//!   [`input`](crate::input) for how inputs are located
+//! - `advent-of-code-2020 run --all --format json` - print the results as JSON lines, see
+//!   [`output`](crate::output)
+//! - `advent-of-code-2020 verify --all` - check every day's answers against the expected answers,
+//!   see [`answers`](crate::answers)
+//! - `advent-of-code-2020 verify 7 --record` - store day 7's current answers as the expected ones
+//! - `advent-of-code-2020 bench 15 --iterations 20` - time day 15 over 20 runs, see
+//!   [`bench`](crate::bench)
+//! - `advent-of-code-2020 run --all --parallel` - run the days, and the parts within each day,
+//!   concurrently
+//!
+//! If no arguments are given, [`parse_args`] returns [`Command::Interactive`] and the runner falls
+//! back to prompting for a day number on stdin.
+
+use answers::ANSWERS_FILE;
+use bench::{self, BenchOptions};
+use input::{self, InputSelection, STDIN};
+use output::Format;
+use solution::Part;
+
+/// The exit code used when the runner completed and every requested day succeeded.
+pub const EXIT_SUCCESS: i32 = 0;
+/// The exit code used when at least one of the requested days failed to run.
+pub const EXIT_FAILURE: i32 = 1;
+/// The exit code used when the command line arguments could not be understood.
+pub const EXIT_USAGE: i32 = 2;
+
+/// What the runner has been asked to do.
+// Only one of these is created per process, so the size of `RunOptions` doesn't matter
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Eq, PartialEq)]
+pub enum Command {
+    /// No arguments were given, prompt for the day on stdin.
+    Interactive,
+    /// Print the usage text.
+    Help,
+    /// Run a set of days.
+    Run(RunOptions),
+}
+
+/// What to do with each day's answers.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Mode {
+    /// Print them.
+    Run,
+    /// Compare them with the expected answers.
+    Verify,
+    /// Compare them with the expected answers, then store them as the new expected answers.
+    Record,
+    /// Ignore them, and report how long each step took over a number of runs.
+    Bench,
+}
+
+/// The options for [`Command::Run`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct RunOptions {
+    /// The days to run, in the order they should be run.
+    pub days: Vec<usize>,
+    /// The parts of each day to run.
+    pub parts: Vec<Part>,
+    /// Which input(s) to run each day against. Only a single day can be run against a specific
+    /// path.
+    pub input: InputSelection,
+    /// Overrides the directory inputs are read from, which is otherwise taken from the
+    /// environment.
+    pub input_dir: Option<String>,
+    /// How to print the results.
+    pub format: Format,
+    /// Whether to print or verify the answers.
+    pub mode: Mode,
+    /// Overrides the location of the expected answers, which is otherwise `answers.toml` in the
+    /// input directory.
+    pub answers: Option<String>,
+    /// How to run the benchmarks in [`Mode::Bench`].
+    pub bench: BenchOptions,
+    /// The number of threads to run days on, with 1 running them one after another on the main
+    /// thread, and 0 using one thread per CPU.
+    pub jobs: usize,
+}
+
+impl RunOptions {
+    /// Run `days` against their default inputs, printing the results as text.
+    pub fn new(days: Vec<usize>, parts: Vec<Part>) -> RunOptions {
+        RunOptions {
+            days,
+            parts,
+            input: InputSelection::Default,
+            input_dir: None,
+            format: Format::Text,
+            mode: Mode::Run,
+            answers: None,
+            bench: BenchOptions::default(),
+            jobs: 1,
+        }
+    }
+}
+
+/// The usage text printed for `--help`, or alongside an error when the arguments are invalid.
+pub fn usage(program: &str, day_count: usize) -> String {
+    format!(
+        "Usage: {program} [run <DAYS>|--all] [--part 1|2] [--input PATH|--input-set NAME|--all-sets]
+                      [--input-dir DIR] [--format text|json|tsv] [--parallel|--jobs N]
+       {program} verify <DAYS>|--all [--part 1|2] [--input-set NAME|--all-sets]
+                      [--input-dir DIR] [--answers PATH] [--record] [--parallel|--jobs N]
+       {program} bench <DAYS>|--all [--part 1|2] [--input PATH|--input-set NAME|--all-sets]
+                      [--input-dir DIR] [--warmup N] [--iterations N] [--baseline PATH]
+                      [--save-baseline PATH] [--threshold PERCENT]
+
+Runs the Advent of Code 2020 solutions. With no arguments, prompts for a day.
+
+Commands:
+  run             Run the solutions and print the answers. This is the default.
+  verify          Run the solutions and check the answers match the expected answers, reporting
+                  each part as pass, FAIL, or missing if no answer has been recorded.
+  bench           Run the solutions repeatedly, and report the min, median, mean and standard
+                  deviation of the time taken to parse the input and to solve each part.
+
+Arguments:
+  <DAYS>          A day (7), an inclusive range (1..=17), an exclusive range (1..5), or a comma
+                  separated list of any of those (1,3,5..=7). Days run from 1 to {day_count}.
+
+Options:
+  -a, --all               Run every day
+  -p, --part <PART>       Only run part 1 or part 2 of each day
+  -i, --input <PATH>      Read the puzzle input from PATH, or stdin if PATH is {stdin}
+  -s, --input-set <NAME>  Read the puzzle input from DIR/day-N-input-NAME
+      --all-sets          Run against every input set found in DIR for each day
+  -d, --input-dir <DIR>   Read inputs from DIR instead of ${input_dir_var} (default: {input_dir})
+  -f, --format <FORMAT>   Print the results as text (the default), json (one object per line
+                          for each day and part) or tsv
+  -P, --parallel          Run the days on one thread per CPU, and the parts of each day on
+                          their own threads. The output is still in day order.
+  -j, --jobs <N>          As --parallel, but with N threads for the days
+      --answers <PATH>    Read the expected answers from PATH instead of DIR/{answers_file}
+      --record            Store the answers as the new expected answers after verifying them
+      --warmup <N>        Run each day N times before timing it (default: {warmup})
+  -n, --iterations <N>    Time each day over N runs (default: {iterations})
+      --baseline <PATH>   Compare the median timings with a baseline saved by --save-baseline
+      --save-baseline <PATH>
+                          Save the median timings as a baseline
+      --threshold <PERCENT>
+                          How much slower than the baseline a step can be before it is reported
+                          as a regression (default: {threshold})
+  -h, --help              Print this help text
+
+Exit codes:
+  0 - every requested day ran successfully, and when verifying no answers differed
+  1 - at least one day failed, an answer differed from the expected answer, or a benchmark
+      regressed compared to the baseline
+  2 - the arguments were invalid",
+        program = program,
+        day_count = day_count,
+        stdin = STDIN,
+        input_dir_var = input::INPUT_DIR_VAR,
+        input_dir = input::DEFAULT_INPUT_DIR,
+        answers_file = ANSWERS_FILE,
+        warmup = bench::DEFAULT_WARMUP,
+        iterations = bench::DEFAULT_ITERATIONS,
+        threshold = bench::DEFAULT_THRESHOLD,
+    )
+}
+
+/// Parse the command line arguments (excluding the program name) into a [`Command`].
+///
+/// `day_count` is the number of days with solutions, and is used to validate the requested days
+/// and to expand `--all`.
+pub fn parse_args<I: IntoIterator<Item=String>>(args: I, day_count: usize) -> Result<Command, String> {
+    let mut args = args.into_iter().peekable();
+
+    if args.peek().is_none() {
+        return Ok(Command::Interactive);
+    }
+
+    // `run` is the default sub-command, so it's optional
+    let mut mode = match args.peek().map(|arg| arg.as_str()) {
+        Some("run") => {
+            args.next();
+            Mode::Run
+        }
+        Some("verify") => {
+            args.next();
+            Mode::Verify
+        }
+        Some("bench") => {
+            args.next();
+            Mode::Bench
+        }
+        _ => Mode::Run,
+    };
+
+    let mut days: Option<Vec<usize>> = None;
+    let mut all = false;
+    let mut parts = vec!(Part::One, Part::Two);
+    let mut input = InputSelection::Default;
+    let mut input_dir = None;
+    let mut format = None;
+    let mut answers = None;
+    let mut bench = BenchOptions::default();
+    let mut jobs = None;
+    // any of the bench options, to report if they're used outside of bench mode
+    let mut bench_option = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-a" | "--all" => all = true,
+            "-p" | "--part" => {
+                let value = args.next().ok_or("--part requires a value")?;
+                parts = vec!(parse_part(value.as_str())?);
+            }
+            "-i" | "--input" => {
+                let value = args.next().ok_or("--input requires a value")?;
+                input = select_input(input, InputSelection::Path(value))?;
+            }
+            "-s" | "--input-set" => {
+                let value = args.next().ok_or("--input-set requires a value")?;
+                input = select_input(input, InputSelection::Set(value))?;
+            }
+            "--all-sets" => input = select_input(input, InputSelection::AllSets)?,
+            "-d" | "--input-dir" => {
+                let value = args.next().ok_or("--input-dir requires a value")?;
+                input_dir = Some(value);
+            }
+            "-f" | "--format" => {
+                let value = args.next().ok_or("--format requires a value")?;
+                format = Some(parse_format(value.as_str())?);
+            }
+            "-P" | "--parallel" => jobs = Some(0),
+            "-j" | "--jobs" => {
+                let value = args.next().ok_or("--jobs requires a value")?;
+                jobs = Some(parse_count("--jobs", value.as_str(), 1)?);
+            }
+            "--answers" => {
+                let value = args.next().ok_or("--answers requires a value")?;
+                answers = Some(value);
+            }
+            "--record" => mode = match mode {
+                Mode::Verify | Mode::Record => Mode::Record,
+                _ => return Err("--record can only be used with verify".to_string()),
+            },
+            "--warmup" => {
+                let value = args.next().ok_or("--warmup requires a value")?;
+                bench.warmup = parse_count("--warmup", value.as_str(), 0)?;
+                bench_option = Some(arg);
+            }
+            "-n" | "--iterations" => {
+                let value = args.next().ok_or("--iterations requires a value")?;
+                bench.iterations = parse_count("--iterations", value.as_str(), 1)?;
+                bench_option = Some(arg);
+            }
+            "--baseline" => {
+                bench.baseline = Some(args.next().ok_or("--baseline requires a value")?);
+                bench_option = Some(arg);
+            }
+            "--save-baseline" => {
+                bench.save_baseline = Some(args.next().ok_or("--save-baseline requires a value")?);
+                bench_option = Some(arg);
+            }
+            "--threshold" => {
+                let value = args.next().ok_or("--threshold requires a value")?;
+                bench.threshold = parse_count("--threshold", value.as_str(), 0)? as u32;
+                bench_option = Some(arg);
+            }
+            flag if flag.starts_with('-') => return Err(format!("Unknown option '{}'", flag)),
+            spec if days.is_none() => days = Some(parse_days(spec, day_count)?),
+            extra => return Err(format!("Unexpected argument '{}'", extra)),
+        }
+    }
+
+    let days = match (days, all) {
+        (Some(_), true) => return Err("Specify either <DAYS> or --all, not both".to_string()),
+        (Some(days), false) => days,
+        (None, true) => (1..=day_count).collect(),
+        (None, false) => return Err("No days specified, use <DAYS> or --all".to_string()),
+    };
+
+    if let InputSelection::Path(_) = input {
+        if mode == Mode::Verify || mode == Mode::Record {
+            // the expected answers are stored by input set, so there's nothing to compare against
+            return Err("--input can't be used with verify, use --input-set".to_string());
+        }
+        if days.len() != 1 {
+            return Err("--input can only be used when running a single day".to_string());
+        }
+    }
+
+    if answers.is_some() && mode != Mode::Verify && mode != Mode::Record {
+        return Err("--answers can only be used with verify".to_string());
+    }
+
+    if let Some(option) = bench_option {
+        if mode != Mode::Bench {
+            return Err(format!("{} can only be used with bench", option));
+        }
+    }
+
+    if jobs.is_some() && mode == Mode::Bench {
+        // running other days at the same time would skew the timings
+        return Err("--parallel and --jobs can't be used with bench".to_string());
+    }
+    let jobs = jobs.unwrap_or(1);
+
+    let format = match (format, mode) {
+        (Some(_), Mode::Verify) | (Some(_), Mode::Record) =>
+            return Err("--format can't be used with verify".to_string()),
+        (Some(_), Mode::Bench) => return Err("--format can't be used with bench".to_string()),
+        (format, _) => format.unwrap_or(Format::Text),
+    };
+
+    Ok(Command::Run(RunOptions { days, parts, input, input_dir, format, mode, answers, bench, jobs }))
+}
+
+/// `--input`, `--input-set` and `--all-sets` are mutually exclusive.
+fn select_input(current: InputSelection, selected: InputSelection) -> Result<InputSelection, String> {
+    match current {
+        InputSelection::Default => Ok(selected),
+        _ => Err("Only one of --input, --input-set or --all-sets can be used".to_string()),
+    }
+}
+
+/// Parse the value passed to `--part`.
+fn parse_part(value: &str) -> Result<Part, String> {
+    match value {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        other => Err(format!("Invalid part '{}', expected 1 or 2", other)),
+    }
+}
+
+/// Parse a count passed to `option`, which must be at least `min`.
+fn parse_count(option: &str, value: &str, min: usize) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(count) if count >= min => Ok(count),
+        _ => Err(format!("Invalid {} '{}', expected a number of at least {}", option, value, min)),
+    }
+}
+
+/// Parse the value passed to `--format`.
+fn parse_format(value: &str) -> Result<Format, String> {
+    match value {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "tsv" => Ok(Format::Tsv),
+        other => Err(format!("Invalid format '{}', expected text, json or tsv", other)),
+    }
+}
+
+/// Parse a day specification, e.g. `7`, `1..=17`, `1..5` or `1,3,5..=7`.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::cli::parse_days;
+/// assert_eq!(Ok(vec!(7)), parse_days("7", 17));
+/// assert_eq!(Ok(vec!(1, 2, 3)), parse_days("1..=3", 17));
+/// assert_eq!(Ok(vec!(1, 2)), parse_days("1..3", 17));
+/// assert_eq!(Ok(vec!(1, 3, 5, 6, 7)), parse_days("1,3,5..=7", 17));
+/// ```
+pub fn parse_days(spec: &str, day_count: usize) -> Result<Vec<usize>, String> {
+    let mut days = Vec::new();
+
+    for item in spec.split(',') {
+        let (start, end) = if let Some(idx) = item.find("..=") {
+            (parse_day(&item[..idx], day_count)?, parse_day(&item[idx + 3..], day_count)?)
+        } else if let Some(idx) = item.find("..") {
+            let end = parse_day(&item[idx + 2..], day_count + 1)?;
+            (parse_day(&item[..idx], day_count)?, end - 1)
+        } else {
+            let day = parse_day(item, day_count)?;
+            (day, day)
+        };
+
+        if start > end {
+            return Err(format!("Invalid day range '{}'", item));
+        }
+
+        days.extend(start..=end);
+    }
+
+    Ok(days)
+}
+
+/// Parse and validate a single day number.
+fn parse_day(value: &str, day_count: usize) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(day) if day >= 1 && day <= day_count => Ok(day),
+        Ok(day) => Err(format!("Invalid Day {}, expected 1 to {}", day, day_count)),
+        Err(_) => Err(format!("Invalid Day '{}'", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bench::BenchOptions;
+    use cli::{parse_args, parse_days, Command, Mode, RunOptions};
+    use input::InputSelection;
+    use output::Format;
+    use solution::Part;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn run(days: Vec<usize>, parts: Vec<Part>, input: Option<&str>) -> Result<Command, String> {
+        let input = input.map(|i| InputSelection::Path(i.to_string())).unwrap_or(InputSelection::Default);
+        Ok(Command::Run(RunOptions { input, ..RunOptions::new(days, parts) }))
+    }
+
+    #[test]
+    fn can_parse_days() {
+        assert_eq!(Ok(vec!(7)), parse_days("7", 17));
+        assert_eq!(Ok(vec!(1, 2, 3)), parse_days("1..=3", 17));
+        assert_eq!(Ok(vec!(1, 2)), parse_days("1..3", 17));
+        assert_eq!(Ok(vec!(1, 3, 5, 6, 7)), parse_days("1,3,5..=7", 17));
+        assert_eq!(Ok((1..=17).collect()), parse_days("1..=17", 17));
+        assert_eq!(Ok((1..=17).collect()), parse_days("1..18", 17));
+
+        assert!(parse_days("0", 17).is_err());
+        assert!(parse_days("18", 17).is_err());
+        assert!(parse_days("1..=18", 17).is_err());
+        assert!(parse_days("5..=3", 17).is_err());
+        assert!(parse_days("seven", 17).is_err());
+    }
+
+    #[test]
+    fn can_parse_args() {
+        let both = vec!(Part::One, Part::Two);
+
+        assert_eq!(Ok(Command::Interactive), parse_args(args(&[]), 17));
+        assert_eq!(Ok(Command::Help), parse_args(args(&["--help"]), 17));
+        assert_eq!(Ok(Command::Help), parse_args(args(&["run", "-h"]), 17));
+
+        assert_eq!(run(vec!(7), both.clone(), None), parse_args(args(&["run", "7"]), 17));
+        assert_eq!(run(vec!(7), both.clone(), None), parse_args(args(&["7"]), 17));
+        assert_eq!(run((1..=17).collect(), both.clone(), None), parse_args(args(&["run", "--all"]), 17));
+        assert_eq!(run(vec!(1, 2, 3), both.clone(), None), parse_args(args(&["run", "1..=3"]), 17));
+        assert_eq!(
+            run(vec!(2), vec!(Part::Two), None),
+            parse_args(args(&["run", "2", "--part", "2"]), 17)
+        );
+        assert_eq!(
+            run(vec!(3), vec!(Part::One), Some("other")),
+            parse_args(args(&["run", "-p", "1", "-i", "other", "3"]), 17)
+        );
+        assert_eq!(run(vec!(3), both.clone(), Some("-")), parse_args(args(&["run", "3", "--input", "-"]), 17));
+    }
+
+    #[test]
+    fn can_parse_input_options() {
+        let both = vec!(Part::One, Part::Two);
+
+        assert_eq!(
+            Ok(Command::Run(RunOptions {
+                days: vec!(1, 2),
+                parts: both.clone(),
+                input: InputSelection::Set("alice".to_string()),
+                input_dir: Some("team".to_string()),
+                ..RunOptions::new(vec!(), vec!())
+            })),
+            parse_args(args(&["run", "1..=2", "--input-set", "alice", "-d", "team"]), 17)
+        );
+        assert_eq!(
+            Ok(Command::Run(RunOptions { input: InputSelection::AllSets, ..RunOptions::new(vec!(4), both.clone()) })),
+            parse_args(args(&["run", "4", "--all-sets"]), 17)
+        );
+
+        assert!(parse_args(args(&["run", "4", "--all-sets", "--input-set", "bob"]), 17).is_err());
+        assert!(parse_args(args(&["run", "4", "--input", "-", "--all-sets"]), 17).is_err());
+        assert!(parse_args(args(&["run", "4", "--input-set"]), 17).is_err());
+        assert!(parse_args(args(&["run", "4", "--input-dir"]), 17).is_err());
+    }
+
+    #[test]
+    fn can_parse_format() {
+        let both = vec!(Part::One, Part::Two);
+
+        assert_eq!(
+            Ok(Command::Run(RunOptions { format: Format::Json, ..RunOptions::new(vec!(5), both.clone()) })),
+            parse_args(args(&["run", "5", "--format", "json"]), 17)
+        );
+        assert_eq!(
+            Ok(Command::Run(RunOptions { format: Format::Tsv, ..RunOptions::new(vec!(5), both.clone()) })),
+            parse_args(args(&["5", "-f", "tsv"]), 17)
+        );
+        assert!(parse_args(args(&["run", "5", "--format", "xml"]), 17).is_err());
+        assert!(parse_args(args(&["run", "5", "--format"]), 17).is_err());
+    }
+
+    #[test]
+    fn can_parse_verify() {
+        let both = vec!(Part::One, Part::Two);
+
+        assert_eq!(
+            Ok(Command::Run(RunOptions { mode: Mode::Verify, ..RunOptions::new((1..=17).collect(), both.clone()) })),
+            parse_args(args(&["verify", "--all"]), 17)
+        );
+        assert_eq!(
+            Ok(Command::Run(RunOptions {
+                mode: Mode::Record,
+                input: InputSelection::AllSets,
+                answers: Some("expected.toml".to_string()),
+                ..RunOptions::new(vec!(7), both.clone())
+            })),
+            parse_args(args(&["verify", "7", "--record", "--all-sets", "--answers", "expected.toml"]), 17)
+        );
+
+        assert!(parse_args(args(&["run", "7", "--record"]), 17).is_err());
+        assert!(parse_args(args(&["7", "--answers", "expected.toml"]), 17).is_err());
+        assert!(parse_args(args(&["verify", "7", "--input", "other"]), 17).is_err());
+        assert!(parse_args(args(&["verify", "7", "--format", "json"]), 17).is_err());
+        assert!(parse_args(args(&["verify"]), 17).is_err());
+    }
+
+    #[test]
+    fn can_parse_bench() {
+        let both = vec!(Part::One, Part::Two);
+
+        assert_eq!(
+            Ok(Command::Run(RunOptions { mode: Mode::Bench, ..RunOptions::new(vec!(15), both.clone()) })),
+            parse_args(args(&["bench", "15"]), 17)
+        );
+        assert_eq!(
+            Ok(Command::Run(RunOptions {
+                mode: Mode::Bench,
+                bench: BenchOptions {
+                    warmup: 0,
+                    iterations: 25,
+                    baseline: Some("before.tsv".to_string()),
+                    save_baseline: Some("after.tsv".to_string()),
+                    threshold: 5,
+                },
+                ..RunOptions::new(vec!(1, 2), vec!(Part::Two))
+            })),
+            parse_args(
+                args(&[
+                    "bench", "1..=2", "-p", "2", "--warmup", "0", "-n", "25", "--baseline", "before.tsv",
+                    "--save-baseline", "after.tsv", "--threshold", "5"
+                ]),
+                17
+            )
+        );
+
+        assert!(parse_args(args(&["bench", "15", "--iterations", "0"]), 17).is_err());
+        assert!(parse_args(args(&["bench", "15", "--warmup", "-1"]), 17).is_err());
+        assert!(parse_args(args(&["bench", "15", "--record"]), 17).is_err());
+        assert!(parse_args(args(&["bench", "15", "--format", "json"]), 17).is_err());
+        assert!(parse_args(args(&["run", "15", "--iterations", "5"]), 17).is_err());
+        assert!(parse_args(args(&["verify", "15", "--baseline", "before.tsv"]), 17).is_err());
+    }
+
+    #[test]
+    fn can_parse_parallel() {
+        let both = vec!(Part::One, Part::Two);
+
+        assert_eq!(
+            Ok(Command::Run(RunOptions { jobs: 0, ..RunOptions::new((1..=17).collect(), both.clone()) })),
+            parse_args(args(&["run", "--all", "--parallel"]), 17)
+        );
+        assert_eq!(
+            Ok(Command::Run(RunOptions { jobs: 4, mode: Mode::Verify, ..RunOptions::new(vec!(1, 2), both.clone()) })),
+            parse_args(args(&["verify", "1,2", "-j", "4"]), 17)
+        );
+
+        assert!(parse_args(args(&["run", "--all", "--jobs", "0"]), 17).is_err());
+        assert!(parse_args(args(&["bench", "--all", "--parallel"]), 17).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_args() {
+        assert!(parse_args(args(&["run"]), 17).is_err());
+        assert!(parse_args(args(&["run", "18"]), 17).is_err());
+        assert!(parse_args(args(&["run", "7", "8"]), 17).is_err());
+        assert!(parse_args(args(&["run", "7", "--all"]), 17).is_err());
+        assert!(parse_args(args(&["run", "7", "--part", "3"]), 17).is_err());
+        assert!(parse_args(args(&["run", "7", "--part"]), 17).is_err());
+        assert!(parse_args(args(&["run", "--all", "--input", "file"]), 17).is_err());
+        assert!(parse_args(args(&["run", "7", "--verbose"]), 17).is_err());
+    }
+}
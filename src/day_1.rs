@@ -1,88 +1,328 @@
-use std::fs;
+//! This is my solution for [Advent of Code - Day 1](https://adventofcode.com/2020/day/1) -
+//! _Report Repair_
 
-pub fn run() {
-    let contents = fs::read_to_string("res/day-1-input").expect("Failed to read file");
-    let mut ints = read_to_ints(contents.as_str());
+use std::collections::HashSet;
+use error::{parse_lines, parse_number, Error, Result};
+use solution::Solution;
 
-    let (a, b) = find_pair_sum(&mut ints, 2020).unwrap();
-    println!("{} x {} = {}", a, b, a * b);
+/// The [`Solution`] for day 1, finds the expenses that sum to 2020 and multiplies them.
+pub struct Day1;
 
-    let (a, b, c) = find_triple_sum(&mut ints, 2020).unwrap();
-    println!("{} x {} x {} = {}", a, b, c, a * b * c);
+impl Solution for Day1 {
+    type Input<'a> = Vec<i32>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    fn parse(&self, input: &str) -> Result<Vec<i32>> {
+        read_to_ints(input)
+    }
+
+    fn part_one(&self, ints: &Vec<i32>) -> Result<i32> {
+        find_k_sum(ints, 2, 2020)
+            .map(|pair| pair.iter().product())
+            .ok_or_else(|| Error::new("No pair sums to 2020"))
+    }
+
+    fn part_two(&self, ints: &Vec<i32>) -> Result<i32> {
+        find_k_sum(ints, 3, 2020)
+            .map(|triple| triple.iter().product())
+            .ok_or_else(|| Error::new("No triple sums to 2020"))
+    }
+}
+
+/// Parse the puzzle input into one number per line.
+pub fn read_to_ints(contents: &str) -> Result<Vec<i32>> {
+    parse_lines(contents, |line| parse_number(line, line, 1))
+}
+
+/// The algorithms available for finding the final pair of numbers in a k-sum search. All of them
+/// work on the sorted numbers and find the same sets, see [`find_k_sum_with`].
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Strategy {
+    /// Close in on the target from both ends of the numbers, `O(n)`.
+    TwoPointer,
+    /// Look up the number needed to reach the target in a set of the numbers seen so far, `O(n)`
+    /// but with the overhead of hashing.
+    HashSet,
+    /// Binary search the rest of the numbers for the number needed to reach the target,
+    /// `O(n log n)`.
+    BinarySearch,
+}
+
+impl Strategy {
+    /// Every strategy, e.g. for comparing them.
+    pub const ALL: [Strategy; 3] = [Strategy::TwoPointer, Strategy::HashSet, Strategy::BinarySearch];
 }
 
-fn read_to_ints(contents: &str) -> Vec<i32> {
-    return contents.lines().flat_map(|line| line.parse::<i32>().ok()).collect();
+/// Find `k` numbers in `ints` that sum to `target_sum`, returned in ascending order. Each entry
+/// can only be used once, but the same value can be used more than once if it is repeated in
+/// `ints`. Returns `None` if there are no such numbers, including when `ints` has fewer than `k`
+/// entries.
+///
+/// The numbers are sorted first, then the first `k - 2` numbers are chosen recursively, and the
+/// last two are found by closing in on the target from both ends of the remaining numbers.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_1::*;
+/// let ints = vec!(1721, 979, 366, 299, 675, 1456);
+///
+/// assert_eq!(Some(vec!(299, 1721)), find_k_sum(&ints, 2, 2020));
+/// assert_eq!(Some(vec!(366, 675, 979)), find_k_sum(&ints, 3, 2020));
+/// assert_eq!(None, find_k_sum(&ints, 4, 2020));
+/// assert_eq!(None, find_k_sum(&[2020], 2, 2020));
+/// ```
+pub fn find_k_sum(ints: &[i32], k: usize, target_sum: i32) -> Option<Vec<i32>> {
+    find_k_sum_with(ints, k, target_sum, Strategy::TwoPointer)
 }
 
-fn find_pair_sum_iter(ints: &Vec<i32>, target_sum: i32, min_idx: usize, max_idx: usize) -> Option<(i32, i32)> {
-    let min = ints.get(min_idx).expect("min out of range");
-    let max = ints.get(max_idx).expect("max out of range");
+/// As [`find_k_sum`], but using `strategy` to find the final pair. Which set is found first when
+/// there is more than one can depend on the strategy.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_1::*;
+/// let ints = vec!(1721, 979, 366, 299, 675, 1456);
+///
+/// for strategy in Strategy::ALL.iter() {
+///     assert_eq!(Some(vec!(299, 1721)), find_k_sum_with(&ints, 2, 2020, *strategy));
+///     assert_eq!(Some(vec!(366, 675, 979)), find_k_sum_with(&ints, 3, 2020, *strategy));
+/// }
+/// ```
+pub fn find_k_sum_with(ints: &[i32], k: usize, target_sum: i32, strategy: Strategy) -> Option<Vec<i32>> {
+    let mut found = Vec::new();
+    k_sum_iter(&sorted(ints), k, target_sum as i64, strategy, &mut Vec::new(), &mut found, false);
+    found.pop()
+}
+
+/// As [`find_k_sum`], but finds every set of `k` numbers that sum to `target_sum`. Each set is in
+/// ascending order, and the sets are in ascending order. Repeated values don't produce the same
+/// set more than once.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_1::*;
+/// let ints = vec!(1, 5, 3, 3, 2, 4);
+///
+/// assert_eq!(vec!(vec!(1, 5), vec!(2, 4), vec!(3, 3)), find_all_k_sums(&ints, 2, 6));
+/// assert_eq!(vec!(vec!(1, 2, 3)), find_all_k_sums(&ints, 3, 6));
+/// ```
+pub fn find_all_k_sums(ints: &[i32], k: usize, target_sum: i32) -> Vec<Vec<i32>> {
+    find_all_k_sums_with(ints, k, target_sum, Strategy::TwoPointer)
+}
+
+/// As [`find_all_k_sums`], but using `strategy` to find the final pair. All the strategies return
+/// the same sets in the same order.
+pub fn find_all_k_sums_with(ints: &[i32], k: usize, target_sum: i32, strategy: Strategy) -> Vec<Vec<i32>> {
+    let mut found = Vec::new();
+    k_sum_iter(&sorted(ints), k, target_sum as i64, strategy, &mut Vec::new(), &mut found, true);
+    found
+}
+
+fn sorted(ints: &[i32]) -> Vec<i32> {
+    let mut sorted = ints.to_vec();
+    sorted.sort_unstable();
+    sorted
+}
 
-    if min + max == target_sum {
-        return Some((*min, *max));
+/// Find the sets of `k` numbers from the sorted `ints` that sum to `target_sum`, appending each
+/// one to `chosen` and pushing it to `found`. Stops after the first unless `find_all` is set. Sums
+/// are done as `i64` so that large `k` can't overflow.
+fn k_sum_iter(
+    ints: &[i32],
+    k: usize,
+    target_sum: i64,
+    strategy: Strategy,
+    chosen: &mut Vec<i32>,
+    found: &mut Vec<Vec<i32>>,
+    find_all: bool,
+) {
+    if k == 0 {
+        if target_sum == 0 {
+            found.push(chosen.clone());
+        }
+        return;
     }
 
-    let max_target_number = target_sum - min;
-    let new_max_idx = find_new_bound(ints, max_target_number, min_idx + 1, max_idx);
-    let new_max = ints.get(new_max_idx).expect("mid out of range");
-    if min + new_max == target_sum {
-        return Some((*min, *new_max));
+    if ints.len() < k {
+        return;
     }
 
-    let min_target_number = target_sum - new_max;
-    let new_min_idx = find_new_bound(ints, min_target_number, min_idx, new_max_idx - 1);
+    if k == 2 {
+        let pairs = match strategy {
+            Strategy::TwoPointer => find_pairs_two_pointer(ints, target_sum, find_all),
+            Strategy::HashSet => find_pairs_hash_set(ints, target_sum, find_all),
+            Strategy::BinarySearch => find_pairs_binary_search(ints, target_sum, find_all),
+        };
 
-    if new_min_idx + 1 >= new_max_idx {
-        return None;
+        for (min, max) in pairs {
+            let mut solution = chosen.clone();
+            solution.push(min);
+            solution.push(max);
+            found.push(solution);
+        }
+        return;
     }
 
-    return find_pair_sum_iter(ints, target_sum, new_min_idx, new_max_idx);
+    let sum = |slice: &[i32]| slice.iter().map(|&n| n as i64).sum::<i64>();
+
+    for i in 0..=(ints.len() - k) {
+        // the same first value would find the same sets again
+        if i > 0 && ints[i] == ints[i - 1] {
+            continue;
+        }
+
+        // the smallest sum starting from here is already too big, and it only grows from here
+        if sum(&ints[i..i + k]) > target_sum {
+            break;
+        }
+        // the largest sum starting from here is too small, try a bigger first number
+        if ints[i] as i64 + sum(&ints[ints.len() - (k - 1)..]) < target_sum {
+            continue;
+        }
+
+        chosen.push(ints[i]);
+        k_sum_iter(&ints[i + 1..], k - 1, target_sum - ints[i] as i64, strategy, chosen, found, find_all);
+        chosen.pop();
+
+        if !find_all && !found.is_empty() {
+            return;
+        }
+    }
 }
 
-fn find_new_bound(ints: &Vec<i32>, target_number: i32, min_idx: usize, max_idx: usize) -> usize {
-    let mid_idx = ((min_idx + max_idx) / 2) as usize;
-    if mid_idx == min_idx {
-        return min_idx;
+/// Find the distinct pairs in the sorted `ints` that sum to `target_sum`, by moving inwards from
+/// both ends. The pairs are in ascending order of their smaller number.
+fn find_pairs_two_pointer(ints: &[i32], target_sum: i64, find_all: bool) -> Vec<(i32, i32)> {
+    let mut pairs = Vec::new();
+    if ints.len() < 2 {
+        return pairs;
     }
 
-    let mid = *ints.get(mid_idx).expect("mid out of range");
-    if mid > target_number
-    {
-        find_new_bound(ints, target_number, min_idx, mid_idx)
-    } else {
-        find_new_bound(ints, target_number, mid_idx, max_idx)
+    let (mut min_idx, mut max_idx) = (0, ints.len() - 1);
+    while min_idx < max_idx {
+        let (min, max) = (ints[min_idx], ints[max_idx]);
+        let sum = min as i64 + max as i64;
+
+        if sum == target_sum {
+            pairs.push((min, max));
+            if !find_all {
+                break;
+            }
+
+            // skip past repeats so the same pair isn't found twice
+            while min_idx < max_idx && ints[min_idx] == min { min_idx += 1 }
+            while min_idx < max_idx && ints[max_idx] == max { max_idx -= 1 }
+        } else if sum < target_sum {
+            min_idx += 1;
+        } else {
+            max_idx -= 1;
+        }
     }
-}
 
-fn find_pair_sum(ints: &mut Vec<i32>, target_sum: i32) -> Option<(i32, i32)> {
-    ints.sort();
-    let max = ints.len() - 1;
-    find_pair_sum_iter(ints, target_sum, 0, max)
+    pairs
 }
 
-fn find_triple_sum(ints: &mut Vec<i32>, target_sum: i32) -> Option<(i32, i32, i32)> {
-    ints.sort();
-    let mut i = 0;
-    let max = ints.len() - 1;
-    while i < ints.len() - 3
-    {
-        let a = ints.get(i).expect("i out of range");
-        let result = find_pair_sum_iter(ints, target_sum - a, i + 1, max);
-        if result.is_some() {
-            let (b, c) = result.unwrap();
-            return Some((*a, b, c));
+/// Find the distinct pairs in the sorted `ints` that sum to `target_sum`, by checking each number
+/// against a set of the numbers before it. The pairs are in ascending order of their smaller
+/// number.
+fn find_pairs_hash_set(ints: &[i32], target_sum: i64, find_all: bool) -> Vec<(i32, i32)> {
+    let mut seen: HashSet<i32> = HashSet::with_capacity(ints.len());
+    let mut pairs = Vec::new();
+
+    for &max in ints {
+        let needed = target_sum - max as i64;
+        // as ints is sorted, anything in seen is no bigger than max
+        if needed >= i32::MIN as i64 && needed <= max as i64 && seen.contains(&(needed as i32)) {
+            let pair = (needed as i32, max);
+            // repeats of max are next to each other, so would find the same pair again
+            if pairs.last() != Some(&pair) {
+                pairs.push(pair);
+                if !find_all {
+                    break;
+                }
+            }
         }
-        i = i + 1;
+        seen.insert(max);
     }
 
-    return None;
+    // pairs are found in ascending order of their larger number, i.e. descending smaller number
+    pairs.reverse();
+    pairs
 }
 
+/// Find the distinct pairs in the sorted `ints` that sum to `target_sum`, by binary searching the
+/// numbers after each number for the one needed to reach the target. The pairs are in ascending
+/// order of their smaller number.
+fn find_pairs_binary_search(ints: &[i32], target_sum: i64, find_all: bool) -> Vec<(i32, i32)> {
+    let mut pairs = Vec::new();
+
+    for (i, &min) in ints.iter().enumerate() {
+        if i > 0 && ints[i - 1] == min {
+            continue;
+        }
+
+        let needed = target_sum - min as i64;
+        if needed < min as i64 {
+            break;
+        }
+        if needed > i32::MAX as i64 {
+            continue;
+        }
+
+        if ints[i + 1..].binary_search(&(needed as i32)).is_ok() {
+            pairs.push((min, needed as i32));
+            if !find_all {
+                break;
+            }
+        }
+    }
+
+    pairs
+}
 
 #[cfg(test)]
 mod tests {
-    use day_1::{read_to_ints, find_pair_sum, find_triple_sum};
+    use day_1::{read_to_ints, find_k_sum, find_all_k_sums, find_all_k_sums_with, find_k_sum_with, Strategy};
+
+    /// A generator of pseudo-random numbers between `min` and `max` inclusive.
+    fn numbers(seed: u64, min: i32, max: i32) -> impl FnMut() -> i32 {
+        let mut state = seed;
+        let range = (max as i64 - min as i64 + 1) as u64;
+        move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (min as i64 + ((state >> 33) % range) as i64) as i32
+        }
+    }
+
+    /// Check every strategy agrees on a seeded random list of `size` numbers.
+    fn assert_strategies_agree_on_random(seed: u64, size: i32, k: usize) {
+        let mut next = numbers(seed, -2 * size, 2 * size);
+        let ints: Vec<i32> = (0..size).map(|_| next()).collect();
+
+        assert_strategies_agree(&ints, k, next());
+    }
+
+    /// Check that every strategy finds the same sets, and that what they find is valid.
+    fn assert_strategies_agree(ints: &[i32], k: usize, target_sum: i32) {
+        let expected = find_all_k_sums_with(ints, k, target_sum, Strategy::TwoPointer);
+
+        for &strategy in Strategy::ALL.iter() {
+            assert_eq!(expected, find_all_k_sums_with(ints, k, target_sum, strategy), "{:?}", strategy);
+
+            let found = find_k_sum_with(ints, k, target_sum, strategy);
+            assert_eq!(expected.is_empty(), found.is_none(), "{:?}", strategy);
+            if let Some(set) = found {
+                assert!(expected.contains(&set), "{:?} found {:?}", strategy, set);
+            }
+        }
+
+        for set in expected {
+            assert_eq!(k, set.len());
+            assert_eq!(target_sum as i64, set.iter().map(|&n| n as i64).sum::<i64>());
+            assert!(set.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+    }
 
     #[test]
     fn can_parse_file() {
@@ -91,27 +331,128 @@ mod tests {
 1926
 1946
 1722
-1776");
+1776").unwrap();
         assert_eq!(ints.len(), 6);
-        assert_eq!(ints.get(0), Some(&1953i32));
+        assert_eq!(ints.first(), Some(&1953i32));
         assert_eq!(ints.get(5), Some(&1776i32));
+
+        let err = read_to_ints("1953\n2oo6").unwrap_err();
+        assert_eq!((Some(2), Some(1)), (err.line, err.column));
     }
 
     #[test]
     fn can_find_sum() {
-        let mut ints = vec!(1721, 979, 366, 299, 675, 1456, 1991, 100);
-        assert_eq!(find_pair_sum(&mut ints, 2020), Some((299i32, 1721i32)));
+        let ints = vec!(1721, 979, 366, 299, 675, 1456, 1991, 100);
+        assert_eq!(find_k_sum(&ints, 2, 2020), Some(vec!(299, 1721)));
 
-        let mut invalid_ints = vec!(1721, 979, 366, 298, 675, 1456, 1991, 100);
-        assert_eq!(find_pair_sum(&mut invalid_ints, 2020), None)
+        let invalid_ints = vec!(1721, 979, 366, 298, 675, 1456, 1991, 100);
+        assert_eq!(find_k_sum(&invalid_ints, 2, 2020), None)
     }
 
     #[test]
     fn can_find_triple_sum() {
-        let mut ints = vec!(1721, 979, 366, 299, 675, 1456);
-        assert_eq!(find_triple_sum(&mut ints, 2020), Some((366i32, 675i32, 979i32)));
+        let ints = vec!(1721, 979, 366, 299, 675, 1456);
+        assert_eq!(find_k_sum(&ints, 3, 2020), Some(vec!(366, 675, 979)));
 
-        let mut invalid_ints = vec!(1721, 979, 366, 299, 674, 1456, 1991, 100);
-        assert_eq!(find_triple_sum(&mut invalid_ints, 2020), None)
+        let invalid_ints = vec!(1721, 979, 366, 299, 674, 1456, 1991, 100);
+        assert_eq!(find_k_sum(&invalid_ints, 3, 2020), None)
+    }
+
+    #[test]
+    fn can_find_k_sum_in_small_inputs() {
+        assert_eq!(find_k_sum(&[], 2, 2020), None);
+        assert_eq!(find_k_sum(&[2020], 2, 2020), None);
+        assert_eq!(find_k_sum(&[1000, 1020], 2, 2020), Some(vec!(1000, 1020)));
+        assert_eq!(find_k_sum(&[1000, 1020], 3, 2020), None);
+        assert_eq!(find_k_sum(&[1000, 20, 1000], 3, 2020), Some(vec!(20, 1000, 1000)));
+        assert_eq!(find_k_sum(&[2020], 1, 2020), Some(vec!(2020)));
+        assert_eq!(find_k_sum(&[], 0, 0), Some(vec!()));
+    }
+
+    #[test]
+    fn can_find_k_sum_with_duplicates() {
+        assert_eq!(find_k_sum(&[1010, 5], 2, 2020), None);
+        assert_eq!(find_k_sum(&[1010, 5, 1010], 2, 2020), Some(vec!(1010, 1010)));
+        assert_eq!(find_all_k_sums(&[1010, 1010, 1010], 2, 2020), vec!(vec!(1010, 1010)));
+        assert_eq!(
+            find_all_k_sums(&[-1, 0, 1, 2, -1, -4], 3, 0),
+            vec!(vec!(-1, -1, 2), vec!(-1, 0, 1))
+        );
+        assert_eq!(
+            find_all_k_sums(&[1, 0, -1, 0, -2, 2], 4, 0),
+            vec!(vec!(-2, -1, 1, 2), vec!(-2, 0, 0, 2), vec!(-1, 0, 0, 1))
+        );
+        assert_eq!(find_all_k_sums(&[i32::MAX, i32::MAX, 2], 3, 2), Vec::<Vec<i32>>::new());
+    }
+
+    #[test]
+    fn strategies_agree_on_examples() {
+        assert_strategies_agree(&[1721, 979, 366, 299, 675, 1456], 2, 2020);
+        assert_strategies_agree(&[1721, 979, 366, 299, 675, 1456], 3, 2020);
+        assert_strategies_agree(&[1010, 1010, 1010, 5], 2, 2020);
+        assert_strategies_agree(&[1, 5, 3, 3, 2, 4], 2, 6);
+        assert_strategies_agree(&[1, 5, 3, 3, 2, 4], 2, 100);
+        assert_strategies_agree(&[], 2, 0);
+        assert_strategies_agree(&[i32::MIN, i32::MAX, -1, 0, 1], 2, -1);
+        assert_strategies_agree(&[i32::MIN, i32::MIN, i32::MAX, i32::MAX], 2, i32::MAX);
+    }
+
+    #[test]
+    fn strategies_agree_on_pairs() {
+        let ints = [7, -3, 12, 5, 5, 0, 9, -3, 14, 2, 8, 1, 3, 11, -6, 10];
+
+        for target in -10..=30 {
+            assert_strategies_agree(&ints, 2, target);
+        }
+        assert_eq!(
+            vec!(vec!(-3, 14), vec!(0, 11), vec!(1, 10), vec!(2, 9), vec!(3, 8)),
+            find_all_k_sums_with(&ints, 2, 11, Strategy::HashSet)
+        );
+    }
+
+    #[test]
+    fn strategies_agree_on_random_pairs() {
+        for (seed, &size) in [10, 1_000, 100_000].iter().enumerate() {
+            assert_strategies_agree_on_random(seed as u64, size, 2);
+        }
+    }
+
+    #[test]
+    #[ignore = "slow without optimisations, run with --release -- --ignored"]
+    fn strategies_agree_on_a_million_random_pairs() {
+        assert_strategies_agree_on_random(3, 1_000_000, 2);
+    }
+
+    #[test]
+    fn strategies_agree_on_random_triples() {
+        for (seed, &size) in [10, 100, 2_000].iter().enumerate() {
+            assert_strategies_agree_on_random(seed as u64, size, 3);
+        }
+    }
+
+    #[test]
+    fn strategies_agree_on_triples() {
+        let ints = [4, -1, 4, 0, 7, -5, 3, 3, 3, 12, -8, 6, 1, 1];
+
+        for target in -15..=25 {
+            assert_strategies_agree(&ints, 3, target);
+        }
+        assert_eq!(
+            vec!(vec!(-8, 1, 7), vec!(-8, 4, 4), vec!(-5, -1, 6), vec!(-5, 1, 4), vec!(-1, 0, 1)),
+            find_all_k_sums_with(&ints, 3, 0, Strategy::BinarySearch)
+        );
+    }
+
+    #[test]
+    #[ignore = "slow without optimisations, run with --release -- --ignored"]
+    fn can_find_sum_in_large_inputs() {
+        // multiples of 3 can't sum to 2020, so only 1000 + 1020 can
+        let mut ints: Vec<i32> = (0..1_000_000).rev().map(|n| n * 3).collect();
+        ints.push(1000);
+
+        for &strategy in Strategy::ALL.iter() {
+            assert_eq!(Some(vec!(1000, 1020)), find_k_sum_with(&ints, 2, 2020, strategy));
+        }
+        assert_strategies_agree(&ints, 2, 2020);
     }
 }
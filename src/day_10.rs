@@ -1,24 +1,42 @@
-use std::fs;
+//! This is my solution for [Advent of Code - Day 10](https://adventofcode.com/2020/day/10) -
+//! _Adapter Array_
 
-pub fn run() {
-    let contents = fs::read_to_string("res/day-10-input").expect("Failed to read file");
-    let adapters = parse(contents.as_str());
+use error::{parse_lines, parse_number, Error, Result};
+use solution::Solution;
 
-    let (ones, threes) = calculate_jolts(&adapters);
-    println!("{} ones x {} threes = {}", ones, threes, ones * threes);
+/// The [`Solution`] for day 10, chains the joltage adapters together.
+pub struct Day10;
 
-    let combinations = calculate_combinations(&adapters);
-    println!("{} possible combinations", combinations);
+impl Solution for Day10 {
+    type Input<'a> = Vec<usize>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<usize>> {
+        parse(input)
+    }
+
+    fn part_one(&self, adapters: &Vec<usize>) -> Result<usize> {
+        let (ones, threes) = calculate_jolts(adapters);
+        Ok(ones * threes)
+    }
+
+    fn part_two(&self, adapters: &Vec<usize>) -> Result<usize> {
+        calculate_combinations(adapters)
+    }
 }
 
-fn parse(input: &str) -> Vec<usize> {
-    let mut adapters: Vec<usize> = input.lines().map(|line| line.parse::<usize>().unwrap()).collect();
+/// Parse the adapters' joltages, sorted in ascending order.
+pub fn parse(input: &str) -> Result<Vec<usize>> {
+    let mut adapters: Vec<usize> = parse_lines(input, |line| parse_number(line, line, 1))?;
     adapters.sort();
 
-    adapters
+    Ok(adapters)
 }
 
-fn calculate_jolts(adapters: &Vec<usize>) -> (usize, usize) {
+/// Part one, count the one and three jolt differences when chaining all the adapters, including
+/// the final jump of three to the device.
+pub fn calculate_jolts(adapters: &[usize]) -> (usize, usize) {
     let (ones, threes, _) = adapters.iter().fold(
         (0, 0, 0),
         |(ones, threes, prev), &adapter| match adapter - prev {
@@ -32,16 +50,18 @@ fn calculate_jolts(adapters: &Vec<usize>) -> (usize, usize) {
     (ones, threes + 1)
 }
 
-fn calculate_combinations(adapters: &Vec<usize>) -> usize {
-    let (combinations, run, _) = adapters.iter().fold(
+/// Part two, count the distinct arrangements of adapters that connect the outlet to the device.
+/// This relies on the gaps between adapters only being 1 or 3 jolts, and fails if not.
+pub fn calculate_combinations(adapters: &[usize]) -> Result<usize> {
+    let (combinations, run, _) = adapters.iter().try_fold(
         (1, 0, 0),
         |(acc, run, prev), &adapter| match adapter - prev {
-            1 => (acc, run + 1, adapter),
-            3 => (acc * run_combinations(run), 0, adapter),
-            _ => panic!("not just 1s and 3s")
-        });
+            1 => Ok((acc, run + 1, adapter)),
+            3 => Ok((acc * run_combinations(run), 0, adapter)),
+            gap => Err(Error::new(format!("Expected only 1 or 3 jolt gaps, found {} before {}", gap, adapter)))
+        })?;
 
-        combinations * run_combinations(run)
+        Ok(combinations * run_combinations(run))
 }
 
 fn run_combinations(run: usize) -> usize {
@@ -108,42 +128,46 @@ mod tests {
     #[test]
     fn can_parse() {
         assert_eq!(
-            vec!(1, 4, 5, 6, 7, 10, 11, 12, 15, 16, 19),
+            Ok(vec!(1, 4, 5, 6, 7, 10, 11, 12, 15, 16, 19)),
             parse(small_input())
         );
 
         assert_eq!(
-            vec!(
+            Ok(vec!(
                 1, 2, 3, 4, 7, 8, 9, 10, 11, 14, 17, 18, 19, 20, 23, 24, 25, 28, 31,
                 32, 33, 34, 35, 38, 39, 42, 45, 46, 47, 48, 49
-            ),
+            )),
             parse(medium_input())
         );
+
+        assert_eq!(Some(2), parse("16\n1O\n15").unwrap_err().line);
     }
 
     #[test]
     fn can_calculate_jolts() {
         assert_eq!(
             (7usize, 5usize),
-            calculate_jolts(&parse(small_input()))
+            calculate_jolts(&parse(small_input()).unwrap())
         );
 
         assert_eq!(
             (22usize, 10usize),
-            calculate_jolts(&parse(medium_input()))
+            calculate_jolts(&parse(medium_input()).unwrap())
         );
     }
 
     #[test]
     fn can_calculate_combinations() {
         assert_eq!(
-            8,
-            calculate_combinations(&parse(small_input()))
+            Ok(8),
+            calculate_combinations(&parse(small_input()).unwrap())
         );
 
         assert_eq!(
-            19208,
-            calculate_combinations(&parse(medium_input()))
+            Ok(19208),
+            calculate_combinations(&parse(medium_input()).unwrap())
         );
+
+        assert!(calculate_combinations(&[1, 2, 4]).is_err());
     }
 }
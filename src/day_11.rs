@@ -1,95 +1,139 @@
-use std::fs;
+//! This is my solution for [Advent of Code - Day 11](https://adventofcode.com/2020/day/11) -
+//! _Seating System_
+
 use day_11::Seat::*;
+use error::{Error, Result};
+use solution::Solution;
 
+/// A position in the waiting area.
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
-enum Seat {
+pub enum Seat {
     FLOOR,
     EMPTY,
     OCCUPIED,
 }
 
+/// A two dimensional grid, stored as a single `Vec` of rows.
 #[derive(Debug, Eq, PartialEq, Clone)]
-struct Grid<T> {
+pub struct Grid<T> {
     row_length: usize,
     data: Vec<T>,
 }
 
 impl<T> Grid<T> {
-    fn new(row_length: usize) -> Grid<T> {
+    /// Create an empty grid, where each row will have `row_length` cells.
+    pub fn new(row_length: usize) -> Grid<T> {
         Grid {
             row_length,
             data: Vec::new(),
         }
     }
 
-    fn get(&self, x: usize, y: usize) -> Option<&T> {
+    /// Create a `width` by `height` grid, with each cell set to `f(x, y)`.
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(width: usize, height: usize, mut f: F) -> Grid<T> {
+        let mut grid = Grid::new(width);
+        for y in 0..height {
+            for x in 0..width {
+                grid.insert(x, y, f(x, y))
+            }
+        }
+        grid
+    }
+
+    /// Get the cell at `x`, `y`, `None` if that is outside the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
         if x >= self.row_length { return None };
         self.data.get(self.row_length * y + x)
     }
 
-    fn insert(&mut self, x: usize, y: usize, value: T) -> () {
+    /// Set the cell at `x`, `y`, cells should be inserted in order.
+    pub fn insert(&mut self, x: usize, y: usize, value: T) {
         assert!(
             x < self.row_length,
-            format!("x = {} is out of bounds for Grid with row size {}", x, self.row_length)
+            "x = {} is out of bounds for Grid with row size {}", x, self.row_length
         );
         self.data.insert(self.row_length * y + x, value)
     }
 
-    fn size(&self) -> (usize, usize) {
+    /// The `(width, height)` of the grid.
+    pub fn size(&self) -> (usize, usize) {
         (self.row_length, (self.data.len() - 1) / self.row_length + 1)
     }
 }
 
-pub fn run() {
-    let contents = fs::read_to_string("res/day-11-input").expect("Failed to read file");
-    let grid = parse_grid(contents.as_str());
+/// The [`Solution`] for day 11, simulates people choosing seats until it stabilises.
+pub struct Day11;
 
-    let adjacent_count = count_stable_adjacent_occupation(&grid);
-    println!("Once adjacent model has stabilised, there are {} occupied seats", adjacent_count);
+impl Solution for Day11 {
+    type Input<'a> = Grid<Seat>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<Grid<Seat>> {
+        parse_grid(input)
+    }
+
+    fn part_one(&self, grid: &Grid<Seat>) -> Result<usize> {
+        Ok(count_stable_adjacent_occupation(grid))
+    }
 
-    let visible_count = count_stable_visible_occupation(&grid);
-    println!("Once visible model has stabilised, there are {} occupied seats", visible_count);
+    fn part_two(&self, grid: &Grid<Seat>) -> Result<usize> {
+        Ok(count_stable_visible_occupation(grid))
+    }
 }
 
-fn parse_grid(input: &str) -> Grid<Seat> {
-    let row_length = input.lines().next().unwrap().len();
+/// Parse the seat layout, `.` is floor, `L` an empty seat, and `#` an occupied seat. The first row
+/// sets the width of the grid, and no row can be wider than that.
+pub fn parse_grid(input: &str) -> Result<Grid<Seat>> {
+    let row_length = input.lines().next().map(|line| line.len()).unwrap_or(0);
+    if row_length == 0 {
+        return Err(Error::new("Expected at least one row of seats"));
+    }
 
     let mut grid = Grid::new(row_length);
 
     for (y, line) in input.lines().enumerate() {
         for (x, char) in line.chars().enumerate() {
+            let error = |message: String| Err(Error::at_column(line, x + 1, message).on_line(y + 1, line));
+            if x >= row_length {
+                return error(format!("Row is wider than the first row's {} seats", row_length));
+            }
+
             match char {
                 '.' => grid.insert(x, y, FLOOR),
                 'L' => grid.insert(x, y, EMPTY),
                 '#' => grid.insert(x, y, OCCUPIED),
-                _ => panic!("Invalid char")
+                other => return error(format!("Invalid char '{}'", other))
             }
         }
     }
 
-    grid
+    Ok(grid)
 }
 
-fn lookup_surrounds(grid: &Grid<Seat>, x: usize, y: usize) -> Vec<Seat> {
-    vec!(
+/// Part one, the seats in the eight cells adjacent to `x`, `y`.
+pub fn lookup_surrounds(grid: &Grid<Seat>, x: usize, y: usize) -> Vec<Seat> {
+    [
         (x.checked_sub(1), y.checked_sub(1)), (Some(x), y.checked_sub(1)), (x.checked_add(1), y.checked_sub(1)),
         (x.checked_sub(1), Some(y)), /*                                 */ (x.checked_add(1), Some(y)),
         (x.checked_sub(1), y.checked_add(1)), (Some(x), y.checked_add(1)), (x.checked_add(1), y.checked_add(1))
-    )
+    ]
         .iter()
         .flat_map(|(x1, y1)| match (*x1, *y1) {
-            (Some(x), Some(y)) => grid.get(x, y).map(|s| *s),
+            (Some(x), Some(y)) => grid.get(x, y).copied(),
             _ => None
         })
         .collect()
 }
 
-fn lookup_visible_seats(grid: &Grid<Seat>, x: usize, y: usize) -> Vec<Seat> {
-    vec!(
+/// Part two, the first seat visible in each of the eight directions from `x`, `y`.
+pub fn lookup_visible_seats(grid: &Grid<Seat>, x: usize, y: usize) -> Vec<Seat> {
+    [
         (-1, -1), (0, -1), (1, -1),
         (-1, 0), /*     */ (1, 0),
         (-1, 1), (0, 1), (1, 1)
-    ).iter().flat_map(|(dx, dy)| lookup_visible_seat(grid, x, y, *dx, *dy)).collect()
+    ].iter().flat_map(|(dx, dy)| lookup_visible_seat(grid, x, y, *dx, *dy)).collect()
 }
 
 fn lookup_visible_seat(grid: &Grid<Seat>, x: usize, y: usize, dx: isize, dy: isize) -> Option<Seat> {
@@ -128,7 +172,10 @@ fn iterate_cell <F> (grid: &Grid<Seat>, x: usize, y: usize, mapper: &F, occupati
     }
 }
 
-fn iterate_grid<F>(grid: &Grid<Seat>, mapper: &F, occupation_threshold: usize) -> (Grid<Seat>, usize) where
+/// Apply one round of the seating rules, returning the new grid and the number of seats that
+/// changed. `mapper` gives the seats considered for each cell, and a seat is vacated if at least
+/// `occupation_threshold` of them are occupied.
+pub fn iterate_grid<F>(grid: &Grid<Seat>, mapper: &F, occupation_threshold: usize) -> (Grid<Seat>, usize) where
     F: Fn(&Grid<Seat>, usize, usize) -> Vec<Seat>
 {
     let (x_max, y_max) = grid.size();
@@ -140,7 +187,7 @@ fn iterate_grid<F>(grid: &Grid<Seat>, mapper: &F, occupation_threshold: usize) -
             let new_seat = iterate_cell(grid, x, y, mapper, occupation_threshold).unwrap();
             new_grid.insert(x, y, new_seat);
             if grid.get(x, y) != Some(&new_seat) {
-                mod_count = mod_count + 1
+                mod_count += 1
             }
         }
     }
@@ -148,7 +195,8 @@ fn iterate_grid<F>(grid: &Grid<Seat>, mapper: &F, occupation_threshold: usize) -
     (new_grid, mod_count)
 }
 
-fn count_stable_adjacent_occupation(grid: &Grid<Seat>) -> usize {
+/// Part one, the occupied seats once the layout stabilises using [`lookup_surrounds`].
+pub fn count_stable_adjacent_occupation(grid: &Grid<Seat>) -> usize {
     let (new_grid, mod_count) = iterate_grid(grid, &lookup_surrounds, 4);
     if mod_count == 0 {
         new_grid.data.iter().filter(|s| **s == OCCUPIED).count()
@@ -157,7 +205,8 @@ fn count_stable_adjacent_occupation(grid: &Grid<Seat>) -> usize {
     }
 }
 
-fn count_stable_visible_occupation(grid: &Grid<Seat>) -> usize {
+/// Part two, the occupied seats once the layout stabilises using [`lookup_visible_seats`].
+pub fn count_stable_visible_occupation(grid: &Grid<Seat>) -> usize {
     let (new_grid, mod_count) = iterate_grid(grid, &lookup_visible_seats, 5);
     if mod_count == 0 {
         new_grid.data.iter().filter(|s| **s == OCCUPIED).count()
@@ -197,7 +246,7 @@ L.LLLLL.LL"
     }
 
     #[test]
-    fn can_parse<'a>() {
+    fn can_parse() {
         assert_eq!(
             Grid {
                 row_length: 10,
@@ -214,16 +263,21 @@ L.LLLLL.LL"
                     EMPTY, FLOOR, EMPTY, EMPTY, EMPTY, EMPTY, EMPTY, FLOOR, EMPTY, EMPTY
                 ),
             },
-            parse_grid(input())
-        )
+            parse_grid(input()).unwrap()
+        );
+
+        let err = parse_grid("L.L\n.X.").unwrap_err();
+        assert_eq!((Some(2), Some(2)), (err.line, err.column));
+        assert!(parse_grid("L.L\n.L.L").is_err());
+        assert!(parse_grid("").is_err());
     }
 
     #[test]
     fn can_size_grid() {
         assert_eq!((3, 3), tiny_grid().size());
-        assert_eq!((10, 10), parse_grid(input()).size());
-        assert_eq!((3, 3), parse_grid("###\n###\n#").size());
-        assert_eq!((3, 3), parse_grid("###\n###\n##").size());
+        assert_eq!((10, 10), parse_grid(input()).unwrap().size());
+        assert_eq!((3, 3), parse_grid("###\n###\n#").unwrap().size());
+        assert_eq!((3, 3), parse_grid("###\n###\n##").unwrap().size());
     }
 
     #[test]
@@ -252,11 +306,11 @@ L.LLLLL.LL"
         assert_eq!(Some(OCCUPIED), iterate_cell(&tiny_grid(), 1, 1, &lookup_surrounds, 4));
         assert_eq!(Some(FLOOR), iterate_cell(&tiny_grid(), 2, 2, &lookup_surrounds, 4));
 
-        let empty_grid = parse_grid("L.L\n.L.\nL.L");
+        let empty_grid = parse_grid("L.L\n.L.\nL.L").unwrap();
         assert_eq!(Some(OCCUPIED), iterate_cell(&empty_grid, 1, 1, &lookup_surrounds, 4));
         assert_eq!(Some(OCCUPIED), iterate_cell(&empty_grid, 0, 0, &lookup_surrounds, 4));
 
-        let full_grid = parse_grid("#.#\n.#.\n#.#");
+        let full_grid = parse_grid("#.#\n.#.\n#.#").unwrap();
         assert_eq!(Some(EMPTY), iterate_cell(&full_grid, 1, 1, &lookup_surrounds, 4));
         assert_eq!(Some(OCCUPIED), iterate_cell(&full_grid, 0, 0, &lookup_surrounds, 4));
     }
@@ -273,7 +327,7 @@ L.LLLLL.LL"
 ..#.#.....
 ##########
 #.######.#
-#.#####.##");
+#.#####.##").unwrap();
         let iter_2_expected = parse_grid("#.LL.L#.##
 #LLLLLL.L#
 L.L.L..L..
@@ -283,7 +337,7 @@ L.L.L..L..
 ..L.L.....
 #LLLLLLLL#
 #.LLLLLL.L
-#.#LLLL.##");
+#.#LLLL.##").unwrap();
         let iter_3_expected = parse_grid("#.##.L#.##
 #L###LL.L#
 L.#.#..#..
@@ -293,7 +347,7 @@ L.#.#..#..
 ..#.#.....
 #L######L#
 #.LL###L.L
-#.#L###.##");
+#.#L###.##").unwrap();
         let iter_4_expected = parse_grid("#.#L.L#.##
 #LLL#LL.L#
 L.L.L..#..
@@ -303,7 +357,7 @@ L.L.L..#..
 ..L.L.....
 #L#LLLL#L#
 #.LLLLLL.L
-#.#L#L#.##");
+#.#L#L#.##").unwrap();
         let iter_5_expected = &parse_grid("#.#L.L#.##
 #LLL#LL.L#
 L.#.L..#..
@@ -313,10 +367,10 @@ L.#.L..#..
 ..L.L.....
 #L#L##L#L#
 #.LLLLLL.L
-#.#L#L#.##");
+#.#L#L#.##").unwrap();
 
 
-        let (iter_1_actual, iter_1_count) = iterate_grid(&parse_grid(input()), &lookup_surrounds, 4);
+        let (iter_1_actual, iter_1_count) = iterate_grid(&parse_grid(input()).unwrap(), &lookup_surrounds, 4);
         let (iter_2_actual, iter_2_count) = iterate_grid(&iter_1_actual, &lookup_surrounds, 4);
         let (iter_3_actual, _iter_3_count) = iterate_grid(&iter_2_actual, &lookup_surrounds, 4);
         let (iter_4_actual, _iter_4_count) = iterate_grid(&iter_3_actual, &lookup_surrounds, 4);
@@ -334,19 +388,19 @@ L.#.L..#..
     #[test]
     fn can_count_stable_adjacent_occupation() {
         assert_eq!(1usize, count_stable_adjacent_occupation(&tiny_grid()));
-        assert_eq!(37usize, count_stable_adjacent_occupation(&parse_grid(input())));
+        assert_eq!(37usize, count_stable_adjacent_occupation(&parse_grid(input()).unwrap()));
     }
 
     #[test]
     fn can_look_up_visible_seat() {
-        assert_eq!(Some(EMPTY), lookup_visible_seat(&parse_grid("#L"), 0, 0, 1, 0));
-        assert_eq!(Some(OCCUPIED), lookup_visible_seat(&parse_grid("#L"), 1, 0, -1, 0));
+        assert_eq!(Some(EMPTY), lookup_visible_seat(&parse_grid("#L").unwrap(), 0, 0, 1, 0));
+        assert_eq!(Some(OCCUPIED), lookup_visible_seat(&parse_grid("#L").unwrap(), 1, 0, -1, 0));
 
-        assert_eq!(Some(EMPTY), lookup_visible_seat(&parse_grid("#.L"), 0, 0, 1, 0));
-        assert_eq!(Some(OCCUPIED), lookup_visible_seat(&parse_grid("#.L"), 2, 0, -1, 0));
+        assert_eq!(Some(EMPTY), lookup_visible_seat(&parse_grid("#.L").unwrap(), 0, 0, 1, 0));
+        assert_eq!(Some(OCCUPIED), lookup_visible_seat(&parse_grid("#.L").unwrap(), 2, 0, -1, 0));
 
-        assert_eq!(None, lookup_visible_seat(&parse_grid("#.."), 0, 0, 1, 0));
-        assert_eq!(None, lookup_visible_seat(&parse_grid("..L"), 2, 0, -1, 0));
+        assert_eq!(None, lookup_visible_seat(&parse_grid("#..").unwrap(), 0, 0, 1, 0));
+        assert_eq!(None, lookup_visible_seat(&parse_grid("..L").unwrap(), 2, 0, -1, 0));
     }
 
     #[test]
@@ -364,7 +418,7 @@ L.#.L..#..
 .........
 #........
 ...#....."
-                ),
+                ).unwrap(),
                 3, 4,
             )
         );
@@ -376,7 +430,7 @@ L.#.L..#..
                     ".............
 .L.L.#.#.#.#.
 ............."
-                ),
+                ).unwrap(),
                 1, 1,
             )
         );
@@ -394,7 +448,7 @@ L.#.L..#..
 ##...##
 #.#.#.#
 .##.##."
-                ),
+                ).unwrap(),
                 3, 3,
             )
         );
@@ -412,7 +466,7 @@ L.#.L..#..
 ..#.#.....
 ##########
 #.######.#
-#.#####.##");
+#.#####.##").unwrap();
         let iter_2_expected = parse_grid("#.LL.LL.L#
 #LLLLLL.LL
 L.L.L..L..
@@ -422,7 +476,7 @@ L.LLLLL.LL
 ..L.L.....
 LLLLLLLLL#
 #.LLLLLL.L
-#.LLLLL.L#");
+#.LLLLL.L#").unwrap();
         let iter_3_expected = parse_grid("#.L#.##.L#
 #L#####.LL
 L.#.#..#..
@@ -432,7 +486,7 @@ L.#.#..#..
 ..#.#.....
 LLL####LL#
 #.L#####.L
-#.L####.L#");
+#.L####.L#").unwrap();
         let iter_4_expected = parse_grid("#.L#.L#.L#
 #LLLLLL.LL
 L.L.L..#..
@@ -442,7 +496,7 @@ L.LL.LL.L#
 ..L.L.....
 LLLLLLLLL#
 #.LLLLL#.L
-#.L#LL#.L#");
+#.L#LL#.L#").unwrap();
         let iter_5_expected = parse_grid("#.L#.L#.L#
 #LLLLLL.LL
 L.L.L..#..
@@ -452,7 +506,7 @@ L.L#.#L.L#
 ..#.#.....
 LLL###LLL#
 #.LLLLL#.L
-#.L#LL#.L#");
+#.L#LL#.L#").unwrap();
 
         let iter_6_expected = &parse_grid("#.L#.L#.L#
 #LLLLLL.LL
@@ -463,10 +517,10 @@ L.L#.LL.L#
 ..#.L.....
 LLL###LLL#
 #.LLLLL#.L
-#.L#LL#.L#");
+#.L#LL#.L#").unwrap();
 
 
-        let (iter_1_actual, iter_1_count) = iterate_grid(&parse_grid(input()), &lookup_visible_seats, 5);
+        let (iter_1_actual, iter_1_count) = iterate_grid(&parse_grid(input()).unwrap(), &lookup_visible_seats, 5);
         let (iter_2_actual, iter_2_count) = iterate_grid(&iter_1_actual, &lookup_visible_seats, 5);
         let (iter_3_actual, _iter_3_count) = iterate_grid(&iter_2_actual, &lookup_visible_seats, 5);
         let (iter_4_actual, _iter_4_count) = iterate_grid(&iter_3_actual, &lookup_visible_seats, 5);
@@ -486,6 +540,6 @@ LLL###LLL#
     #[test]
     fn can_count_stable_visible_occupation() {
         assert_eq!(1usize, count_stable_visible_occupation(&tiny_grid()));
-        assert_eq!(26usize, count_stable_visible_occupation(&parse_grid(input())));
+        assert_eq!(26usize, count_stable_visible_occupation(&parse_grid(input()).unwrap()));
     }
 }
@@ -1,8 +1,13 @@
-use std::fs;
+//! This is my solution for [Advent of Code - Day 12](https://adventofcode.com/2020/day/12) -
+//! _Rain Risk_
+
 use day_12::Instruction::*;
+use error::{parse_lines, parse_number, Error, Result};
+use solution::Solution;
 
+/// A navigation instruction, e.g. `F10` is `Forward(10)`.
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
-enum Instruction {
+pub enum Instruction {
     North(isize),
     South(isize),
     East(isize),
@@ -12,8 +17,9 @@ enum Instruction {
     Forward(isize),
 }
 
+/// The position of the ship, and either its heading or the waypoint relative to it.
 #[derive(Debug, Eq, PartialEq)]
-struct Ship {
+pub struct Ship {
     x: isize,
     y: isize,
     facing: Facing,
@@ -33,7 +39,7 @@ impl Facing {
     }
     
     fn rotate(&self, degrees: isize) -> Facing {
-        match degrees % 360 { 
+        match degrees.rem_euclid(360) { 
             0 => Facing {dx: self.dx, dy: self.dy},
             90 => Facing {dx: -self.dy, dy: self.dx},
             180 => Facing {dx: -self.dx, dy: -self.dy},
@@ -51,15 +57,18 @@ impl Facing {
 }
 
 impl Ship {
-    fn new() -> Ship {
+    /// A ship at the origin facing east.
+    pub fn new() -> Ship {
         Ship { x: 0, y: 0, facing: Facing::EAST }
     }
 
-    fn new_waypoint() -> Ship {
+    /// A ship at the origin with a waypoint 10 east and 1 north of it.
+    pub fn new_waypoint() -> Ship {
         Ship { x: 0, y: 0, facing: Facing { dx: 10, dy: -1 } }
     }
 
-    fn navigate(&mut self, inst: Instruction) {
+    /// Part one, move the ship or change its heading.
+    pub fn navigate(&mut self, inst: Instruction) {
         match inst {
             North(n) => self.advance(Facing::NORTH.multiply(n)),
             East(n) => self.advance(Facing::EAST.multiply(n)),
@@ -71,11 +80,13 @@ impl Ship {
         }
     }
 
-    fn navigate_all(&mut self, instructions: &Vec<Instruction>) {
+    /// Apply [`Ship::navigate`] to each instruction in turn.
+    pub fn navigate_all(&mut self, instructions: &[Instruction]) {
         instructions.iter().for_each(|&i| self.navigate(i))
     }
 
-    fn navigate_with_waypoint(&mut self, inst: Instruction) {
+    /// Part two, move the waypoint, or move the ship towards it.
+    pub fn navigate_with_waypoint(&mut self, inst: Instruction) {
         match inst {
             North(n) => self.facing = self.facing.merge(Facing::NORTH.multiply(n)),
             East(n) => self.facing = self.facing.merge(Facing::EAST.multiply(n)),
@@ -87,48 +98,80 @@ impl Ship {
         }
     }
 
-    fn navigate_all_with_waypoint(&mut self, instructions: &Vec<Instruction>) {
+    /// Apply [`Ship::navigate_with_waypoint`] to each instruction in turn.
+    pub fn navigate_all_with_waypoint(&mut self, instructions: &[Instruction]) {
         instructions.iter().for_each(|&i| self.navigate_with_waypoint(i))
     }
     
     fn advance(&mut self, vector: Facing) {
-        self.x = self.x + vector.dx;
-        self.y = self.y + vector.dy;
+        self.x += vector.dx;
+        self.y += vector.dy;
     }
 
-    fn manhattan_distance(&self) -> usize {
+    /// The manhattan distance of the ship from the origin.
+    pub fn manhattan_distance(&self) -> usize {
         (self.x.abs() + self.y.abs()) as usize
     }
 }
 
-pub fn run() {
-    let contents = fs::read_to_string("res/day-12-input").expect("Failed to read file");
-    let instructions = parse_input(contents.as_str());
+impl Default for Ship {
+    fn default() -> Ship {
+        Ship::new()
+    }
+}
+
+/// The [`Solution`] for day 12, works out where the ferry ends up.
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input<'a> = Vec<Instruction>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-    let mut ship = Ship::new();
-    ship.navigate_all(&instructions);
-    println!("{:?} has a manhattan distance of {} from its starting position.", ship, ship.manhattan_distance());
+    fn parse(&self, input: &str) -> Result<Vec<Instruction>> {
+        parse_input(input)
+    }
 
-    let mut waypoint_ship = Ship::new_waypoint();
-    waypoint_ship.navigate_all_with_waypoint(&instructions);
-    println!("Using a waypoint, {:?} has a manhattan distance of {} from its starting position.", waypoint_ship, waypoint_ship.manhattan_distance());
+    fn part_one(&self, instructions: &Vec<Instruction>) -> Result<usize> {
+        let mut ship = Ship::new();
+        ship.navigate_all(instructions);
+        Ok(ship.manhattan_distance())
+    }
+
+    fn part_two(&self, instructions: &Vec<Instruction>) -> Result<usize> {
+        let mut waypoint_ship = Ship::new_waypoint();
+        waypoint_ship.navigate_all_with_waypoint(instructions);
+        Ok(waypoint_ship.manhattan_distance())
+    }
 }
 
-fn parse_input(input: &str) -> Vec<Instruction> {
-    input.lines()
-        .map(|line| line.split_at(1))
-        .map(|(letter, number)| (letter.chars().next().unwrap(), number.parse::<isize>().unwrap()))
-        .map(|(instruction, magnitude)| match instruction {
-            'N' => North(magnitude),
-            'S' => South(magnitude),
-            'E' => East(magnitude),
-            'W' => West(magnitude),
-            'L' => Left(magnitude),
-            'R' => Right(magnitude),
-            'F' => Forward(magnitude),
-            _ => panic!(format!("Invalid instruction {}", magnitude))
-        })
-        .collect()
+/// Parse the navigation instructions, one per line. Turns must be a multiple of 90 degrees.
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>> {
+    parse_lines(input, parse_instruction)
+}
+
+fn parse_instruction(line: &str) -> Result<Instruction> {
+    let instruction = line.chars().next().ok_or_else(|| Error::invalid(line, "Empty instruction"))?;
+    let (_, number) = line.split_at(instruction.len_utf8());
+    let magnitude: isize = parse_number(number, line, 2)?;
+
+    if magnitude < 0 {
+        return Err(Error::at_column(line, 2, "Expected a positive magnitude"));
+    }
+    if (instruction == 'L' || instruction == 'R') && magnitude % 90 != 0 {
+        return Err(Error::at_column(line, 2, format!("Can only turn in multiples of 90°, not {}°", magnitude)));
+    }
+
+    match instruction {
+        'N' => Ok(North(magnitude)),
+        'S' => Ok(South(magnitude)),
+        'E' => Ok(East(magnitude)),
+        'W' => Ok(West(magnitude)),
+        'L' => Ok(Left(magnitude)),
+        'R' => Ok(Right(magnitude)),
+        'F' => Ok(Forward(magnitude)),
+        other => Err(Error::at_column(line, 1, format!("Invalid instruction '{}'", other)))
+    }
 }
 
 #[cfg(test)]
@@ -146,8 +189,13 @@ N3
 F7
 R90
 F11"
-            )
-        )
+            ).unwrap()
+        );
+
+        let err = parse_input("F10\nZ3").unwrap_err();
+        assert_eq!((Some(2), Some(1)), (err.line, err.column));
+        assert_eq!(Some(2), parse_input("R45").unwrap_err().column);
+        assert_eq!(Some(2), parse_input("Nx").unwrap_err().column);
     }
 
     #[test]
@@ -187,19 +235,19 @@ F11"
 
         let mut other = Ship::new();
         other.navigate_all(
-            &vec!(
+            &[
                 Left(90),
                 Left(180),
                 Left(270),
-            )
+            ]
         );
         assert_eq!(Facing::WEST, other.facing);
         other.navigate_all(
-            &vec!(
+            &[
                 Right(90),
                 Right(180),
                 Right(270),
-            )
+            ]
         );
         assert_eq!(Facing::EAST, other.facing);
     }
@@ -221,7 +269,7 @@ N3
 F7
 R90
 F11"
-        ));
+        ).unwrap());
 
         assert_eq!(Ship { x: 17, y: 8, facing: Facing::SOUTH }, ship);
         assert_eq!(25, ship.manhattan_distance());
@@ -236,7 +284,7 @@ N3
 F7
 R90
 F11"
-        ));
+        ).unwrap());
 
         assert_eq!(Ship { x: 214, y: 72, facing: Facing {dx: 4, dy: 10} }, ship);
         assert_eq!(286, ship.manhattan_distance());
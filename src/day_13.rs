@@ -12,23 +12,35 @@
 //! however quick enough to calculate for any pair of busses, and from that build a much faster
 //! recursive solution.
 
-use std::fs;
+use error::{parse_number, Error, Result};
+use solution::Solution;
 
-/// The entry point for running the solutions with the 'real' puzzle input.
+/// The [`Solution`] for day 13.
 ///
-/// - The puzzle input is expected to be at `<project_root>/res/day-13-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day 13.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-13-input").expect("Failed to read file");
-    let (timestamp, bus_ids) = parse_input(contents.as_str());
-    let (bus_id, wait) = find_best_departure(
-        timestamp,
-        bus_ids.iter().map(|(_, bus_id)| *bus_id).collect(),
-    );
-    println!("The next bus: {} x wait time: {} minutes = {}", bus_id, wait, bus_id * wait);
-
-    let sequence_start = find_sequential_departure(bus_ids);
-    println!("The first sequential start begins at timestamp {}", sequence_start)
+/// - Part one is the next bus ID multiplied by the wait time, see [`find_best_departure`].
+/// - Part two is the first timestamp that starts the sequence, see [`find_sequential_departure`].
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input<'a> = (usize, Vec<(usize, usize)>);
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<(usize, Vec<(usize, usize)>)> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, (timestamp, bus_ids): &(usize, Vec<(usize, usize)>)) -> Result<usize> {
+        let (bus_id, wait) = find_best_departure(
+            *timestamp,
+            bus_ids.iter().map(|(_, bus_id)| *bus_id).collect(),
+        );
+        Ok(bus_id * wait)
+    }
+
+    fn part_two(&self, (_, bus_ids): &(usize, Vec<(usize, usize)>)) -> Result<usize> {
+        Ok(find_sequential_departure(bus_ids.clone()))
+    }
 }
 
 /// Takes the puzzle input and returns the starting timestamp, and a list of bus IDs
@@ -37,27 +49,40 @@ pub fn run() {
 /// > earliest timestamp you could depart on a bus. The second line lists the bus IDs that are in
 /// > service according to the shuttle company; entries that show x must be out of service, so you
 /// > decide to ignore them.
-fn parse_input(input: &str) -> (usize, Vec<(usize, usize)>) {
+///
+/// Bus IDs must be positive, and there must be at least one bus in service.
+pub fn parse_input(input: &str) -> Result<(usize, Vec<(usize, usize)>)> {
     let mut lines = input.lines();
-    let timestamp =
-        lines.next().expect("Missing line 1")
-            .parse::<usize>().expect("Line 1 was not a valid timestamp");
-
-    let bus_ids: Vec<(usize, usize)> =
-        lines.next().expect("Missing line 2")
-            .split(',')
-            .enumerate()
-            .filter_map(|(index, id)| id.parse::<usize>().ok().map(|bus_id| (index, bus_id)))
-            .collect();
-
-    (timestamp, bus_ids)
+    let timestamp_line = lines.next().ok_or_else(|| Error::new("Missing line 1"))?;
+    let timestamp = parse_number(timestamp_line, timestamp_line, 1).map_err(|err| err.on_line(1, timestamp_line))?;
+
+    let bus_line = lines.next().ok_or_else(|| Error::new("Missing line 2"))?;
+    let mut bus_ids: Vec<(usize, usize)> = Vec::new();
+    let mut column = 1;
+
+    for (index, id) in bus_line.split(',').enumerate() {
+        if id != "x" {
+            let bus_id = parse_number(id, bus_line, column).map_err(|err| err.on_line(2, bus_line))?;
+            if bus_id == 0 {
+                return Err(Error::at_column(bus_line, column, "Bus IDs must be positive").on_line(2, bus_line));
+            }
+            bus_ids.push((index, bus_id));
+        }
+        column += id.len() + 1;
+    }
+
+    if bus_ids.is_empty() {
+        return Err(Error::invalid(bus_line, "No buses are in service").on_line(2, bus_line));
+    }
+
+    Ok((timestamp, bus_ids))
 }
 
 /// Calculate the wait time from timestamp until bus_id will depart
 /// > The time this loop takes a particular bus is also its ID number: the bus with ID 5 departs
 /// > from the sea port at timestamps 0, 5, 10, 15, and so on. The bus with ID 11 departs at 0, 11,
 /// > 22, 33, and so on. If you are there when the bus departs, you can ride that bus to the airport!
-fn next_departure(timestamp: usize, bus_id: usize) -> usize {
+pub fn next_departure(timestamp: usize, bus_id: usize) -> usize {
     if timestamp == 0 {
         return 0;
     }
@@ -68,7 +93,7 @@ fn next_departure(timestamp: usize, bus_id: usize) -> usize {
 /// The solution to part 1 - which is the next bus to depart after your  arrival time?
 /// If you can arrive at the bus stop at earliest time, what is the next bus you can take, and how
 /// long will you need to wait.
-fn find_best_departure(earliest_time: usize, bus_ids: Vec<usize>) -> (usize, usize) {
+pub fn find_best_departure(earliest_time: usize, bus_ids: Vec<usize>) -> (usize, usize) {
     let (best_id, departure_time) =
         bus_ids.iter().map(|id| (id, next_departure(earliest_time, *id)))
             .min_by_key(|(_, time)| *time)
@@ -99,7 +124,7 @@ fn find_best_departure(earliest_time: usize, bus_ids: Vec<usize>) -> (usize, usi
 /// Given a sequence `x,2,3` i.e 2 with an offset of 1 three with an offset of 2. Then this happens
 /// with a base timestamp of 1, and then 7:
 ///
-/// ```
+/// ```text
 /// t  Valid?    ID:2    ID:3                    ID:6  |
 /// ---------------------------------------------------|
 /// 0             X       X                       X    |
@@ -134,7 +159,7 @@ fn find_sequential_departure_iter(acc: (usize, usize), next: (usize, usize), rem
     let mut base_offset;
 
     loop {
-        position = position + period_a;
+        position += period_a;
         // prevent -ve starts
         if position < offset_a {
             continue
@@ -165,9 +190,9 @@ fn find_sequential_departure_iter(acc: (usize, usize), next: (usize, usize), rem
     }
 }
 
-/// The solution to part 2. Sets up the data for ['find_sequential_departure_iter`] and delegates
-fn find_sequential_departure(bus_ids: Vec<(usize, usize)>) -> usize {
-    let &(pos, first_bus) = bus_ids.get(0).expect("First bus id empty");
+/// The solution to part 2. Sets up the data for [`find_sequential_departure_iter`] and delegates
+pub fn find_sequential_departure(bus_ids: Vec<(usize, usize)>) -> usize {
+    let &(pos, first_bus) = bus_ids.first().expect("First bus id empty");
     find_sequential_departure_iter((0, 1),  (pos, first_bus), bus_ids.iter().skip(1).collect())
 }
 
@@ -181,9 +206,14 @@ mod tests {
 7,13,x,x,59,x,31,19";
 
         assert_eq!(
-            (939usize, vec!((0, 7), (1, 13), (4, 59), (6, 31), (7, 19))),
+            Ok((939usize, vec!((0, 7), (1, 13), (4, 59), (6, 31), (7, 19)))),
             parse_input(input)
-        )
+        );
+
+        let err = parse_input("939\n7,13,x,y,59").unwrap_err();
+        assert_eq!((Some(2), Some(8)), (err.line, err.column));
+        assert!(parse_input("939").is_err());
+        assert!(parse_input("939\nx,x").is_err());
     }
 
     #[test]
@@ -7,35 +7,44 @@
 //! in a variable that is explicitly a Mask rather than an Instruction that could be either a Mask
 //! or a Mem.
 
-use std::fs;
 use regex::Regex;
 use im::{HashMap, HashSet};
 use either::Either;
 use either::Either::*;
+use error::{parse_number, Error, Result};
+use solution::Solution;
 
-/// The entry point for running the solutions with the 'real' puzzle input.
+/// The [`Solution`] for day 14.
 ///
-/// - The puzzle input is expected to be at `<project_root>/res/day-14-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day 14.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-14-input").expect("Failed to read file");
-
-    let memory = run_program_v1(contents.as_str());
-    let sum = sum_memory(memory);
-    println!("The sum of memory values after running the program v1 is: {}", sum);
-
-    let memory = run_program_v2(contents.as_str());
-    let sum = sum_memory(memory);
-    println!("The sum of memory values after running the program v2 is: {}", sum);
+/// - The program is interpreted as it is run, so parsing is deferred to [`run_program_v1`] and
+///   [`run_program_v2`].
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input<'a> = &'a str;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<&'a str> {
+        Ok(input)
+    }
+
+    fn part_one(&self, program: &&str) -> Result<usize> {
+        Ok(sum_memory(run_program_v1(program)?))
+    }
+
+    fn part_two(&self, program: &&str) -> Result<usize> {
+        Ok(sum_memory(run_program_v2(program)?))
+    }
 }
 
 /// Representing an input line that overwrites the current bitmask, see [`parse_line`].
 #[derive(Debug, Eq, PartialEq)]
-struct Mask { mask: usize, data: usize }
+pub struct Mask { pub mask: usize, pub data: usize }
 
 /// Represents an input line that updates the current memory values, see [`parse_line`].
 #[derive(Debug, Eq, PartialEq)]
-struct Mem { address: usize, value: usize }
+pub struct Mem { pub address: usize, pub value: usize }
 
 /// Parse a line from the puzzle input into structured data
 ///
@@ -55,59 +64,74 @@ struct Mem { address: usize, value: usize }
 ///
 /// # Examples from Tests
 /// ```
+/// # extern crate im;
+/// # extern crate either;
+/// # use im::{HashMap, HashSet};
+/// # use either::Either::*;
+/// # use advent_of_code_2020::day_14::*;
 /// assert_eq!(
 ///     Left(Mask {
 ///         mask: 0b111111111111111111111111111111111111,
 ///         data: 0b000000000000000000000000000000000000,
 ///     }),
-///     parse_line("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX")
+///     parse_line("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX").unwrap()
 /// );
 /// assert_eq!(
 ///     Left(Mask {
 ///         mask: 0b111111111111111111111111111110111101,
 ///         data: 0b000000000000000000000000000001000000,
 ///     }),
-///     parse_line("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X")
+///     parse_line("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").unwrap()
 /// );
 ///
 /// assert_eq!(
 ///     Right(Mem { address: 8, value: 11 }),
-///     parse_line("mem[8] = 11")
+///     parse_line("mem[8] = 11").unwrap()
 /// );
 /// assert_eq!(
 ///     Right(Mem { address: 7, value: 101 }),
-///     parse_line("mem[7] = 101")
+///     parse_line("mem[7] = 101").unwrap()
 /// );
 /// assert_eq!(
 ///     Right(Mem { address: 8, value: 0 }),
-///     parse_line("mem[8] = 0")
+///     parse_line("mem[8] = 0").unwrap()
 /// );
 /// ```
-fn parse_line(line: &str) -> Either<Mask, Mem> {
-    let mut parts = line.split(" = ");
-    let inst = parts.next().expect("Invalid line");
-    let value = parts.next().expect("Invalid line");
+pub fn parse_line(line: &str) -> Result<Either<Mask, Mem>> {
+    let separator = " = ";
+    let split = line.find(separator)
+        .ok_or_else(|| Error::invalid(line, "Expected '<instruction> = <value>'"))?;
+    let inst = &line[..split];
+    let value = &line[split + separator.len()..];
+    let value_column = split + separator.len() + 1;
 
     if inst == "mask" {
+        if value.len() != 36 {
+            return Err(Error::at_column(line, value_column, "Expected a 36 bit mask"));
+        }
+
         let (mask, data) =
-            value.chars().fold(
+            value.chars().enumerate().try_fold(
                 (0usize, 0usize),
-                |(mask, data), char| (
-                    mask << 1 | if char == 'X' { 1 } else { 0 },
-                    data << 1 | if char == '1' { 1 } else { 0 }
-                ),
-            );
+                |(mask, data), (index, char)| match char {
+                    'X' | '0' | '1' => Ok((
+                        mask << 1 | if char == 'X' { 1 } else { 0 },
+                        data << 1 | if char == '1' { 1 } else { 0 }
+                    )),
+                    other => Err(Error::at_column(line, value_column + index, format!("Invalid mask bit '{}'", other))),
+                },
+            )?;
 
-        Left(Mask { mask, data })
+        Ok(Left(Mask { mask, data }))
     } else {
         let re = Regex::new(r"^mem\[(\d+)]$").unwrap();
 
         match re.captures(inst) {
-            Some(cap) => Right(Mem {
-                address: cap.get(1).unwrap().as_str().parse::<usize>().unwrap(),
-                value: value.parse::<usize>().unwrap(),
-            }),
-            None => panic!("Invalid line")
+            Some(cap) => Ok(Right(Mem {
+                address: parse_number(cap.get(1).unwrap().as_str(), line, 5)?,
+                value: parse_number(value, line, value_column)?,
+            })),
+            None => Err(Error::at_column(line, 1, format!("Expected 'mask' or 'mem[<address>]', found '{}'", inst)))
         }
     }
 }
@@ -121,12 +145,17 @@ fn parse_line(line: &str) -> Either<Mask, Mem> {
 ///
 /// # Example from Tests
 /// ```
+/// # extern crate im;
+/// # extern crate either;
+/// # use im::{HashMap, HashSet};
+/// # use either::Either::*;
+/// # use advent_of_code_2020::day_14::*;
 /// let mut expected: HashMap<usize, usize> = HashMap::new();
 ///
 /// let program_1 = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\nmem[8] = 11";
 ///
 /// expected.insert(8, 73);
-/// assert_eq!(expected, run_program_v1(program_1));
+/// assert_eq!(expected, run_program_v1(program_1).unwrap());
 ///
 /// let program_2 = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
 /// mem[8] = 11
@@ -135,18 +164,18 @@ fn parse_line(line: &str) -> Either<Mask, Mem> {
 ///
 /// expected.insert(7, 101);
 /// expected.insert(8, 64);
-/// let memory = run_program_v1(program_2);
+/// let memory = run_program_v1(program_2).unwrap();
 ///
 /// assert_eq!(expected, memory);
 ///
 /// assert_eq!(165usize, sum_memory(memory));
 /// ```
-fn run_program_v1(program: &str) -> HashMap<usize, usize> {
+pub fn run_program_v1(program: &str) -> Result<HashMap<usize, usize>> {
     let mut memory = HashMap::new();
     let mut current_mask = Mask { mask: 0, data: 0 };
 
-    for line in program.lines() {
-        match parse_line(line) {
+    for (index, line) in program.lines().enumerate() {
+        match parse_line(line).map_err(|err| err.on_line(index + 1, line))? {
             Left(Mask { mask, data }) => current_mask = Mask { mask, data },
             Right(Mem { address, value }) => {
                 memory.insert(
@@ -157,7 +186,7 @@ fn run_program_v1(program: &str) -> HashMap<usize, usize> {
         }
     }
 
-    return memory;
+    Ok(memory)
 }
 
 /// Takes the string input and returns the memory state after that has been interpreted using the
@@ -177,20 +206,25 @@ fn run_program_v1(program: &str) -> HashMap<usize, usize> {
 ///
 /// # Example from Tests
 /// ```
+/// # extern crate im;
+/// # extern crate either;
+/// # use im::{HashMap, HashSet};
+/// # use either::Either::*;
+/// # use advent_of_code_2020::day_14::*;
 /// let program = "mask = 000000000000000000000000000000X1001X
 /// mem[42] = 100
 /// mask = 00000000000000000000000000000000X0XX
 /// mem[26] = 1";
 ///
-/// let memory = run_program_v2(program);
+/// let memory = run_program_v2(program).unwrap();
 /// assert_eq!(208usize, sum_memory(memory));
 /// ```
-fn run_program_v2(program: &str) -> HashMap<usize, usize> {
+pub fn run_program_v2(program: &str) -> Result<HashMap<usize, usize>> {
     let mut memory = HashMap::new();
     let mut current_mask = Mask { mask: 0, data: 0 };
 
-    for line in program.lines() {
-        match parse_line(line) {
+    for (index, line) in program.lines().enumerate() {
+        match parse_line(line).map_err(|err| err.on_line(index + 1, line))? {
             Left(Mask { mask, data }) => current_mask = Mask { mask, data },
             Right(Mem { address, value }) =>
                 for address in explode_addresses(&current_mask, address) {
@@ -199,7 +233,7 @@ fn run_program_v2(program: &str) -> HashMap<usize, usize> {
         }
     }
 
-    return memory;
+    Ok(memory)
 }
 
 /// Because floating bits can take on any value, this returns all the addresses that a given mask
@@ -215,6 +249,11 @@ fn run_program_v2(program: &str) -> HashMap<usize, usize> {
 ///
 /// # Examples from Tests
 /// ```
+/// # extern crate im;
+/// # extern crate either;
+/// # use im::{HashMap, HashSet};
+/// # use either::Either::*;
+/// # use advent_of_code_2020::day_14::*;
 /// let expected: HashSet<usize> = vec!(26usize, 27usize, 58usize, 59usize).into_iter().collect();
 /// assert_eq!(
 ///     expected,
@@ -233,13 +272,13 @@ fn run_program_v2(program: &str) -> HashMap<usize, usize> {
 /// assert_eq!(
 ///     expected,
 ///     explode_addresses(
-///         &parse_line("mask = 00000000000000000000000000000000X0XX")
+///         &parse_line("mask = 00000000000000000000000000000000X0XX").unwrap()
 ///             .expect_left("Failed to parse as mask"),
 ///         26,
 ///     )
 /// );
 /// ```
-fn explode_addresses(mask: &Mask, input: usize) -> HashSet<usize> {
+pub fn explode_addresses(mask: &Mask, input: usize) -> HashSet<usize> {
     let mut addresses = HashSet::new();
     addresses.insert((input | mask.data) & !mask.mask);
 
@@ -264,7 +303,7 @@ fn explode_addresses(mask: &Mask, input: usize) -> HashSet<usize> {
 ///
 /// Both puzzle parts finally sum all the memory registers into a single number as the expected
 /// answer. Extracted into a function to avoid repetition.
-fn sum_memory(memory: HashMap<usize, usize>) -> usize {
+pub fn sum_memory(memory: HashMap<usize, usize>) -> usize {
     memory.iter().map(|(_, v)| *v).sum()
 }
 
@@ -282,27 +321,32 @@ mod tests {
                 mask: 0b111111111111111111111111111111111111,
                 data: 0b000000000000000000000000000000000000,
             }),
-            parse_line("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX")
+            parse_line("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX").unwrap()
         );
         assert_eq!(
             Left(Mask {
                 mask: 0b111111111111111111111111111110111101,
                 data: 0b000000000000000000000000000001000000,
             }),
-            parse_line("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X")
+            parse_line("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").unwrap()
         );
         assert_eq!(
             Right(Mem { address: 8, value: 11 }),
-            parse_line("mem[8] = 11")
+            parse_line("mem[8] = 11").unwrap()
         );
         assert_eq!(
             Right(Mem { address: 7, value: 101 }),
-            parse_line("mem[7] = 101")
+            parse_line("mem[7] = 101").unwrap()
         );
         assert_eq!(
             Right(Mem { address: 8, value: 0 }),
-            parse_line("mem[8] = 0")
+            parse_line("mem[8] = 0").unwrap()
         );
+
+        assert_eq!(Some(12), parse_line("mask = XXXX2XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX").unwrap_err().column);
+        assert_eq!(Some(1), parse_line("mam[8] = 11").unwrap_err().column);
+        assert_eq!(Some(10), parse_line("mem[8] = -11").unwrap_err().column);
+        assert!(parse_line("mem[8] 11").is_err());
     }
 
     //noinspection SpellCheckingInspection
@@ -312,7 +356,7 @@ mod tests {
 
         let program_1 = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\nmem[8] = 11";
         expected.insert(8, 73);
-        assert_eq!(expected, run_program_v1(program_1));
+        assert_eq!(expected, run_program_v1(program_1).unwrap());
 
         let program_2 = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
 mem[8] = 11
@@ -320,7 +364,7 @@ mem[7] = 101
 mem[8] = 0";
         expected.insert(7, 101);
         expected.insert(8, 64);
-        let memory = run_program_v1(program_2);
+        let memory = run_program_v1(program_2).unwrap();
         assert_eq!(expected, memory);
 
         assert_eq!(165usize, sum_memory(memory));
@@ -348,7 +392,7 @@ mem[8] = 0";
         assert_eq!(
             expected,
             explode_addresses(
-                &parse_line("mask = 00000000000000000000000000000000X0XX")
+                &parse_line("mask = 00000000000000000000000000000000X0XX").unwrap()
                     .expect_left("Failed to parse as mask"),
                 26,
             )
@@ -362,7 +406,7 @@ mem[42] = 100
 mask = 00000000000000000000000000000000X0XX
 mem[26] = 1";
 
-        let memory = run_program_v2(program);
+        let memory = run_program_v2(program).unwrap();
 
         assert_eq!(208usize, sum_memory(memory));
     }
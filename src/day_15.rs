@@ -10,29 +10,50 @@
 //! All of the work is done in [`play_memory_game`], which worked for both parts. The main
 //! awkwardness was eliminating out by 1 errors, but the tests highlighted all of those quickly.
 
-/// The entry point for running the solutions with the 'real' puzzle input.
+use error::{parse_number, Result};
+use solution::Solution;
+
+/// The [`Solution`] for day 15.
 ///
-/// - The puzzle input is expected to be at `<project_root>/res/day-15-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day 15.
-pub fn run() {
-    let contents = "8,11,0,19,1,2";
+/// - Part one is the 2020th number spoken, part two the 30,000,000th.
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input<'a> = Vec<u32>;
+    type PartOne = u32;
+    type PartTwo = u32;
 
-    let result_2020 = play_memory_game(parse(contents), 2020);
-    println!("The 2020th number is: {}", result_2020);
+    fn parse(&self, input: &str) -> Result<Vec<u32>> {
+        parse(input.trim())
+    }
 
-    let result_30m = play_memory_game(parse(contents), 30000000);
-    println!("The 30,000,000th number is: {}", result_30m);
+    fn part_one(&self, seed: &Vec<u32>) -> Result<u32> {
+        Ok(play_memory_game(seed.clone(), 2020))
+    }
+
+    fn part_two(&self, seed: &Vec<u32>) -> Result<u32> {
+        Ok(play_memory_game(seed.clone(), 30000000))
+    }
 }
 
 /// Parses the seed string into a usable Vec
 ///
 /// # Examples from test
 /// ```
-/// assert_eq!(vec!(0u32, 3u32, 6u32), parse("0,3,6"));
-/// assert_eq!(vec!(1u32, 2u32, 3u32), parse("1,2,3"));
+/// # use advent_of_code_2020::day_15::*;
+/// assert_eq!(Ok(vec!(0u32, 3u32, 6u32)), parse("0,3,6"));
+/// assert_eq!(Ok(vec!(1u32, 2u32, 3u32)), parse("1,2,3"));
 /// ```
-pub fn parse(input: &str) -> Vec<u32> {
-    input.split(',').map(|n| n.parse().unwrap()).collect()
+pub fn parse(input: &str) -> Result<Vec<u32>> {
+    let mut column = 1;
+
+    input.split(',')
+        .map(|n| {
+            let number = parse_number(n, input, column).map_err(|err| err.on_line(1, input));
+            column += n.len() + 1;
+            number
+        })
+        .collect()
 }
 
 /// Solution to both parts
@@ -75,6 +96,7 @@ pub fn parse(input: &str) -> Vec<u32> {
 ///
 /// # Examples from tests
 /// ```
+/// # use advent_of_code_2020::day_15::*;
 /// assert_eq!(0, play_memory_game(vec!(0, 3, 6), 1));
 /// assert_eq!(3, play_memory_game(vec!(0, 3, 6), 2));
 /// assert_eq!(6, play_memory_game(vec!(0, 3, 6), 3));
@@ -134,8 +156,9 @@ mod tests {
 
     #[test]
     fn can_parse() {
-        assert_eq!(vec!(0u32, 3u32, 6u32), parse("0,3,6"));
-        assert_eq!(vec!(1u32, 2u32, 3u32), parse("1,2,3"));
+        assert_eq!(Ok(vec!(0u32, 3u32, 6u32)), parse("0,3,6"));
+        assert_eq!(Ok(vec!(1u32, 2u32, 3u32)), parse("1,2,3"));
+        assert_eq!(Some(5), parse("1,2,x").unwrap_err().column);
     }
 
     #[test]
@@ -9,59 +9,63 @@
 //!
 //! Part 2 is mostly handled by [`get_valid_positions`] with help from [`map_ticket`]. Also there
 //! was more work to be done than usual to turn the puzzle solution into a single number output for
-//! submission in [`run`].
+//! submission in [`Day16`].
 
-use std::fs;
 use std::collections::{HashMap, HashSet};
 use regex::Regex;
+use elimination::eliminate;
+use error::{parse_lines, parse_number, Error, Result};
+use solution::Solution;
 
-/// The entry point for running the solutions with the 'real' puzzle input.
+/// The [`Solution`] for day 16.
 ///
-/// - The puzzle input is expected to be at `<project_root>/res/day-16-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day 16.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-16-input").expect("Failed to read file");
-    let (constraints, my_ticket, tickets) = parse_input(contents.as_str());
-    let invalid = get_scan_error_rate(&constraints, &tickets);
-    println!("The scan error rate was: {}", invalid.iter().sum::<usize>());
-
-    let mapping = get_valid_positions(&constraints, &tickets);
-    let mapped_ticket = map_ticket(mapping, my_ticket);
-
-    let departure_location = mapped_ticket.get("departure location").expect("missing departure location");
-    let departure_station = mapped_ticket.get("departure station").expect("missing departure station");
-    let departure_platform = mapped_ticket.get("departure platform").expect("missing departure platform");
-    let departure_track = mapped_ticket.get("departure track").expect("missing departure track");
-    let departure_date = mapped_ticket.get("departure date").expect("missing departure date");
-    let departure_time = mapped_ticket.get("departure time").expect("missing departure time");
-
-    println!(
-        "location: {} x station: {} x platform: {} x track: {} x date: {} x time: {} = {}",
-        departure_location,
-        departure_station,
-        departure_platform,
-        departure_track,
-        departure_date,
-        departure_time,
-        departure_location * departure_station * departure_platform * departure_track * departure_date * departure_time
-    );
+/// - Part two is the product of the six `departure` fields on my ticket.
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'a> = Notes<'a>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, (constraints, _, tickets): &Self::Input<'_>) -> Result<usize> {
+        Ok(get_scan_error_rate(constraints, tickets).iter().sum())
+    }
+
+    fn part_two(&self, (constraints, my_ticket, tickets): &Self::Input<'_>) -> Result<usize> {
+        let mapping = get_valid_positions(constraints, tickets)?;
+        let mapped_ticket = map_ticket(mapping, my_ticket.clone())?;
+
+        Ok(
+            mapped_ticket.iter()
+                .filter(|(field, _)| field.starts_with("departure"))
+                .map(|(_, value)| value)
+                .product()
+        )
+    }
 }
 
+/// The parsed puzzle input: the field constraints, my ticket, and the nearby tickets.
+pub type Notes<'a> = (HashMap<&'a str, Constraint>, Vec<usize>, Vec<Vec<usize>>);
+
 /// Holds constraints on a fields value
 ///
 /// The constraints in the input file all have the format `class: 1-3 or 5-7`. For that example this
 /// would be Constraint { (1,3), (5,7) }
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
-struct Constraint {
-    lower_range: (usize, usize),
-    upper_range: (usize, usize),
+pub struct Constraint {
+    pub lower_range: (usize, usize),
+    pub upper_range: (usize, usize),
 }
 
 impl Constraint {
     /// Given a field value, does it fall within either of the constraint's ranges?
     ///
     /// Ranges are inclusive at both ends
-    pub(crate) fn validate(&self, number: usize) -> bool {
+    pub fn validate(&self, number: usize) -> bool {
         (number >= self.lower_range.0 && number <= self.lower_range.1)
             || (number >= self.upper_range.0 && number <= self.upper_range.1)
     }
@@ -81,8 +85,12 @@ impl Constraint {
 /// __Nearby Tickets__: One ticket per line, each is a list of comma separated, unlabelled field
 /// values. See also [`parse_ticket`].
 ///
+/// Errors report their line number within the whole input, not just the section they were found in.
+///
 /// # Examples from Tests
 /// ```
+/// # use std::collections::HashMap;
+/// # use advent_of_code_2020::day_16::*;
 /// let input = "class: 1-3 or 5-7
 /// row: 6-11 or 33-44
 /// seat: 13-40 or 45-50
@@ -111,49 +119,67 @@ impl Constraint {
 ///         vec!(38usize, 6usize, 12usize)
 ///     )
 /// );
-/// assert_eq!(expected, parse_input(input));
+/// assert_eq!(Ok(expected), parse_input(input));
 /// ```
-fn parse_input(input: &str) -> (HashMap<&str, Constraint>, Vec<usize>, Vec<Vec<usize>>) {
+pub fn parse_input(input: &str) -> Result<Notes<'_>> {
     let mut parts = input.split("\n\n");
-    let constraints = parse_constraints(parts.next().expect("Invalid input - missing part 1"));
-    let my_ticket = parse_ticket(
-        parts.next().expect("Invalid input - missing part 2")
-            .lines().nth(1).expect("Invalid input, failed to find my ticket numbers")
-    );
-    let other_tickets =
-        parts.next().expect("Invalid input - missing part 3")
-            .lines().skip(1).map(|line| parse_ticket(line))
-            .collect();
-
-    (constraints, my_ticket, other_tickets)
+
+    let constraints_section = parts.next().unwrap_or_default();
+    let constraints = parse_constraints(constraints_section)?;
+    // the line before the section starts, accounting for the blank separator line
+    let mut offset = constraints_section.lines().count() + 1;
+
+    let my_section = parts.next().ok_or_else(|| Error::new("Missing the 'your ticket' section"))?;
+    let my_line = my_section.lines().nth(1)
+        .ok_or_else(|| Error::new("Missing your ticket's numbers").on_line(offset + 1, my_section))?;
+    let my_ticket = parse_ticket(my_line).map_err(|err| err.on_line(offset + 2, my_line))?;
+    offset += my_section.lines().count() + 1;
+
+    let other_section = parts.next().ok_or_else(|| Error::new("Missing the 'nearby tickets' section"))?;
+    let other_tickets = other_section.lines()
+        .enumerate()
+        .skip(1)
+        .map(|(index, line)| parse_ticket(line).map_err(|err| err.on_line(offset + index + 1, line)))
+        .collect::<Result<_>>()?;
+
+    Ok((constraints, my_ticket, other_tickets))
 }
 
 /// Parses the constraint section.
-fn parse_constraints(input: &str) -> HashMap<&str, Constraint> {
+pub fn parse_constraints(input: &str) -> Result<HashMap<&str, Constraint>> {
     let re = Regex::new(r"^([a-z ]+): (\d+)-(\d+) or (\d+)-(\d+)").expect("Invalid Regex");
 
-    input.lines().map(|line| {
-        let cap = re.captures(line).expect("Failed to parse constraint line");
-        (
-            cap.get(1).expect("Missing constraint label").as_str(),
+    let constraints = parse_lines(input, |line| {
+        let cap = re.captures(line)
+            .ok_or_else(|| Error::invalid(line, "Expected a constraint, e.g. 'class: 1-3 or 5-7'"))?;
+        let number = |group: usize| {
+            let m = cap.get(group).expect("Regex has five groups");
+            parse_number(m.as_str(), line, m.start() + 1)
+        };
+
+        Ok((
+            cap.get(1).expect("Regex has five groups").as_str(),
             Constraint {
-                lower_range: (
-                    cap.get(2).expect("missing min 1").as_str().parse().expect("min 1 not a number"),
-                    cap.get(3).expect("missing max 1").as_str().parse().expect("max 1 not a number")
-                ),
-                upper_range: (
-                    cap.get(4).expect("missing min 2").as_str().parse().expect("min 2 not a number"),
-                    cap.get(5).expect("missing max 2").as_str().parse().expect("max 2 not a number")
-                ),
+                lower_range: (number(2)?, number(3)?),
+                upper_range: (number(4)?, number(5)?),
             }
-        )
-    }
-    ).collect()
+        ))
+    })?;
+
+    Ok(constraints.into_iter().collect())
 }
 
 /// Parses a single line with a list of comma separated, unlabelled field values.
-fn parse_ticket(line: &str) -> Vec<usize> {
-    line.split(',').flat_map(|num| num.parse()).collect()
+pub fn parse_ticket(line: &str) -> Result<Vec<usize>> {
+    let mut column = 1;
+
+    line.split(',')
+        .map(|num| {
+            let number = parse_number(num, line, column);
+            column += num.len() + 1;
+            number
+        })
+        .collect()
 }
 
 /// The solution to part 1. Delegates most of the work to [`get_invalid_numbers`].
@@ -165,6 +191,8 @@ fn parse_ticket(line: &str) -> Vec<usize> {
 ///
 /// # Example from Test:
 /// ```
+/// # use std::collections::HashMap;
+/// # use advent_of_code_2020::day_16::*;
 /// let input = "class: 1-3 or 5-7
 /// row: 6-11 or 33-44
 /// seat: 13-40 or 45-50
@@ -177,20 +205,20 @@ fn parse_ticket(line: &str) -> Vec<usize> {
 /// 40,4,50
 /// 55,2,20
 /// 38,6,12";
-/// let (constraints, _, tickets) = parse_input(input);
+/// let (constraints, _, tickets) = parse_input(input).unwrap();
 /// assert_eq!(
 ///    vec!(4usize, 55usize, 12usize),
 ///    get_scan_error_rate(&constraints, &tickets)
 /// )
 /// ```
-fn get_scan_error_rate(constraints: &HashMap<&str, Constraint>, tickets: &Vec<Vec<usize>>) -> Vec<usize> {
-    tickets.into_iter().flat_map(|ticket| get_invalid_numbers(&constraints, &ticket)).collect()
+pub fn get_scan_error_rate(constraints: &HashMap<&str, Constraint>, tickets: &[Vec<usize>]) -> Vec<usize> {
+    tickets.iter().flat_map(|ticket| get_invalid_numbers(constraints, ticket)).collect()
 }
 
 /// Given a ticket, and set of constraints return a list of numbers that are not valid for __any__
 /// of the constraints, regardless of position.
-fn get_invalid_numbers(constraints: &HashMap<&str, Constraint>, ticket: &Vec<usize>) -> Vec<usize> {
-    ticket.into_iter()
+pub fn get_invalid_numbers(constraints: &HashMap<&str, Constraint>, ticket: &[usize]) -> Vec<usize> {
+    ticket.iter()
         .flat_map(|&number|
             if constraints.iter().any(|(_, cons)| cons.validate(number)) {
                 None
@@ -220,12 +248,15 @@ fn get_invalid_numbers(constraints: &HashMap<&str, Constraint>, ticket: &Vec<usi
 ///
 /// Tickets are discarded as invalid if [`get_invalid_numbers`] is not empty for that ticket.
 ///
-/// We then repeatedly loop over this map of sets, where a singleton set is encountered we write
-/// that position to the output array, and remove that position from all fields' sets. This
-/// generates more singletons, and the process is repeated until the output map is fully populated.
+/// The map of sets is then reduced to a single position per field by [`eliminate`], repeatedly
+/// taking the fields with only one valid position left and removing that position from all the
+/// other fields' sets. If at any point there are no singletons the tickets don't have a unique
+/// solution, and an error is returned.
 ///
 /// # Example from Tests
 /// ```
+/// # use std::collections::HashMap;
+/// # use advent_of_code_2020::day_16::*;
 /// let input = "class: 0-1 or 4-19
 /// row: 0-5 or 8-19
 /// seat: 0-13 or 16-19
@@ -243,15 +274,15 @@ fn get_invalid_numbers(constraints: &HashMap<&str, Constraint>, ticket: &Vec<usi
 /// expected.insert("row", 0usize);
 /// expected.insert("seat", 2usize);
 ///
-/// let (constraints, _, tickets) = parse_input(input);
+/// let (constraints, _, tickets) = parse_input(input).unwrap();
 ///
-/// assert_eq!(expected, get_valid_positions(&constraints, &tickets));
+/// assert_eq!(Ok(expected), get_valid_positions(&constraints, &tickets));
 /// ```
-fn get_valid_positions<'a>(constraints: &'a HashMap<&str, Constraint>, tickets: &Vec<Vec<usize>>) -> HashMap<&'a str, usize> {
+pub fn get_valid_positions<'a>(constraints: &'a HashMap<&str, Constraint>, tickets: &Vec<Vec<usize>>) -> Result<HashMap<&'a str, usize>> {
     let mut validity: HashMap<&str, HashSet<usize>> = HashMap::new();
     for ticket in tickets {
         // discard invalid
-        if !get_invalid_numbers(constraints, &ticket).is_empty() {
+        if !get_invalid_numbers(constraints, ticket).is_empty() {
             continue
         }
 
@@ -259,13 +290,13 @@ fn get_valid_positions<'a>(constraints: &'a HashMap<&str, Constraint>, tickets:
         if validity.is_empty()
         {
             let len = ticket.len();
-            constraints.into_iter().for_each(|(&key, _)| {
-                let set: HashSet<usize> = (0..len).into_iter().collect();
+            constraints.iter().for_each(|(&key, _)| {
+                let set: HashSet<usize> = (0..len).collect();
                 validity.insert(key, set);
             })
         }
 
-        ticket.into_iter().enumerate().for_each(|(i, &number)| {
+        ticket.iter().enumerate().for_each(|(i, &number)| {
             for (&key, &constraint) in constraints {
                 let not_valid = !constraint.validate(number);
                 if not_valid {
@@ -276,51 +307,28 @@ fn get_valid_positions<'a>(constraints: &'a HashMap<&str, Constraint>, tickets:
         });
     }
 
-    let mut output: HashMap<&str, usize> = HashMap::new();
-    let keys: Vec<&str> = validity.keys().map(|k| *k).collect();
-
-    loop {
-        let singletons: HashMap<&str, usize> =
-            validity.iter()
-                .filter_map(
-                    |(key, set)| {
-                        if set.len() == 1 {
-                            Some((*key, *set.iter().next().expect("set has length 1")))
-                        } else {
-                            None
-                        }
-                    }
-                )
-                .collect();
-
-        if singletons.is_empty() {
-            panic!("failed to find singleton")
-        }
-
-        singletons.into_iter().for_each(|(key, position)| {
-            output.insert(key, position);
-            for &key in keys.as_slice() {
-                let set = validity.get_mut(key).expect("missing validity");
-                set.remove(&position);
-            }
-        });
-
-        if output.len() == validity.len() {
-            break;
-        }
+    if validity.is_empty() {
+        return Err(Error::new("No valid tickets to find the field positions from"));
     }
 
-    output
+    eliminate(validity).ok_or_else(|| Error::new("Failed to find a field with only one valid position"))
 }
 
 /// The final step of part 2: combine a mapping from [`get_valid_positions`] with ticket data.
-fn map_ticket(mapping: HashMap<&str, usize>, ticket: Vec<usize>) -> HashMap<&str, usize> {
-    mapping.into_iter().map(|(key, pos)| (key, *ticket.get(pos).unwrap())).collect()
+pub fn map_ticket(mapping: HashMap<&str, usize>, ticket: Vec<usize>) -> Result<HashMap<&str, usize>> {
+    mapping.into_iter()
+        .map(|(key, pos)|
+            ticket.get(pos)
+                .map(|&value| (key, value))
+                .ok_or_else(|| Error::new(format!("Ticket has no value for '{}' at position {}", key, pos + 1)))
+        )
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use day_16::{Constraint, parse_input, get_scan_error_rate, get_valid_positions, map_ticket};
+    use error::Error;
     use std::collections::HashMap;
 
     fn get_input() -> &'static str {
@@ -356,12 +364,25 @@ nearby tickets:
             )
         );
 
-        assert_eq!(expected, parse_input(get_input()));
+        assert_eq!(Ok(expected), parse_input(get_input()));
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        let err = parse_input(&get_input().replace("55,2,20", "55,2,2O")).unwrap_err();
+        assert_eq!(Some(11), err.line);
+        assert_eq!(Some(6), err.column);
+
+        assert_eq!(
+            Err(Error::invalid("row 6-11 or 33-44", "Expected a constraint, e.g. 'class: 1-3 or 5-7'").on_line(2, "row 6-11 or 33-44")),
+            parse_input(&get_input().replace("row:", "row"))
+        );
+        assert!(parse_input("class: 1-3 or 5-7").is_err());
     }
 
     #[test]
     fn can_calculate_error_rate() {
-        let (constraints, _, tickets) = parse_input(get_input());
+        let (constraints, _, tickets) = parse_input(get_input()).unwrap();
 
         assert_eq!(vec!(4usize, 55usize, 12usize), get_scan_error_rate(&constraints, &tickets));
     }
@@ -380,9 +401,9 @@ nearby tickets:
 15,1,5
 5,14,9";
 
-        let (constraints, _, tickets) = parse_input(input);
+        let (constraints, _, tickets) = parse_input(input).unwrap();
 
-        assert_eq!(get_expected_mapping(), get_valid_positions(&constraints, &tickets));
+        assert_eq!(Ok(get_expected_mapping()), get_valid_positions(&constraints, &tickets));
     }
 
     fn get_expected_mapping() -> HashMap<&'static str, usize> {
@@ -400,6 +421,7 @@ nearby tickets:
         expected.insert("row", 11);
         expected.insert("seat", 13);
 
-        assert_eq!(expected, map_ticket(get_expected_mapping(), vec!(11, 12, 13)));
+        assert_eq!(Ok(expected), map_ticket(get_expected_mapping(), vec!(11, 12, 13)));
+        assert!(map_ticket(get_expected_mapping(), vec!(11, 12)).is_err());
     }
 }
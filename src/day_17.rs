@@ -10,33 +10,46 @@
 //!
 //! __Part 2__ - [`FourDGrid`], [`parse_input_4d`], [`iterate_grid_4d`].
 
-use std::fs;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
 use std::fmt;
+use error::{Error, Result};
+use solution::Solution;
 
-/// The entry point for running the solutions with the 'real' puzzle input.
+/// The [`Solution`] for day 17.
 ///
-/// - The puzzle input is expected to be at `<project_root>/res/day-17-input`
-/// - It is expected this will be called by [`super::main()`] when the user elects to run day 17.
-pub fn run() {
-    let contents = fs::read_to_string("res/day-17-input").expect("Failed to read file");
-    let mut grid = parse_input_3d(contents.as_str());
-    for _ in 0..6 {
-        grid = iterate_grid_3d(&grid)
+/// - Both parts count the active cells after the 6 step boot cycle, in 3D and 4D respectively.
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input<'a> = (ThreeDGrid, FourDGrid);
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<(ThreeDGrid, FourDGrid)> {
+        Ok((parse_input_3d(input)?, parse_input_4d(input)?))
+    }
+
+    fn part_one(&self, (grid, _): &(ThreeDGrid, FourDGrid)) -> Result<usize> {
+        let mut grid = grid.clone();
+        for _ in 0..6 {
+            grid = iterate_grid_3d(&grid)
+        }
+        Ok(grid.count_active())
     }
-    println!("After the 6 step boot cycle there are {} active cells in the 3d grid", grid.count_active());
 
-    let mut grid = parse_input_4d(contents.as_str());
-    for _ in 0..6 {
-        grid = iterate_grid_4d(&grid)
+    fn part_two(&self, (_, grid): &(ThreeDGrid, FourDGrid)) -> Result<usize> {
+        let mut grid = grid.clone();
+        for _ in 0..6 {
+            grid = iterate_grid_4d(&grid)
+        }
+        Ok(grid.count_active())
     }
-    println!("After the 6 step boot cycle there are {} active cells in the 4d grid", grid.count_active());
 }
 
 /// Represents a three dimensional infinite grid.
-#[derive(Clone)]
-struct ThreeDGrid {
+#[derive(Clone, Default)]
+pub struct ThreeDGrid {
     /// Holds the grid data
     grid: HashMap<isize, HashMap<isize, HashSet<isize>>>,
     /// Lower bound of data in the x dimension
@@ -54,7 +67,7 @@ struct ThreeDGrid {
 }
 
 impl ThreeDGrid {
-    fn new() -> ThreeDGrid {
+    pub fn new() -> ThreeDGrid {
         ThreeDGrid {
             grid: HashMap::new(),
             x_min: 0,
@@ -67,26 +80,59 @@ impl ThreeDGrid {
     }
 
     /// Get the state of a specific cell in the grid
-    fn is_cell_active(&self, x: isize, y: isize, z: isize) -> bool {
+    pub fn is_cell_active(&self, x: isize, y: isize, z: isize) -> bool {
         self.grid.get(&z)
-            .map(|plane| plane.get(&y)).flatten()
+            .and_then(|plane| plane.get(&y))
             .map(|column| column.contains(&x))
             .unwrap_or(false)
     }
 
     /// Set the state of a specific state in the grid
-    fn toggle_cell(&mut self, x: isize, y: isize, z: isize, active: bool) {
-        if !self.grid.contains_key(&z) {
-            self.grid.insert(z, HashMap::new());
-        }
-
-        let plane = self.grid.get_mut(&z).expect("Ensured existence above");
-
-        if !plane.contains_key(&y) {
-            plane.insert(y, HashSet::new());
-        }
-
-        let column = plane.get_mut(&y).expect("Ensured existence above");
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_17::ThreeDGrid;
+    /// let mut grid = ThreeDGrid::new();
+    ///
+    /// assert_eq!(false, grid.is_cell_active(1, 0, 0));
+    /// assert_eq!(false, grid.is_cell_active(0, 1, 0));
+    /// assert_eq!(false, grid.is_cell_active(0, 0, 1));
+    ///
+    /// assert_eq!(0usize, grid.count_active());
+    ///
+    /// assert_eq!(((0, 0), (0, 0), (0, 0)), grid.bounds());
+    ///
+    /// grid.toggle_cell(1, 0, 0, true);
+    ///
+    /// assert_eq!(true, grid.is_cell_active(1, 0, 0));
+    /// assert_eq!(false, grid.is_cell_active(0, 1, 0));
+    /// assert_eq!(false, grid.is_cell_active(0, 0, 1));
+    ///
+    /// assert_eq!(1usize, grid.count_active());
+    ///
+    /// grid.toggle_cell(0, 1, 0, true);
+    /// grid.toggle_cell(0, 0, 1, false);
+    ///
+    /// assert_eq!(true, grid.is_cell_active(1, 0, 0));
+    /// assert_eq!(true, grid.is_cell_active(0, 1, 0));
+    /// assert_eq!(false, grid.is_cell_active(0, 0, 1));
+    ///
+    /// assert_eq!(2usize, grid.count_active());
+    ///
+    /// grid.toggle_cell(1, 0, 0, false);
+    /// grid.toggle_cell(0, 1, 0, true);
+    ///
+    /// assert_eq!(false, grid.is_cell_active(1, 0, 0));
+    /// assert_eq!(true, grid.is_cell_active(0, 1, 0));
+    /// assert_eq!(false, grid.is_cell_active(0, 0, 1));
+    ///
+    /// assert_eq!(1usize, grid.count_active());
+    ///
+    /// assert_eq!(((0, 1), (0, 1), (0, 0)), grid.bounds());
+    /// ```
+    pub fn toggle_cell(&mut self, x: isize, y: isize, z: isize, active: bool) {
+        let plane = self.grid.entry(z).or_default();
+        let column = plane.entry(y).or_default();
 
         if active {
             column.insert(x);
@@ -107,26 +153,43 @@ impl ThreeDGrid {
     }
 
     /// Returns the number of active cells in the grid
-    fn count_active(&self) -> usize {
-        self.grid.iter()
-            .flat_map(|(_, plane)| plane.iter())
+    pub fn count_active(&self) -> usize {
+        self.grid.values().flat_map(|plane| plane.iter())
             .map(|(_, column)| column.len())
             .sum()
     }
 
+    /// The inclusive `(min, max)` bounds of the active cells seen so far in each of the x, y and z
+    /// dimensions.
+    pub fn bounds(&self) -> ((isize, isize), (isize, isize), (isize, isize)) {
+        ((self.x_min, self.x_max), (self.y_min, self.y_max), (self.z_min, self.z_max))
+    }
+
     /// How many of the 26 grid cells adjacent to the target cell are active
     ///
     /// > Each cube only ever considers its neighbors: any of the 26 other cubes where any of their
     /// > coordinates differ by at most 1. For example, given the cube at x=1,y=2,z=3, its neighbors
     /// >  include the cube at x=2,y=2,z=2, the cube at x=0,y=2,z=3, and so on.
-    fn count_adjacent(&self, x: isize, y: isize, z: isize) -> usize {
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_17::parse_input_3d;
+    /// let input = ".#.\n..#\n###";
+    /// let grid = parse_input_3d(input).unwrap();
+    ///
+    /// assert_eq!(1usize, grid.count_adjacent(0,0,0));
+    /// assert_eq!(5usize, grid.count_adjacent(1,1,0));
+    /// assert_eq!(2usize, grid.count_adjacent(2,2,0));
+    /// assert_eq!(1usize, grid.count_adjacent(3,3,0));
+    /// ```
+    pub fn count_adjacent(&self, x: isize, y: isize, z: isize) -> usize {
         let mut sum = 0;
         for z1 in (z - 1)..=(z + 1) {
             for y1 in (y - 1)..=(y + 1) {
                 for x1 in (x - 1)..=(x + 1) {
                     if z1 == z && y1 == y && x1 == x { continue }
                     if self.is_cell_active(x1, y1, z1) {
-                        sum = sum + 1
+                        sum += 1
                     }
                 }
             }
@@ -142,17 +205,17 @@ impl Debug for ThreeDGrid {
         let mut out = "".to_owned();
 
         for z in (self.z_min)..=(self.z_max) {
-            out = out + format!("z={}\n  ", z).as_str();
+            out += format!("z={}\n  ", z).as_str();
             for x in (self.x_min)..=(self.x_max) {
-                out = out + format!("{:2}", x).as_str();
+                out += format!("{:2}", x).as_str();
             }
-            out = out + "\n";
+            out += "\n";
             for y in (self.y_min)..=(self.y_max) {
-                out = out + format!("{:2}", y).as_str();
+                out += format!("{:2}", y).as_str();
                 for x in (self.x_min)..=(self.x_max) {
-                    out = out + if self.is_cell_active(x, y, z) { " #" } else { " ." }
+                    out += if self.is_cell_active(x, y, z) { " #" } else { " ." }
                 }
-                out = out + "\n";
+                out += "\n";
             }
         }
 
@@ -160,9 +223,12 @@ impl Debug for ThreeDGrid {
     }
 }
 
+/// The inclusive `(min, max)` bounds of a [`FourDGrid`] in each of the x, y, z and w dimensions.
+type Bounds4d = ((isize, isize), (isize, isize), (isize, isize), (isize, isize));
+
 /// Represents a three dimensional infinite grid.
-#[derive(Clone)]
-struct FourDGrid {
+#[derive(Clone, Default)]
+pub struct FourDGrid {
     /// Implements the grid as a map of nested 3D grids.
     grid: HashMap<isize, ThreeDGrid>,
     /// Upper bound of data in the w dimension
@@ -172,7 +238,7 @@ struct FourDGrid {
 }
 
 impl FourDGrid {
-    fn new() -> FourDGrid {
+    pub fn new() -> FourDGrid {
         FourDGrid {
             grid: HashMap::new(),
             w_min: 0,
@@ -181,68 +247,13 @@ impl FourDGrid {
     }
 
     /// Get the state of a specific cell in the grid
-    fn is_cell_active(&self, x: isize, y: isize, z: isize, w: isize) -> bool {
-        self.grid.get(&w).map_or(false, |cube| cube.is_cell_active(x, y, z))
+    pub fn is_cell_active(&self, x: isize, y: isize, z: isize, w: isize) -> bool {
+        self.grid.get(&w).is_some_and(|cube| cube.is_cell_active(x, y, z))
     }
 
     /// Set the state of a specific state in the grid
-    ///
-    /// # Examples from Tests
-    /// ```
-    /// let mut grid = ThreeDGrid::new();
-    ///
-    /// assert_eq!(false, grid.is_cell_active(1, 0, 0));
-    /// assert_eq!(false, grid.is_cell_active(0, 1, 0));
-    /// assert_eq!(false, grid.is_cell_active(0, 0, 1));
-    ///
-    /// assert_eq!(0usize, grid.count_active());
-    ///
-    /// assert_eq!(0isize, grid.x_min);
-    /// assert_eq!(0isize, grid.x_max);
-    /// assert_eq!(0isize, grid.y_min);
-    /// assert_eq!(0isize, grid.y_max);
-    /// assert_eq!(0isize, grid.z_min);
-    /// assert_eq!(0isize, grid.z_max);
-    ///
-    /// grid.toggle_cell(1, 0, 0, true);
-    ///
-    /// assert_eq!(true, grid.is_cell_active(1, 0, 0));
-    /// assert_eq!(false, grid.is_cell_active(0, 1, 0));
-    /// assert_eq!(false, grid.is_cell_active(0, 0, 1));
-    ///
-    /// assert_eq!(1usize, grid.count_active());
-    ///
-    /// grid.toggle_cell(0, 1, 0, true);
-    /// grid.toggle_cell(0, 0, 1, false);
-    ///
-    /// assert_eq!(true, grid.is_cell_active(1, 0, 0));
-    /// assert_eq!(true, grid.is_cell_active(0, 1, 0));
-    /// assert_eq!(false, grid.is_cell_active(0, 0, 1));
-    ///
-    /// assert_eq!(2usize, grid.count_active());
-    ///
-    /// grid.toggle_cell(1, 0, 0, false);
-    /// grid.toggle_cell(0, 1, 0, true);
-    ///
-    /// assert_eq!(false, grid.is_cell_active(1, 0, 0));
-    /// assert_eq!(true, grid.is_cell_active(0, 1, 0));
-    /// assert_eq!(false, grid.is_cell_active(0, 0, 1));
-    ///
-    /// assert_eq!(1usize, grid.count_active());
-    ///
-    /// assert_eq!(0isize, grid.x_min);
-    /// assert_eq!(1isize, grid.x_max);
-    /// assert_eq!(0isize, grid.y_min);
-    /// assert_eq!(1isize, grid.y_max);
-    /// assert_eq!(0isize, grid.z_min);
-    /// assert_eq!(0isize, grid.z_max);
-    /// ```
-    fn toggle_cell(&mut self, x: isize, y: isize, z: isize, w: isize, active: bool) {
-        if !self.grid.contains_key(&w) {
-            self.grid.insert(w, ThreeDGrid::new());
-        }
-
-        let cube = self.grid.get_mut(&w).expect("Ensured existence above");
+    pub fn toggle_cell(&mut self, x: isize, y: isize, z: isize, w: isize, active: bool) {
+        let cube = self.grid.entry(w).or_default();
         
         cube.toggle_cell(x, y, z, active);
         
@@ -253,23 +264,12 @@ impl FourDGrid {
     }
 
     /// Returns the number of active cells in the grid
-    fn count_active(&self) -> usize {
-        self.grid.iter().map(|(_, cube)| cube.count_active()).sum()
+    pub fn count_active(&self) -> usize {
+        self.grid.values().map(|cube| cube.count_active()).sum()
     }
 
     /// How many of the 80 grid cells adjacent to the target cell are active
-    ///
-    /// # Examples frm Tests
-    /// ```
-    /// let input = ".#.\n..#\n###";
-    /// let grid = parse_input_3d(input);
-    ///
-    /// assert_eq!(1usize, grid.count_adjacent(0,0,0));
-    /// assert_eq!(5usize, grid.count_adjacent(1,1,0));
-    /// assert_eq!(2usize, grid.count_adjacent(2,2,0));
-    /// assert_eq!(1usize, grid.count_adjacent(3,3,0));
-    /// ```
-    fn count_adjacent(&self, x: isize, y: isize, z: isize, w: isize) -> usize {
+    pub fn count_adjacent(&self, x: isize, y: isize, z: isize, w: isize) -> usize {
         let mut sum = 0;
         for w1 in (w - 1)..=(w + 1) {
             for z1 in (z - 1)..=(z + 1) {
@@ -277,7 +277,7 @@ impl FourDGrid {
                     for x1 in (x - 1)..=(x + 1) {
                         if z1 == z && y1 == y && x1 == x && w1 == w { continue }
                         if self.is_cell_active(x1, y1, z1, w1) {
-                            sum = sum + 1
+                            sum += 1
                         }
                     }
                 }
@@ -288,7 +288,7 @@ impl FourDGrid {
     }
 
     /// Return the bounds of the data in the grid by querying the inner 3D grids
-    fn get_bounds(&self) -> ((isize, isize),(isize, isize),(isize, isize),(isize, isize)) {
+    fn get_bounds(&self) -> Bounds4d {
         self.grid.iter().fold(
             ((0isize, 0isize), (0isize, 0isize), (0isize, 0isize), (self.w_min, self.w_max)),
             |((x_min, x_max),(y_min, y_max),(z_min, z_max),(w_min, w_max)), (_, cube)| {
@@ -314,8 +314,9 @@ impl FourDGrid {
 ///
 /// # Example from Tests
 /// ```
+/// # use advent_of_code_2020::day_17::*;
 /// let input = ".#.\n..#\n###";
-/// let grid = parse_input_3d(input);
+/// let grid = parse_input_3d(input).unwrap();
 ///
 /// assert_eq!(true, grid.is_cell_active(1, 0, 0));
 /// assert_eq!(true, grid.is_cell_active(2, 1, 0));
@@ -325,23 +326,16 @@ impl FourDGrid {
 ///
 /// assert_eq!(5usize, grid.count_active());
 ///
-/// assert_eq!(0isize, grid.x_min);
-/// assert_eq!(2isize, grid.x_max);
-/// assert_eq!(0isize, grid.y_min);
-/// assert_eq!(2isize, grid.y_max);
-/// assert_eq!(0isize, grid.z_min);
-/// assert_eq!(0isize, grid.z_max);/
+/// assert_eq!(((0, 2), (0, 2), (0, 0)), grid.bounds());
 /// ```
-fn parse_input_3d(input: &str) -> ThreeDGrid {
+pub fn parse_input_3d(input: &str) -> Result<ThreeDGrid> {
     let mut grid = ThreeDGrid::new();
 
-    for (y, line) in input.lines().enumerate() {
-        for (x, char) in line.chars().enumerate() {
-            grid.toggle_cell(x as isize, y as isize, 0, char == '#')
-        }
+    for (x, y, active) in parse_cells(input)? {
+        grid.toggle_cell(x, y, 0, active)
     }
 
-    grid
+    Ok(grid)
 }
 
 /// Build the initial 4D Grid from the puzzle input.
@@ -352,8 +346,9 @@ fn parse_input_3d(input: &str) -> ThreeDGrid {
 ///
 /// # Example from Test
 /// ```
+/// # use advent_of_code_2020::day_17::*;
 /// let input = ".#.\n..#\n###";
-/// let mut grid = parse_input_4d(input);
+/// let mut grid = parse_input_4d(input).unwrap();
 ///
 /// assert_eq!(true, grid.is_cell_active(1, 0, 0, 0));
 /// assert_eq!(true, grid.is_cell_active(2, 1, 0, 0));
@@ -363,16 +358,36 @@ fn parse_input_3d(input: &str) -> ThreeDGrid {
 ///
 /// assert_eq!(5usize, grid.count_active());
 /// ```
-fn parse_input_4d(input: &str) -> FourDGrid {
+pub fn parse_input_4d(input: &str) -> Result<FourDGrid> {
     let mut grid = FourDGrid::new();
 
+    for (x, y, active) in parse_cells(input)? {
+        grid.toggle_cell(x, y, 0, 0, active)
+    }
+
+    Ok(grid)
+}
+
+/// Shared by [`parse_input_3d`] and [`parse_input_4d`], turns the 2D slice into a list of
+/// `(x, y, active)` cells.
+fn parse_cells(input: &str) -> Result<Vec<(isize, isize, bool)>> {
+    let mut cells = Vec::new();
+
     for (y, line) in input.lines().enumerate() {
         for (x, char) in line.chars().enumerate() {
-            grid.toggle_cell(x as isize, y as isize, 0,  0,char == '#')
+            let active = match char {
+                '#' => true,
+                '.' => false,
+                other => return Err(
+                    Error::at_column(line, x + 1, format!("Invalid cube '{}', expected '#' or '.'", other))
+                        .on_line(y + 1, line)
+                ),
+            };
+            cells.push((x as isize, y as isize, active));
         }
     }
 
-    grid
+    Ok(cells)
 }
 
 /// Produce the next grid by applying the rules of the game to the current gird. Solution to part 1.
@@ -388,8 +403,9 @@ fn parse_input_4d(input: &str) -> FourDGrid {
 ///
 /// # Examples from Test
 /// ```
+/// # use advent_of_code_2020::day_17::*;
 /// let input = ".#.\n..#\n###";
-/// let mut grid = parse_input_3d(input);
+/// let mut grid = parse_input_3d(input).unwrap();
 ///
 /// grid = iterate_grid_3d(&grid);
 ///
@@ -418,7 +434,7 @@ fn parse_input_4d(input: &str) -> FourDGrid {
 /// grid = iterate_grid_3d(&grid);
 /// assert_eq!(112usize, grid.count_active());
 /// ```
-fn iterate_grid_3d(grid: &ThreeDGrid) -> ThreeDGrid {
+pub fn iterate_grid_3d(grid: &ThreeDGrid) -> ThreeDGrid {
     let mut new_grid = grid.clone();
     for z in (grid.z_min - 1)..=(grid.z_max + 1) {
         for y in (grid.y_min - 1)..=(grid.y_max + 1) {
@@ -444,8 +460,9 @@ fn iterate_grid_3d(grid: &ThreeDGrid) -> ThreeDGrid {
 ///
 /// # Examples from Tests
 /// ```
+/// # use advent_of_code_2020::day_17::*;
 /// let input = ".#.\n..#\n###";
-/// let mut grid = parse_input_4d(input);
+/// let mut grid = parse_input_4d(input).unwrap();
 ///
 /// assert_eq!(true, grid.is_cell_active(1, 0, 0, 0));
 /// assert_eq!(true, grid.is_cell_active(2, 1, 0, 0));
@@ -467,8 +484,7 @@ fn iterate_grid_3d(grid: &ThreeDGrid) -> ThreeDGrid {
 /// grid = iterate_grid_4d(&grid);
 /// assert_eq!(848usize, grid.count_active());
 /// ```
-
-fn iterate_grid_4d(grid: &FourDGrid) -> FourDGrid {
+pub fn iterate_grid_4d(grid: &FourDGrid) -> FourDGrid {
     let mut new_grid = grid.clone();
     let ((x_min, x_max),(y_min, y_max),(z_min, z_max),(w_min, w_max)) = grid.get_bounds();
     
@@ -498,13 +514,13 @@ mod tests {
     #[test]
     fn can_parse() {
         let input = ".#.\n..#\n###";
-        let grid = parse_input_3d(input);
+        let grid = parse_input_3d(input).unwrap();
 
-        assert_eq!(true, grid.is_cell_active(1, 0, 0));
-        assert_eq!(true, grid.is_cell_active(2, 1, 0));
-        assert_eq!(true, grid.is_cell_active(0, 2, 0));
-        assert_eq!(true, grid.is_cell_active(1, 2, 0));
-        assert_eq!(true, grid.is_cell_active(2, 2, 0));
+        assert!(grid.is_cell_active(1, 0, 0));
+        assert!(grid.is_cell_active(2, 1, 0));
+        assert!(grid.is_cell_active(0, 2, 0));
+        assert!(grid.is_cell_active(1, 2, 0));
+        assert!(grid.is_cell_active(2, 2, 0));
 
         assert_eq!(5usize, grid.count_active());
 
@@ -516,12 +532,21 @@ mod tests {
         assert_eq!(0isize, grid.z_max);
     }
 
+    #[test]
+    fn rejects_invalid_cubes() {
+        let err = parse_input_3d(".#.\n.x#\n###").unwrap_err();
+
+        assert_eq!(Some(2), err.line);
+        assert_eq!(Some(2), err.column);
+        assert!(parse_input_4d("#?").is_err());
+    }
+
     #[test]
     fn can_toggle_cell() {
         let mut grid = ThreeDGrid::new();
-        assert_eq!(false, grid.is_cell_active(1, 0, 0));
-        assert_eq!(false, grid.is_cell_active(0, 1, 0));
-        assert_eq!(false, grid.is_cell_active(0, 0, 1));
+        assert!(!grid.is_cell_active(1, 0, 0));
+        assert!(!grid.is_cell_active(0, 1, 0));
+        assert!(!grid.is_cell_active(0, 0, 1));
 
         assert_eq!(0usize, grid.count_active());
 
@@ -534,25 +559,25 @@ mod tests {
 
         grid.toggle_cell(1, 0, 0, true);
 
-        assert_eq!(true, grid.is_cell_active(1, 0, 0));
-        assert_eq!(false, grid.is_cell_active(0, 1, 0));
-        assert_eq!(false, grid.is_cell_active(0, 0, 1));
+        assert!(grid.is_cell_active(1, 0, 0));
+        assert!(!grid.is_cell_active(0, 1, 0));
+        assert!(!grid.is_cell_active(0, 0, 1));
         assert_eq!(1usize, grid.count_active());
 
         grid.toggle_cell(0, 1, 0, true);
         grid.toggle_cell(0, 0, 1, false);
 
-        assert_eq!(true, grid.is_cell_active(1, 0, 0));
-        assert_eq!(true, grid.is_cell_active(0, 1, 0));
-        assert_eq!(false, grid.is_cell_active(0, 0, 1));
+        assert!(grid.is_cell_active(1, 0, 0));
+        assert!(grid.is_cell_active(0, 1, 0));
+        assert!(!grid.is_cell_active(0, 0, 1));
         assert_eq!(2usize, grid.count_active());
 
         grid.toggle_cell(1, 0, 0, false);
         grid.toggle_cell(0, 1, 0, true);
 
-        assert_eq!(false, grid.is_cell_active(1, 0, 0));
-        assert_eq!(true, grid.is_cell_active(0, 1, 0));
-        assert_eq!(false, grid.is_cell_active(0, 0, 1));
+        assert!(!grid.is_cell_active(1, 0, 0));
+        assert!(grid.is_cell_active(0, 1, 0));
+        assert!(!grid.is_cell_active(0, 0, 1));
         assert_eq!(1usize, grid.count_active());
 
         assert_eq!(0isize, grid.x_min);
@@ -566,7 +591,7 @@ mod tests {
     #[test]
     fn can_count_adjacent() {
         let input = ".#.\n..#\n###";
-        let grid = parse_input_3d(input);
+        let grid = parse_input_3d(input).unwrap();
 
         assert_eq!(1usize, grid.count_adjacent(0,0,0));
         assert_eq!(5usize, grid.count_adjacent(1,1,0));
@@ -577,24 +602,24 @@ mod tests {
     #[test]
     fn can_iterate() {
         let input = ".#.\n..#\n###";
-        let mut grid = parse_input_3d(input);
+        let mut grid = parse_input_3d(input).unwrap();
 
         grid = iterate_grid_3d(&grid);
 
 
-        assert_eq!(true, grid.is_cell_active(0, 1, -1));
-        assert_eq!(true, grid.is_cell_active(2, 2, -1));
-        assert_eq!(true, grid.is_cell_active(1, 3, -1));
+        assert!(grid.is_cell_active(0, 1, -1));
+        assert!(grid.is_cell_active(2, 2, -1));
+        assert!(grid.is_cell_active(1, 3, -1));
 
-        assert_eq!(true, grid.is_cell_active(0, 1, 0));
-        assert_eq!(true, grid.is_cell_active(2, 1, 0));
-        assert_eq!(true, grid.is_cell_active(1, 2, 0));
-        assert_eq!(true, grid.is_cell_active(2, 2, 0));
-        assert_eq!(true, grid.is_cell_active(1, 3, 1));
+        assert!(grid.is_cell_active(0, 1, 0));
+        assert!(grid.is_cell_active(2, 1, 0));
+        assert!(grid.is_cell_active(1, 2, 0));
+        assert!(grid.is_cell_active(2, 2, 0));
+        assert!(grid.is_cell_active(1, 3, 1));
 
-        assert_eq!(true, grid.is_cell_active(0, 1, 1));
-        assert_eq!(true, grid.is_cell_active(2, 2, 1));
-        assert_eq!(true, grid.is_cell_active(1, 3, 1));
+        assert!(grid.is_cell_active(0, 1, 1));
+        assert!(grid.is_cell_active(2, 2, 1));
+        assert!(grid.is_cell_active(1, 3, 1));
 
         assert_eq!(11usize, grid.count_active());
 
@@ -614,13 +639,13 @@ mod tests {
     #[test]
     fn can_expand_to_4d() {
         let input = ".#.\n..#\n###";
-        let mut grid = parse_input_4d(input);
+        let mut grid = parse_input_4d(input).unwrap();
 
-        assert_eq!(true, grid.is_cell_active(1, 0, 0, 0));
-        assert_eq!(true, grid.is_cell_active(2, 1, 0, 0));
-        assert_eq!(true, grid.is_cell_active(0, 2, 0, 0));
-        assert_eq!(true, grid.is_cell_active(1, 2, 0, 0));
-        assert_eq!(true, grid.is_cell_active(2, 2, 0, 0));
+        assert!(grid.is_cell_active(1, 0, 0, 0));
+        assert!(grid.is_cell_active(2, 1, 0, 0));
+        assert!(grid.is_cell_active(0, 2, 0, 0));
+        assert!(grid.is_cell_active(1, 2, 0, 0));
+        assert!(grid.is_cell_active(2, 2, 0, 0));
 
         assert_eq!(5usize, grid.count_active());
 
@@ -0,0 +1,403 @@
+//! This is my solution for [Advent of Code - Day 18](https://adventofcode.com/2020/day/18) -
+//! _Operation Order_
+//!
+//! Evaluate arithmetic expressions where the usual precedence rules don't apply. Both parts use the
+//! same pipeline: [`tokenize`] a line, [`parse_expression`] the tokens into an [`Expression`] tree
+//! using [precedence climbing](https://en.wikipedia.org/wiki/Operator-precedence_parser#Precedence_climbing_method),
+//! then [`evaluate`] the tree. The only difference between the parts is the [`Precedence`] table
+//! passed to the parser, as the tree captures the order the operations are applied in.
+//!
+//! __Part 1__ - [`Precedence::EQUAL`], `+` and `*` are evaluated left to right.
+//!
+//! __Part 2__ - [`Precedence::ADVANCED`], `+` is evaluated before `*`.
+
+use error::{parse_lines, parse_number, Error, Result};
+use solution::Solution;
+
+/// The [`Solution`] for day 18.
+///
+/// - Each line is parsed twice, once with each part's [`Precedence`], as the shape of the tree
+///   depends on it.
+/// - Both parts sum the result of evaluating each line.
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input<'a> = (Vec<Expression>, Vec<Expression>);
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(&self, input: &str) -> Result<(Vec<Expression>, Vec<Expression>)> {
+        Ok((parse_input(input, &Precedence::EQUAL)?, parse_input(input, &Precedence::ADVANCED)?))
+    }
+
+    fn part_one(&self, (expressions, _): &(Vec<Expression>, Vec<Expression>)) -> Result<u64> {
+        sum_expressions(expressions)
+    }
+
+    fn part_two(&self, (_, expressions): &(Vec<Expression>, Vec<Expression>)) -> Result<u64> {
+        sum_expressions(expressions)
+    }
+}
+
+/// The binary operators that can appear in an expression.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Operator {
+    Add,
+    Multiply,
+}
+
+impl Operator {
+    /// Apply the operator, failing rather than panicking if the result doesn't fit in a `u64`.
+    pub fn apply(&self, lhs: u64, rhs: u64) -> Result<u64> {
+        match self {
+            Operator::Add => lhs.checked_add(rhs),
+            Operator::Multiply => lhs.checked_mul(rhs),
+        }.ok_or_else(|| Error::new(format!("Overflow evaluating {} {} {}", lhs, self.symbol(), rhs)))
+    }
+
+    fn symbol(&self) -> char {
+        match self {
+            Operator::Add => '+',
+            Operator::Multiply => '*',
+        }
+    }
+}
+
+/// The lexical elements of an expression.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Token {
+    Number(u64),
+    Operator(Operator),
+    Open,
+    Close,
+}
+
+/// Split a line into [`Token`]s, each paired with the 1-indexed column it started at so that the
+/// parser can report where it went wrong. Whitespace is ignored, and numbers can have many digits,
+/// though the puzzle input only uses single digits.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_18::*;
+/// # use advent_of_code_2020::day_18::Token::*;
+/// assert_eq!(
+///     vec!(
+///         (1, Number(2)),
+///         (3, Operator(Operator::Multiply)),
+///         (5, Number(3)),
+///         (7, Operator(Operator::Add)),
+///         (9, Open),
+///         (10, Number(4)),
+///         (12, Operator(Operator::Multiply)),
+///         (14, Number(15)),
+///         (16, Close),
+///     ),
+///     tokenize("2 * 3 + (4 * 15)").unwrap()
+/// );
+///
+/// assert_eq!(Some(5), tokenize("2 * x").unwrap_err().column);
+/// ```
+pub fn tokenize(line: &str) -> Result<Vec<(usize, Token)>> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().peekable();
+
+    while let Some((index, char)) = chars.next() {
+        let column = index + 1;
+        match char {
+            '+' => tokens.push((column, Token::Operator(Operator::Add))),
+            '*' => tokens.push((column, Token::Operator(Operator::Multiply))),
+            '(' => tokens.push((column, Token::Open)),
+            ')' => tokens.push((column, Token::Close)),
+            '0'..='9' => {
+                let mut end = index + 1;
+                while let Some((next, '0'..='9')) = chars.peek() {
+                    end = next + 1;
+                    chars.next();
+                }
+                tokens.push((column, Token::Number(parse_number(&line[index..end], line, column)?)))
+            }
+            c if c.is_whitespace() => {}
+            c => return Err(Error::at_column(line, column, format!("Unexpected character '{}'", c))),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Whether a chain of operators with the same precedence groups from the left or the right.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
+/// The binding power of each [`Operator`]. Higher precedence operators are applied first. Left
+/// associative operators must be below `u8::MAX`, as parsing them needs a higher precedence.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Precedence {
+    pub add: (u8, Associativity),
+    pub multiply: (u8, Associativity),
+}
+
+impl Precedence {
+    /// The rules for part 1: all operators are equal, and are applied left to right.
+    pub const EQUAL: Precedence = Precedence {
+        add: (1, Associativity::Left),
+        multiply: (1, Associativity::Left),
+    };
+
+    /// The rules for part 2: addition is applied before multiplication.
+    pub const ADVANCED: Precedence = Precedence {
+        add: (2, Associativity::Left),
+        multiply: (1, Associativity::Left),
+    };
+
+    /// The usual rules of arithmetic, for comparison: multiplication is applied before addition.
+    pub const STANDARD: Precedence = Precedence {
+        add: (1, Associativity::Left),
+        multiply: (2, Associativity::Left),
+    };
+
+    /// The precedence and associativity of an operator.
+    pub fn of(&self, operator: Operator) -> (u8, Associativity) {
+        match operator {
+            Operator::Add => self.add,
+            Operator::Multiply => self.multiply,
+        }
+    }
+}
+
+/// The parsed form of an expression. Brackets don't need their own node, the grouping they
+/// represent is captured by the shape of the tree.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Expression {
+    Number(u64),
+    Binary(Box<Expression>, Operator, Box<Expression>),
+}
+
+impl Expression {
+    /// Convenience constructor for a [`Expression::Binary`] node.
+    pub fn binary(lhs: Expression, operator: Operator, rhs: Expression) -> Expression {
+        Expression::Binary(Box::new(lhs), operator, Box::new(rhs))
+    }
+}
+
+/// Tracks the progress through a line's tokens whilst parsing it.
+struct Parser<'a> {
+    line: &'a str,
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    precedence: &'a Precedence,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<(usize, Token)> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<(usize, Token)> {
+        let token = self.peek();
+        self.position += 1;
+        token
+    }
+
+    /// The column to report if the line ended too soon.
+    fn end_column(&self) -> usize {
+        self.line.len() + 1
+    }
+
+    /// Parse a number or a bracketed sub-expression.
+    fn parse_operand(&mut self) -> Result<Expression> {
+        match self.next() {
+            Some((_, Token::Number(value))) => Ok(Expression::Number(value)),
+            Some((column, Token::Open)) => {
+                let expression = self.parse_binary(0)?;
+                match self.next() {
+                    Some((_, Token::Close)) => Ok(expression),
+                    Some((close, _)) => Err(Error::at_column(self.line, close, "Expected ')'")),
+                    None => Err(Error::at_column(self.line, column, "Unclosed '('")),
+                }
+            }
+            Some((column, _)) => Err(Error::at_column(self.line, column, "Expected a number or '('")),
+            None => Err(Error::at_column(self.line, self.end_column(), "Expected a number or '('")),
+        }
+    }
+
+    /// Parse a chain of operations, consuming operators with at least `min_precedence`. Each time a
+    /// higher precedence operator is found the right hand side is parsed recursively so that it
+    /// binds more tightly. For left associative operators the recursion only accepts strictly
+    /// higher precedence, so that equal operators are instead folded into the left hand side.
+    fn parse_binary(&mut self, min_precedence: u8) -> Result<Expression> {
+        let mut lhs = self.parse_operand()?;
+
+        while let Some((column, Token::Operator(operator))) = self.peek() {
+            let (precedence, associativity) = self.precedence.of(operator);
+            if precedence < min_precedence {
+                break;
+            }
+
+            self.next();
+            let rhs = match associativity {
+                Associativity::Left => {
+                    let higher = precedence.checked_add(1).ok_or_else(|| Error::at_column(
+                        self.line,
+                        column,
+                        format!("Left associative operators can't have precedence {}", u8::MAX),
+                    ))?;
+                    self.parse_binary(higher)?
+                }
+                Associativity::Right => self.parse_binary(precedence)?,
+            };
+            lhs = Expression::binary(lhs, operator, rhs);
+        }
+
+        Ok(lhs)
+    }
+}
+
+/// Parse a line into an [`Expression`], grouping operations according to `precedence`.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_18::*;
+/// # use advent_of_code_2020::day_18::Expression::Number;
+/// let expression = "1 + 2 * 3";
+///
+/// assert_eq!(
+///     Expression::binary(
+///         Expression::binary(Number(1), Operator::Add, Number(2)),
+///         Operator::Multiply,
+///         Number(3)
+///     ),
+///     parse_expression(expression, &Precedence::EQUAL).unwrap()
+/// );
+/// assert_eq!(
+///     Expression::binary(
+///         Number(1),
+///         Operator::Add,
+///         Expression::binary(Number(2), Operator::Multiply, Number(3))
+///     ),
+///     parse_expression(expression, &Precedence::STANDARD).unwrap()
+/// );
+///
+/// assert_eq!(Some(5), parse_expression("1 + (2 * 3", &Precedence::EQUAL).unwrap_err().column);
+/// ```
+pub fn parse_expression(line: &str, precedence: &Precedence) -> Result<Expression> {
+    let mut parser = Parser { line, tokens: tokenize(line)?, position: 0, precedence };
+    let expression = parser.parse_binary(0)?;
+
+    match parser.peek() {
+        None => Ok(expression),
+        Some((column, Token::Close)) => Err(Error::at_column(line, column, "Unmatched ')'")),
+        Some((column, _)) => Err(Error::at_column(line, column, "Expected an operator")),
+    }
+}
+
+/// Parse each line of the puzzle input into an [`Expression`].
+pub fn parse_input(input: &str, precedence: &Precedence) -> Result<Vec<Expression>> {
+    parse_lines(input, |line| parse_expression(line, precedence))
+}
+
+/// Calculate the value of an expression.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_18::*;
+/// let expression = "1 + 2 * 3 + 4 * 5 + 6";
+///
+/// assert_eq!(Ok(71), evaluate(&parse_expression(expression, &Precedence::EQUAL).unwrap()));
+/// assert_eq!(Ok(231), evaluate(&parse_expression(expression, &Precedence::ADVANCED).unwrap()));
+/// assert_eq!(Ok(33), evaluate(&parse_expression(expression, &Precedence::STANDARD).unwrap()));
+/// ```
+pub fn evaluate(expression: &Expression) -> Result<u64> {
+    match expression {
+        Expression::Number(value) => Ok(*value),
+        Expression::Binary(lhs, operator, rhs) => operator.apply(evaluate(lhs)?, evaluate(rhs)?),
+    }
+}
+
+/// Sum the values of each expression, the answer for both parts.
+pub fn sum_expressions(expressions: &[Expression]) -> Result<u64> {
+    expressions.iter().try_fold(0u64, |sum, expression| Operator::Add.apply(sum, evaluate(expression)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use day_18::{evaluate, parse_expression, parse_input, sum_expressions, Associativity, Precedence};
+
+    fn eval(line: &str, precedence: &Precedence) -> u64 {
+        evaluate(&parse_expression(line, precedence).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn can_evaluate_with_equal_precedence() {
+        let examples = vec!(
+            ("1 + 2 * 3 + 4 * 5 + 6", 71),
+            ("1 + (2 * 3) + (4 * (5 + 6))", 51),
+            ("2 * 3 + (4 * 5)", 26),
+            ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 437),
+            ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12240),
+            ("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", 13632),
+        );
+
+        for (line, expected) in examples {
+            assert_eq!(expected, eval(line, &Precedence::EQUAL), "{}", line);
+        }
+    }
+
+    #[test]
+    fn can_evaluate_with_advanced_precedence() {
+        let examples = vec!(
+            ("1 + 2 * 3 + 4 * 5 + 6", 231),
+            ("1 + (2 * 3) + (4 * (5 + 6))", 51),
+            ("2 * 3 + (4 * 5)", 46),
+            ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 1445),
+            ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 669060),
+            ("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", 23340),
+        );
+
+        for (line, expected) in examples {
+            assert_eq!(expected, eval(line, &Precedence::ADVANCED), "{}", line);
+        }
+    }
+
+    #[test]
+    fn can_configure_associativity() {
+        let right = Precedence { add: (1, Associativity::Right), multiply: (1, Associativity::Right) };
+
+        assert_eq!(20, eval("2 * 3 + 7", &right));
+        assert_eq!(13, eval("2 * 3 + 7", &Precedence::EQUAL));
+        assert_eq!(13, eval("(2 * 3) + 7", &right));
+
+        let highest = Precedence { add: (u8::MAX, Associativity::Left), multiply: (1, Associativity::Left) };
+        assert_eq!(Some(3), parse_expression("2 + 3", &highest).unwrap_err().column);
+        let highest = Precedence { add: (u8::MAX, Associativity::Right), multiply: (1, Associativity::Left) };
+        assert_eq!(20, eval("2 * 3 + 7", &highest));
+    }
+
+    #[test]
+    fn can_sum_input() {
+        let input = "2 * 3 + (4 * 5)\n5 + (8 * 3 + 9 + 3 * 4 * 3)";
+
+        assert_eq!(Ok(26 + 437), sum_expressions(&parse_input(input, &Precedence::EQUAL).unwrap()));
+        assert_eq!(Ok(46 + 1445), sum_expressions(&parse_input(input, &Precedence::ADVANCED).unwrap()));
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        let column = |line: &str| parse_expression(line, &Precedence::EQUAL).unwrap_err().column;
+
+        assert_eq!(Some(5), column("1 + a"));
+        assert_eq!(Some(6), column("1 + 2)"));
+        assert_eq!(Some(3), column("1 2"));
+        assert_eq!(Some(5), column("1 + *"));
+        assert_eq!(Some(5), column("1 + "));
+        assert_eq!(Some(1), column("(1 + 2"));
+        assert_eq!(Some(1), column(""));
+
+        let err = parse_input("1 + 2\n(3 * 4", &Precedence::ADVANCED).unwrap_err();
+        assert_eq!((Some(2), Some(1)), (err.line, err.column));
+
+        assert!(evaluate(&parse_expression("99999999999 * 99999999999", &Precedence::EQUAL).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,429 @@
+//! This is my solution for [Advent of Code - Day 19](https://adventofcode.com/2020/day/19) -
+//! _Monster Messages_
+//!
+//! Check which messages match a grammar of numbered rules. Rather than building a regex, or
+//! backtracking to find a single way to match each rule, [`RuleTable::match_rule`] returns every
+//! position a rule could finish matching at. A sequence of rules is then just each of those
+//! positions fed into the next rule in turn, and alternatives are the union of the positions each
+//! one can reach. As every rule consumes at least one character, and rules can't loop back to
+//! themselves before consuming anything, a looping rule stops once it runs out of message to match,
+//! so the looping rules from part two don't need any special treatment.
+//!
+//! __Part 1__ - [`parse_input`], [`RuleTable::matches`].
+//!
+//! __Part 2__ - [`RuleTable::replace`] with [`LOOPING_RULES`].
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use regex::Regex;
+use error::{parse_lines, parse_number, Error, Result};
+use solution::Solution;
+
+/// The [`Solution`] for day 19.
+///
+/// - Part one counts the messages that match rule 0.
+/// - Part two does the same after replacing rules 8 and 11 with [`LOOPING_RULES`].
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input<'a> = (RuleTable, Vec<&'a str>);
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<(RuleTable, Vec<&'a str>)> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, (rules, messages): &(RuleTable, Vec<&str>)) -> Result<usize> {
+        Ok(count_matches(rules, messages))
+    }
+
+    fn part_two(&self, (rules, messages): &(RuleTable, Vec<&str>)) -> Result<usize> {
+        let mut rules = rules.clone();
+        rules.replace(LOOPING_RULES)?;
+        Ok(count_matches(&rules, messages))
+    }
+}
+
+/// The rule replacements for part two, which make rules 8 and 11 loop.
+pub const LOOPING_RULES: &str = "8: 42 | 42 8\n11: 42 31 | 42 11 31";
+
+/// A single rule in the grammar.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Rule {
+    /// Matches the quoted text exactly, e.g. `"a"`.
+    Literal(String),
+    /// Matches if any of the sequences of rules match in order, e.g. `1 2 | 2 1`.
+    Alternatives(Vec<Vec<usize>>),
+}
+
+impl Rule {
+    /// Parse a line of the rules section into the rule's number and the rule.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_19::*;
+    /// assert_eq!(Ok((4, Rule::Literal("a".to_string()))), Rule::from_line("4: \"a\""));
+    /// assert_eq!(
+    ///     Ok((1, Rule::Alternatives(vec!(vec!(2, 3), vec!(3, 2))))),
+    ///     Rule::from_line("1: 2 3 | 3 2")
+    /// );
+    ///
+    /// assert_eq!(Some(10), Rule::from_line("1: 2 3 | x 2").unwrap_err().column);
+    /// ```
+    pub fn from_line(line: &str) -> Result<(usize, Rule)> {
+        let re = Regex::new(r#"^(\d+): (?:"([^"]+)"|(.+))$"#).expect("Invalid Regex");
+        let cap = re.captures(line)
+            .ok_or_else(|| Error::invalid(line, "Expected a rule, e.g. '0: 1 2 | 3' or '1: \"a\"'"))?;
+
+        let id = parse_number(&cap[1], line, 1)?;
+
+        if let Some(literal) = cap.get(2) {
+            return Ok((id, Rule::Literal(literal.as_str().to_string())));
+        }
+
+        let body = cap.get(3).unwrap();
+        let mut alternatives = Vec::new();
+        let mut start = body.start();
+
+        for alternative in body.as_str().split('|') {
+            let mut sequence = Vec::new();
+            let mut offset = start;
+            for part in alternative.split(' ') {
+                if !part.is_empty() {
+                    sequence.push(parse_number(part, line, offset + 1)?);
+                }
+                offset += part.len() + 1;
+            }
+
+            if sequence.is_empty() {
+                return Err(Error::at_column(line, start + 1, "Expected at least one rule number"));
+            }
+
+            alternatives.push(sequence);
+            start += alternative.len() + 1;
+        }
+
+        Ok((id, Rule::Alternatives(alternatives)))
+    }
+}
+
+/// All the rules of the grammar, by number.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct RuleTable {
+    rules: HashMap<usize, Rule>,
+}
+
+impl RuleTable {
+    /// Parse the rules section of the puzzle input, checking the rules as in
+    /// [`RuleTable::replace`].
+    pub fn from_lines(input: &str) -> Result<RuleTable> {
+        let mut table = RuleTable::default();
+        table.replace(input)?;
+
+        if !table.rules.contains_key(&0) {
+            return Err(Error::new("Missing rule 0"));
+        }
+
+        Ok(table)
+    }
+
+    /// Add or replace the rules given, one per line, in the same format as the puzzle input. The
+    /// rules are all checked before any are applied, so if there is an error the table is left as
+    /// it was.
+    ///
+    /// As well as every rule referenced needing to be defined, rules must not be able to refer
+    /// back to themselves before matching anything, e.g. `8: 8 42`, as matching them would never
+    /// end. Every rule matches at least one character, so only the first rule of each alternative
+    /// needs to be followed to find these.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_19::*;
+    /// let mut rules = RuleTable::from_lines("0: 1 2\n1: \"a\"\n2: \"b\"").unwrap();
+    ///
+    /// assert_eq!(Some(2), rules.replace("2: 1 | 2 1\n1: 2 | \"a\"").unwrap_err().line);
+    /// assert_eq!(Some(&Rule::Literal("b".to_string())), rules.get(2));
+    ///
+    /// assert_eq!(Ok(()), rules.replace("2: 1 | 1 2"));
+    /// ```
+    pub fn replace(&mut self, input: &str) -> Result<()> {
+        let rules = parse_lines(input, Rule::from_line)?;
+        let mut replaced = self.rules.clone();
+        replaced.extend(rules.iter().cloned());
+
+        for ((index, line), (id, rule)) in input.lines().enumerate().zip(&rules) {
+            if let Rule::Alternatives(alternatives) = rule {
+                if let Some(missing) = alternatives.iter().flatten().find(|id| !replaced.contains_key(id)) {
+                    return Err(Error::invalid(line, format!("Rule {} is not defined", missing)).on_line(index + 1, line));
+                }
+            }
+
+            // the existing rules were checked when they were added, so any new loop must pass
+            // through one of the rules being added
+            if RuleTable::loops_without_matching(&replaced, *id) {
+                return Err(
+                    Error::invalid(line, format!("Rule {} can refer back to itself without matching anything", id))
+                        .on_line(index + 1, line)
+                );
+            }
+        }
+
+        self.rules = replaced;
+        Ok(())
+    }
+
+    /// Can rule `id` get back to itself by only following the first rule of its alternatives?
+    fn loops_without_matching(rules: &HashMap<usize, Rule>, id: usize) -> bool {
+        let mut visited = HashSet::new();
+        let mut stack = vec!(id);
+
+        while let Some(current) = stack.pop() {
+            if let Some(Rule::Alternatives(alternatives)) = rules.get(&current) {
+                for &first in alternatives.iter().filter_map(|sequence| sequence.first()) {
+                    if first == id {
+                        return true;
+                    }
+                    if visited.insert(first) {
+                        stack.push(first);
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Look up a rule by number.
+    pub fn get(&self, id: usize) -> Option<&Rule> {
+        self.rules.get(&id)
+    }
+
+    /// Find every position in `message` that rule `id` could finish matching at, if it starts
+    /// matching at `start`. An empty set means the rule can't match there.
+    ///
+    /// The table doesn't allow rules that refer back to themselves before matching anything, see
+    /// [`RuleTable::replace`], so each level of recursion moves further through the message.
+    pub fn match_rule(&self, id: usize, message: &str, start: usize) -> BTreeSet<usize> {
+        if start >= message.len() {
+            return BTreeSet::new();
+        }
+
+        match self.rules.get(&id) {
+            Some(Rule::Literal(literal)) => {
+                if message[start..].starts_with(literal.as_str()) {
+                    vec!(start + literal.len()).into_iter().collect()
+                } else {
+                    BTreeSet::new()
+                }
+            }
+            Some(Rule::Alternatives(alternatives)) => alternatives.iter()
+                .flat_map(|sequence| self.match_sequence(sequence, message, start))
+                .collect(),
+            None => BTreeSet::new(),
+        }
+    }
+
+    /// Find every position a sequence of rules could finish matching at.
+    fn match_sequence(&self, sequence: &[usize], message: &str, start: usize) -> BTreeSet<usize> {
+        let mut positions: BTreeSet<usize> = vec!(start).into_iter().collect();
+
+        for &id in sequence {
+            positions = positions.into_iter()
+                .flat_map(|position| self.match_rule(id, message, position))
+                .collect();
+
+            if positions.is_empty() {
+                break;
+            }
+        }
+
+        positions
+    }
+
+    /// Does rule 0 match the whole of `message`?
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_19::*;
+    /// let rules = RuleTable::from_lines(
+    ///     "0: 4 1 5\n1: 2 3 | 3 2\n2: 4 4 | 5 5\n3: 4 5 | 5 4\n4: \"a\"\n5: \"b\""
+    /// ).unwrap();
+    ///
+    /// assert!(rules.matches("ababbb"));
+    /// assert!(rules.matches("abbbab"));
+    /// assert!(!rules.matches("bababa"));
+    /// assert!(!rules.matches("aaabbb"));
+    /// assert!(!rules.matches("aaaabbb"));
+    /// ```
+    pub fn matches(&self, message: &str) -> bool {
+        self.match_rule(0, message, 0).contains(&message.len())
+    }
+}
+
+/// Split the puzzle input into the rules and the messages to check.
+pub fn parse_input(input: &str) -> Result<(RuleTable, Vec<&str>)> {
+    let mut sections = input.splitn(2, "\n\n");
+    let rules_section = sections.next().unwrap_or_default();
+    let rules = RuleTable::from_lines(rules_section)?;
+
+    let messages = sections.next().ok_or_else(|| Error::new("Missing the messages section"))?;
+    // the line before the section starts, accounting for the blank separator line
+    let offset = rules_section.lines().count() + 1;
+
+    for (index, line) in messages.lines().enumerate() {
+        if let Some(position) = line.find(|c: char| !c.is_ascii_alphanumeric()) {
+            return Err(Error::at_column(line, position + 1, "Unexpected character in message")
+                .on_line(offset + index + 1, line));
+        }
+    }
+
+    Ok((rules, messages.lines().collect()))
+}
+
+/// Count the messages that match rule 0.
+pub fn count_matches(rules: &RuleTable, messages: &[&str]) -> usize {
+    messages.iter().filter(|message| rules.matches(message)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use day_19::{count_matches, parse_input, Rule, RuleTable, LOOPING_RULES};
+
+    fn looping_example() -> &'static str {
+        "42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: \"a\"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: \"b\"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"
+    }
+
+    #[test]
+    fn can_parse_rules() {
+        assert_eq!(Ok((15, Rule::Alternatives(vec!(vec!(1), vec!(14))))), Rule::from_line("15: 1 | 14"));
+        assert_eq!(Ok((11, Rule::Alternatives(vec!(vec!(42, 11, 31))))), Rule::from_line("11: 42 11 31"));
+
+        assert!(Rule::from_line("11 42 31").is_err());
+        assert_eq!(Some(7), Rule::from_line("1: 2 | | 3").unwrap_err().column);
+
+        let err = RuleTable::from_lines("0: 1 2\n1: \"a\"").unwrap_err();
+        assert_eq!((Some(1), "Rule 2 is not defined".to_string()), (err.line, err.message));
+        assert!(RuleTable::from_lines("1: \"a\"").is_err());
+    }
+
+    #[test]
+    fn can_parse_input() {
+        let (rules, messages) = parse_input("0: 1 1\n1: \"a\"\n\naa\naaa").unwrap();
+
+        assert_eq!(Some(&Rule::Literal("a".to_string())), rules.get(1));
+        assert_eq!(vec!("aa", "aaa"), messages);
+
+        let err = parse_input("0: 1 1\n1: \"a\"\n\naa\na a").unwrap_err();
+        assert_eq!((Some(5), Some(2)), (err.line, err.column));
+        assert!(parse_input("0: 1 1\n1: \"a\"").is_err());
+    }
+
+    #[test]
+    fn can_count_matches() {
+        let (rules, messages) = parse_input(
+            "0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: \"a\"
+5: \"b\"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb"
+        ).unwrap();
+
+        assert_eq!(2, count_matches(&rules, &messages));
+    }
+
+    #[test]
+    fn can_match_looping_rules() {
+        let (mut rules, messages) = parse_input(looping_example()).unwrap();
+
+        assert_eq!(3, count_matches(&rules, &messages));
+
+        rules.replace(LOOPING_RULES).unwrap();
+        assert_eq!(12, count_matches(&rules, &messages));
+        assert!(rules.matches("babbbbaabbbbbabbbbbbaabaaabaaa"));
+        assert!(!rules.matches("aaaabbaaaabbaaa"));
+    }
+
+    #[test]
+    fn rejects_rules_that_loop_without_matching() {
+        let error = RuleTable::from_lines("0: 1\n1: 0").unwrap_err();
+        assert_eq!(Some(1), error.line);
+        assert_eq!(Some("0: 1".to_string()), error.text);
+
+        let (mut rules, messages) = parse_input(looping_example()).unwrap();
+        let original = rules.clone();
+
+        let error = rules.replace("8: 8 42").unwrap_err();
+        assert_eq!(Some(1), error.line);
+        assert_eq!(original, rules);
+
+        let error = rules.replace("8: 42 | 42 8\n11: 42 31 | 11 31").unwrap_err();
+        assert_eq!(Some(2), error.line);
+        assert_eq!(original, rules);
+        assert_eq!(3, count_matches(&rules, &messages));
+    }
+
+    #[test]
+    fn leaves_rules_unchanged_on_error() {
+        let (mut rules, _) = parse_input(looping_example()).unwrap();
+        let original = rules.clone();
+
+        assert_eq!(Some(2), rules.replace("8: 42 | 42 8\n11: 42 x").unwrap_err().line);
+        assert_eq!(original, rules);
+
+        assert_eq!(Some(2), rules.replace("8: 42 | 42 8\n11: 42 99 31").unwrap_err().line);
+        assert_eq!(original, rules);
+    }
+}
@@ -1,85 +1,1109 @@
+//! This is my solution for [Advent of Code - Day 2](https://adventofcode.com/2020/day/2) -
+//! _Password Philosophy_
+//!
+//! Each line of the input is a policy and a password, and the two parts interpret the policy
+//! differently. As well as the two puzzle validators, [`is_valid_sr`] and [`is_valid_ot`], there
+//! is a [`PolicyEngine`] that checks each password against a list of named [`Rule`]s, which can
+//! be combined with [`Rule::All`] and [`Rule::Any`], and reports why any that fail did so.
+//!
+//! An [`Audit`] summarises a whole password file instead, including lines that can't be parsed,
+//! as text or CSV so that the reports for two versions of a file can be diffed.
+//!
+//! Going the other way, a [`PasswordGenerator`] makes passwords that pass or fail a [`Validator`]
+//! for a given policy, for fuzzing the validators and generating large inputs to benchmark with.
+//!
+//! __Part 1__ - [`parse_line`], [`is_valid_sr`].
+//!
+//! __Part 2__ - [`is_valid_ot`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Display, Formatter};
 use regex::Regex;
-use std::fs;
-
-pub fn run() {
-    let contents = fs::read_to_string("res/day-2-input").expect("Failed to read file");
-    let lines_sr = contents.lines();
-    let count_sr = lines_sr.flat_map(|line| parse_line(line))
-        .filter(|(policy, password)| is_valid_sr(policy, password))
-        .count();
-    println!("There were {} valid sled rental lines", count_sr);
-
-    let lines_ot = contents.lines();
-    let count_ot = lines_ot.flat_map(|line| parse_line(line))
-        .filter(|(policy, password)| is_valid_ot(policy, password))
-        .count();
-    println!("There were {} valid Official Toboggan lines", count_ot);
-}
-
-#[derive(Debug, Eq, PartialEq)]
-struct Policy {
-    min: usize,
-    max: usize,
-    letter: char,
-}
-
-fn parse_line(line: &str) -> Option<(Policy, &str)> {
-    let re = Regex::new(r"^(\d+)-(\d+) ([a-z]): ([a-z]+)$").unwrap();
-    match re.captures(line) {
-        Some(m) => Some((
-            Policy {
-                min: m.get(1).unwrap().as_str().parse::<usize>().unwrap(),
-                max: m.get(2).unwrap().as_str().parse::<usize>().unwrap(),
-                letter: m.get(3).unwrap().as_str().parse::<char>().unwrap(),
-            },
-            m.get(4).unwrap().as_str()
-        )),
-        _ => None
-    }
-}
-
-fn is_valid_sr(policy: &Policy, password: &str) -> bool {
+use error::{parse_lines, parse_number, Error, Result};
+use output::csv_field;
+use solution::Solution;
+
+/// The [`Solution`] for day 2, counts the passwords that are valid under each of the two policies.
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input<'a> = Vec<(Policy, &'a str)>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Vec<(Policy, &'a str)>> {
+        parse_lines(input, parse_line)
+    }
+
+    fn part_one(&self, lines: &Vec<(Policy, &str)>) -> Result<usize> {
+        Ok(lines.iter().filter(|(policy, password)| is_valid_sr(policy, password)).count())
+    }
+
+    fn part_two(&self, lines: &Vec<(Policy, &str)>) -> Result<usize> {
+        Ok(lines.iter().filter(|(policy, password)| is_valid_ot(policy, password)).count())
+    }
+}
+
+/// The policy that a password must satisfy, e.g. `1-3 a`. How `min` and `max` are interpreted
+/// depends on the rules being applied, see [`is_valid_sr`] and [`is_valid_ot`].
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Policy {
+    pub min: usize,
+    pub max: usize,
+    pub letter: char,
+}
+
+/// Parse a line of the format `1-3 a: abcde` into its policy and password. It is an error if the
+/// line doesn't match that format, or the policy's range doesn't make sense.
+pub fn parse_line(line: &str) -> Result<(Policy, &str)> {
+    parse_policy_line(line, &Regex::new(r"^(\d+)-(\d+) ([a-z]): ([a-z]+)$").unwrap())
+}
+
+/// As [`parse_line`], but the password can be any characters other than whitespace, so that a
+/// [`PolicyEngine`] can check real password files rather than just the puzzle's lowercase ones.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_2::*;
+/// assert_eq!(Ok((Policy { min: 1, max: 3, letter: 'a' }, "Ab3$é")), parse_password_line("1-3 a: Ab3$é"));
+/// assert!(parse_line("1-3 a: Ab3$é").is_err());
+/// assert!(parse_password_line("1-3 a: ab cd").is_err());
+/// ```
+pub fn parse_password_line(line: &str) -> Result<(Policy, &str)> {
+    parse_policy_line(line, &Regex::new(r"^(\d+)-(\d+) ([a-z]): (\S+)$").unwrap())
+}
+
+/// Parse a policy line with a regex capturing the min, max, letter and password in that order.
+fn parse_policy_line<'a>(line: &'a str, re: &Regex) -> Result<(Policy, &'a str)> {
+    let m = re.captures(line)
+        .ok_or_else(|| Error::invalid(line, "Expected a policy and password, e.g. '1-3 a: abcde'"))?;
+
+    let min_match = m.get(1).unwrap();
+    let max_match = m.get(2).unwrap();
+    let min = parse_number(min_match.as_str(), line, min_match.start() + 1)?;
+    let max = parse_number(max_match.as_str(), line, max_match.start() + 1)?;
+
+    if min == 0 {
+        return Err(Error::at_column(line, min_match.start() + 1, "Policy positions start at 1"));
+    }
+    if max < min {
+        return Err(Error::at_column(line, max_match.start() + 1, format!("Policy maximum is less than {}", min)));
+    }
+
+    Ok((
+        Policy { min, max, letter: m.get(3).unwrap().as_str().chars().next().unwrap() },
+        m.get(4).unwrap().as_str()
+    ))
+}
+
+/// Part one, the sled rental rules: `letter` must appear between `min` and `max` times inclusive.
+pub fn is_valid_sr(policy: &Policy, password: &str) -> bool {
     let count = password.chars().filter(|&c| c == policy.letter).count();
 
-    return count >= policy.min && count <= policy.max;
+    count >= policy.min && count <= policy.max
 }
 
-fn is_valid_ot(policy: &Policy, password: &str) -> bool {
-    if password.len() < policy.max as usize {
+/// Part two, the Official Toboggan rules: `letter` must appear at exactly one of the 1-indexed
+/// positions `min` and `max`. Positions are counted in characters, and a policy with a position of
+/// 0 can't be passed.
+pub fn is_valid_ot(policy: &Policy, password: &str) -> bool {
+    if policy.min == 0 || password.chars().count() < policy.max {
         return false
     }
 
-    let mut chars = password.chars();
+    let a = password.chars().nth(policy.min - 1) == Some(policy.letter);
+    let b = password.chars().nth(policy.max - 1) == Some(policy.letter);
+
+    a != b
+}
+
+/// A set of characters that a [`Rule::Contains`] can require.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum CharacterClass {
+    Lowercase,
+    Uppercase,
+    Digit,
+    /// Anything that isn't a letter, digit or whitespace.
+    Symbol,
+}
+
+impl CharacterClass {
+    /// Is `c` in this class?
+    pub fn contains(&self, c: char) -> bool {
+        match self {
+            CharacterClass::Lowercase => c.is_lowercase(),
+            CharacterClass::Uppercase => c.is_uppercase(),
+            CharacterClass::Digit => c.is_ascii_digit(),
+            CharacterClass::Symbol => !c.is_alphanumeric() && !c.is_whitespace(),
+        }
+    }
+}
+
+impl Display for CharacterClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CharacterClass::Lowercase => write!(f, "lowercase letter"),
+            CharacterClass::Uppercase => write!(f, "uppercase letter"),
+            CharacterClass::Digit => write!(f, "digit"),
+            CharacterClass::Symbol => write!(f, "symbol"),
+        }
+    }
+}
+
+/// A check that a password must pass. The count range and positional rules use the [`Policy`]
+/// from the password's line, the others are the same for every line.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Rule {
+    /// The sled rental rules, see [`is_valid_sr`].
+    CountRange,
+    /// The Official Toboggan rules, see [`is_valid_ot`].
+    PositionalXor,
+    /// The password has at least this many characters.
+    MinLength(usize),
+    /// The password has at least this many characters from the class.
+    Contains(CharacterClass, usize),
+    /// The password doesn't contain this text.
+    Forbidden(String),
+    /// Every one of the rules passes.
+    All(Vec<Rule>),
+    /// At least one of the rules passes.
+    Any(Vec<Rule>),
+}
+
+impl Rule {
+    /// Check a password against the rule, returning why it failed if it did.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_2::*;
+    /// let policy = Policy { min: 1, max: 3, letter: 'b' };
+    ///
+    /// assert_eq!(Ok(()), Rule::MinLength(5).check(&policy, "cdefg"));
+    /// assert_eq!(
+    ///     Err("'b' appears 0 times, expected 1 to 3".to_string()),
+    ///     Rule::CountRange.check(&policy, "cdefg")
+    /// );
+    /// assert_eq!(
+    ///     Err("'b' appears 0 times, expected 1 to 3; contains 0 digits, expected at least 1".to_string()),
+    ///     Rule::All(vec!(Rule::CountRange, Rule::Contains(CharacterClass::Digit, 1))).check(&policy, "cdefg")
+    /// );
+    /// ```
+    pub fn check(&self, policy: &Policy, password: &str) -> std::result::Result<(), String> {
+        match self {
+            Rule::CountRange => {
+                if is_valid_sr(policy, password) {
+                    return Ok(());
+                }
+                let count = password.chars().filter(|&c| c == policy.letter).count();
+                Err(format!("'{}' appears {} times, expected {} to {}", policy.letter, count, policy.min, policy.max))
+            }
+            Rule::PositionalXor => {
+                if is_valid_ot(policy, password) {
+                    return Ok(());
+                }
+                if policy.min == 0 {
+                    return Err("positions start at 1".to_string());
+                }
+                if password.chars().count() < policy.max {
+                    return Err(format!("password is shorter than position {}", policy.max));
+                }
+                let at = |position: usize| password.chars().nth(position - 1) == Some(policy.letter);
+                let which = if at(policy.min) { "both" } else { "neither" };
+                Err(format!("'{}' is at {} of positions {} and {}", policy.letter, which, policy.min, policy.max))
+            }
+            Rule::MinLength(min) => {
+                let length = password.chars().count();
+                if length >= *min {
+                    return Ok(());
+                }
+                Err(format!("password has {} characters, expected at least {}", length, min))
+            }
+            Rule::Contains(class, min) => {
+                let count = password.chars().filter(|&c| class.contains(c)).count();
+                if count >= *min {
+                    return Ok(());
+                }
+                let plural = if count == 1 { "" } else { "s" };
+                Err(format!("contains {} {}{}, expected at least {}", count, class, plural, min))
+            }
+            Rule::Forbidden(text) => {
+                if !password.contains(text.as_str()) {
+                    return Ok(());
+                }
+                Err(format!("contains forbidden text '{}'", text))
+            }
+            Rule::All(rules) => {
+                let failures: Vec<String> = rules.iter()
+                    .filter_map(|rule| rule.check(policy, password).err())
+                    .collect();
+                if failures.is_empty() {
+                    return Ok(());
+                }
+                Err(failures.join("; "))
+            }
+            Rule::Any(rules) => {
+                let mut failures = Vec::new();
+                for rule in rules {
+                    match rule.check(policy, password) {
+                        Ok(()) => return Ok(()),
+                        Err(reason) => failures.push(reason),
+                    }
+                }
+                Err(format!("none of: {}", failures.join("; ")))
+            }
+        }
+    }
+}
+
+/// A list of named rules to check passwords against.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct PolicyEngine {
+    policies: Vec<(String, Rule)>,
+}
+
+impl PolicyEngine {
+    /// An engine without any policies.
+    pub fn new() -> PolicyEngine {
+        PolicyEngine::default()
+    }
+
+    /// The puzzle's two policies, `sled rental` and `official toboggan`.
+    pub fn puzzle() -> PolicyEngine {
+        PolicyEngine::new()
+            .with("sled rental", Rule::CountRange)
+            .with("official toboggan", Rule::PositionalXor)
+    }
+
+    /// Add a policy, passwords are checked against the policies in the order they were added.
+    pub fn with<S: Into<String>>(mut self, name: S, rule: Rule) -> PolicyEngine {
+        self.policies.push((name.into(), rule));
+        self
+    }
+
+    /// Check a password against each policy.
+    pub fn check(&self, policy: &Policy, password: &str) -> Vec<PolicyResult> {
+        self.policies.iter()
+            .map(|(name, rule)| PolicyResult { name: name.clone(), outcome: rule.check(policy, password) })
+            .collect()
+    }
+
+    /// Parse a password file and check each line against each policy. Lines that can't be parsed
+    /// are recorded in the report, and the rest of the file is still checked. Unlike the puzzle,
+    /// passwords can contain any characters other than whitespace, see [`parse_password_line`].
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_2::*;
+    /// let engine = PolicyEngine::puzzle().with("long", Rule::MinLength(6));
+    /// let report = engine.apply("1-3 a: abcde\n1-3 b: cdefg\n1-3 b cdefg\n2-9 c: ccccccccc");
+    ///
+    /// assert_eq!(4, report.len());
+    /// assert_eq!(2, report.count_passing("sled rental"));
+    /// assert_eq!(1, report.count_passing("official toboggan"));
+    /// assert_eq!(1, report.count_passing("long"));
+    /// assert_eq!(Some(3), report.parse_errors()[0].line);
+    /// ```
+    pub fn apply<'a>(&self, input: &'a str) -> Report<'a> {
+        let lines = input.lines()
+            .enumerate()
+            .map(|(index, text)| {
+                let line = index + 1;
+                let parsed = parse_password_line(text).map_err(|err| err.on_line(line, text));
+                let results = match &parsed {
+                    Ok((policy, password)) => self.check(policy, password),
+                    Err(_) => Vec::new(),
+                };
+                LineReport { line, parsed, results }
+            })
+            .collect();
+
+        Report { lines }
+    }
+}
+
+/// Whether a password passed one of a [`PolicyEngine`]'s policies, and why not if it failed.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PolicyResult {
+    pub name: String,
+    pub outcome: std::result::Result<(), String>,
+}
+
+impl PolicyResult {
+    pub fn passed(&self) -> bool {
+        self.outcome.is_ok()
+    }
+}
+
+/// The results of checking one line of a password file.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct LineReport<'a> {
+    /// The 1-indexed line in the password file.
+    pub line: usize,
+    /// The policy and password, or why the line couldn't be parsed.
+    pub parsed: Result<(Policy, &'a str)>,
+    /// The result of each policy, in the order they were added to the engine. Empty if the line
+    /// couldn't be parsed.
+    pub results: Vec<PolicyResult>,
+}
+
+impl LineReport<'_> {
+    /// Was the line parsed, and did the password pass every policy?
+    pub fn passed(&self) -> bool {
+        self.parsed.is_ok() && self.results.iter().all(|result| result.passed())
+    }
+}
+
+/// Formats the line followed by an indented line for each policy, e.g.
+///
+/// ```text
+/// line 2, 1-3 b: cdefg
+///     sled rental: failed - 'b' appears 0 times, expected 1 to 3
+///     official toboggan: failed - 'b' is at neither of positions 1 and 3
+/// ```
+///
+/// Or if the line couldn't be parsed, the reason why:
+///
+/// ```text
+/// line 3, 1-3 b cdefg
+///     failed to parse - Expected a policy and password, e.g. '1-3 a: abcde'
+/// ```
+impl Display for LineReport<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (policy, password) = match &self.parsed {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                let text = err.text.as_deref().unwrap_or_default();
+                return write!(f, "line {}, {}\n    failed to parse - {}", self.line, text, err.message);
+            }
+        };
+
+        write!(f, "line {}, {}-{} {}: {}", self.line, policy.min, policy.max, policy.letter, password)?;
+        for result in &self.results {
+            match &result.outcome {
+                Ok(()) => write!(f, "\n    {}: passed", result.name)?,
+                Err(reason) => write!(f, "\n    {}: failed - {}", result.name, reason)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The results of checking every line of a password file, see [`PolicyEngine::apply`].
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Report<'a> {
+    pub lines: Vec<LineReport<'a>>,
+}
+
+impl Report<'_> {
+    /// The number of lines checked.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Were there no lines to check?
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Why each line that couldn't be parsed failed, in line order.
+    pub fn parse_errors(&self) -> Vec<&Error> {
+        self.lines.iter().filter_map(|line| line.parsed.as_ref().err()).collect()
+    }
+
+    /// How many passwords passed the named policy.
+    pub fn count_passing(&self, name: &str) -> usize {
+        self.lines.iter()
+            .filter(|line| line.results.iter().any(|result| result.name == name && result.passed()))
+            .count()
+    }
+}
+
+impl Display for Report<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self.lines.iter().map(|line| line.to_string()).collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// The two puzzle validators, so that they can be chosen at runtime.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Validator {
+    /// See [`is_valid_sr`].
+    SledRental,
+    /// See [`is_valid_ot`].
+    OfficialToboggan,
+}
+
+impl Validator {
+    pub fn is_valid(&self, policy: &Policy, password: &str) -> bool {
+        match self {
+            Validator::SledRental => is_valid_sr(policy, password),
+            Validator::OfficialToboggan => is_valid_ot(policy, password),
+        }
+    }
+}
+
+/// Generates lowercase passwords that pass or fail a [`Validator`]. The passwords only depend on
+/// the seed and the order of the calls, so runs can be repeated.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PasswordGenerator {
+    state: u64,
+}
+
+impl PasswordGenerator {
+    pub fn new(seed: u64) -> PasswordGenerator {
+        PasswordGenerator { state: seed }
+    }
+
+    /// The next number from a [SplitMix64](https://prng.di.unimi.it/splitmix64.c) sequence.
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `0` up to, but not including, `bound`.
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    /// A number between `min` and `max` inclusive.
+    fn between(&mut self, min: usize, max: usize) -> usize {
+        min + self.below(max - min + 1)
+    }
+
+    /// A random lowercase letter other than `excluded`.
+    fn letter_except(&mut self, excluded: char) -> char {
+        loop {
+            let letter = (b'a' + self.below(26) as u8) as char;
+            if letter != excluded {
+                return letter;
+            }
+        }
+    }
+
+    /// Generate a password of `length` characters that passes the validator for `policy` if
+    /// `valid` is set, or fails it otherwise. Returns `None` if there is no such password, e.g. a
+    /// password too short to contain the letter `min` times can't pass the sled rental rules, or
+    /// if the policy's positions don't make sense for the official toboggan rules.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_2::*;
+    /// let policy = Policy { min: 1, max: 3, letter: 'a' };
+    /// let mut generator = PasswordGenerator::new(2020);
+    ///
+    /// let password = generator.generate(Validator::SledRental, &policy, 8, true).unwrap();
+    /// assert_eq!(8, password.len());
+    /// assert!(is_valid_sr(&policy, &password));
+    ///
+    /// let password = generator.generate(Validator::OfficialToboggan, &policy, 8, false).unwrap();
+    /// assert!(!is_valid_ot(&policy, &password));
+    ///
+    /// assert_eq!(None, generator.generate(Validator::OfficialToboggan, &policy, 2, true));
+    /// ```
+    pub fn generate(&mut self, validator: Validator, policy: &Policy, length: usize, valid: bool) -> Option<String> {
+        match (validator, valid) {
+            (Validator::SledRental, _) => {
+                // the number of times the letter can appear to get the result wanted
+                let counts: Vec<usize> = (0..=length)
+                    .filter(|count| (*count >= policy.min && *count <= policy.max) == valid)
+                    .collect();
+                if counts.is_empty() {
+                    return None;
+                }
+                let count = counts[self.below(counts.len())];
+                Some(self.with_letter_count(policy.letter, length, count))
+            }
+            (Validator::OfficialToboggan, _) if policy.min == 0 || policy.min > policy.max => None,
+            (Validator::OfficialToboggan, true) => {
+                if length < policy.max || policy.min == policy.max {
+                    return None;
+                }
+                let mut password = self.with_letter_count(policy.letter, length, 0);
+                let position = if self.below(2) == 0 { policy.min } else { policy.max };
+                password.replace_range(position - 1..position, &policy.letter.to_string());
+                Some(password)
+            }
+            (Validator::OfficialToboggan, false) => {
+                let mut password: Vec<char> = (0..length).map(|_| self.letter_except(policy.letter)).collect();
+                // too short passwords fail regardless, otherwise the letter goes in both positions or neither
+                if length >= policy.max && self.below(2) == 0 {
+                    password[policy.min - 1] = policy.letter;
+                    password[policy.max - 1] = policy.letter;
+                }
+                Some(password.into_iter().collect())
+            }
+        }
+    }
+
+    /// A password of `length` characters containing `letter` exactly `count` times.
+    fn with_letter_count(&mut self, letter: char, length: usize, count: usize) -> String {
+        let mut password: Vec<char> = (0..length).map(|_| self.letter_except(letter)).collect();
+
+        // shuffle the first `count` positions into place, a partial Fisher-Yates shuffle
+        let mut positions: Vec<usize> = (0..length).collect();
+        for index in 0..count {
+            let swap = self.between(index, length - 1);
+            positions.swap(index, swap);
+            password[positions[index]] = letter;
+        }
+
+        password.into_iter().collect()
+    }
+
+    /// Generate a puzzle input of `lines` random policies, each with a password of up to
+    /// `max_length` characters. Each password is generated to pass or fail one of the validators,
+    /// chosen at random, so that both validators have a mix of passwords to accept and reject.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_2::*;
+    /// let input = PasswordGenerator::new(1).generate_input(100, 20);
+    ///
+    /// assert_eq!(100, input.lines().count());
+    /// assert_eq!(100, Audit::new(&input).lines.iter().filter(|line| line.parsed.is_ok()).count());
+    /// ```
+    pub fn generate_input(&mut self, lines: usize, max_length: usize) -> String {
+        (0..lines)
+            .map(|_| {
+                let length = self.between(1, max_length.max(1));
+                let min = self.between(1, length);
+                let max = self.between(min, length);
+                let policy = Policy { min, max, letter: (b'a' + self.below(26) as u8) as char };
+
+                let validator = if self.below(2) == 0 { Validator::SledRental } else { Validator::OfficialToboggan };
+                let valid = self.below(2) == 0;
+                let password = self.generate(validator, &policy, length, valid)
+                    .unwrap_or_else(|| self.with_letter_count(policy.letter, length, 0));
+
+                format!("{}-{} {}: {}", policy.min, policy.max, policy.letter, password)
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// The column names printed as the first row of [`Audit::to_csv`].
+pub const AUDIT_CSV_HEADER: &str = "line,min,max,letter,password,sled_rental,official_toboggan,duplicate_of,error";
+
+/// A line of a password file, as seen by an [`Audit`].
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AuditLine<'a> {
+    /// The 1-indexed line in the password file.
+    pub line: usize,
+    /// The policy and password, or why the line couldn't be parsed.
+    pub parsed: Result<(Policy, &'a str)>,
+    /// The first earlier line with the same password, if there is one.
+    pub duplicate_of: Option<usize>,
+}
+
+/// A summary of every line of a password file. Unlike the [`Solution`], lines that can't be parsed
+/// are recorded rather than stopping the whole file being checked.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Audit<'a> {
+    pub lines: Vec<AuditLine<'a>>,
+}
+
+impl<'a> Audit<'a> {
+    /// Parse and check each line of a password file.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_2::*;
+    /// let audit = Audit::new("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n1-3 a abcde\n1-2 b: abcde");
+    ///
+    /// assert_eq!(vec!(2), audit.failing_sled_rental());
+    /// assert_eq!(vec!(2, 3), audit.failing_official_toboggan());
+    /// assert_eq!(Some(4), audit.parse_errors()[0].line);
+    /// assert_eq!(Some(1), audit.lines[4].duplicate_of);
+    /// ```
+    pub fn new(input: &'a str) -> Audit<'a> {
+        let mut first_seen: HashMap<&str, usize> = HashMap::new();
+
+        let lines = input.lines()
+            .enumerate()
+            .map(|(index, text)| {
+                let line = index + 1;
+                let parsed = parse_line(text).map_err(|err| err.on_line(line, text));
+                let duplicate_of = match &parsed {
+                    Ok((_, password)) => match first_seen.get(password) {
+                        Some(&first) => Some(first),
+                        None => {
+                            first_seen.insert(password, line);
+                            None
+                        }
+                    },
+                    Err(_) => None,
+                };
+
+                AuditLine { line, parsed, duplicate_of }
+            })
+            .collect();
+
+        Audit { lines }
+    }
+
+    fn parsed(&self) -> impl Iterator<Item=(usize, &Policy, &'a str)> + '_ {
+        self.lines.iter().filter_map(|line| match &line.parsed {
+            Ok((policy, password)) => Some((line.line, policy, *password)),
+            Err(_) => None,
+        })
+    }
+
+    /// How many of the parsed lines have a policy for each letter.
+    pub fn letters(&self) -> BTreeMap<char, usize> {
+        let mut letters = BTreeMap::new();
+        for (_, policy, _) in self.parsed() {
+            *letters.entry(policy.letter).or_insert(0) += 1;
+        }
+        letters
+    }
+
+    /// The lines whose password fails the sled rental rules, see [`is_valid_sr`].
+    pub fn failing_sled_rental(&self) -> Vec<usize> {
+        self.parsed()
+            .filter(|(_, policy, password)| !is_valid_sr(policy, password))
+            .map(|(line, _, _)| line)
+            .collect()
+    }
+
+    /// The lines whose password fails the Official Toboggan rules, see [`is_valid_ot`].
+    pub fn failing_official_toboggan(&self) -> Vec<usize> {
+        self.parsed()
+            .filter(|(_, policy, password)| !is_valid_ot(policy, password))
+            .map(|(line, _, _)| line)
+            .collect()
+    }
+
+    /// The errors for lines that couldn't be parsed.
+    pub fn parse_errors(&self) -> Vec<&Error> {
+        self.lines.iter().filter_map(|line| line.parsed.as_ref().err()).collect()
+    }
+
+    /// The passwords that are used on more than one line, and the lines they are used on.
+    pub fn duplicates(&self) -> BTreeMap<&'a str, Vec<usize>> {
+        let mut used: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (line, _, password) in self.parsed() {
+            used.entry(password).or_default().push(line);
+        }
+        used.retain(|_, lines| lines.len() > 1);
+        used
+    }
+
+    /// Format the audit as comma separated values with one row per line of the password file, and
+    /// [`AUDIT_CSV_HEADER`] as the first row. Lines that couldn't be parsed only have the `line`
+    /// and `error` columns filled in.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_2::*;
+    /// assert_eq!(
+    ///     "line,min,max,letter,password,sled_rental,official_toboggan,duplicate_of,error
+    /// 1,1,3,a,abcde,pass,pass,,
+    /// 2,,,,,,,,Policy positions start at 1",
+    ///     Audit::new("1-3 a: abcde\n0-3 a: abcde").to_csv()
+    /// );
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let pass_fail = |valid: bool| if valid { "pass" } else { "fail" };
 
-    let a = chars.nth(policy.min - 1).unwrap();
-    let b = chars.nth(policy.max - policy.min - 1).unwrap();
+        let rows = self.lines.iter().map(|line| match &line.parsed {
+            Ok((policy, password)) => format!(
+                "{},{},{},{},{},{},{},{},",
+                line.line,
+                policy.min,
+                policy.max,
+                csv_field(&policy.letter.to_string()),
+                csv_field(password),
+                pass_fail(is_valid_sr(policy, password)),
+                pass_fail(is_valid_ot(policy, password)),
+                line.duplicate_of.map(|first| first.to_string()).unwrap_or_default(),
+            ),
+            Err(err) => format!("{},,,,,,,,{}", line.line, csv_field(&err.message)),
+        });
 
-    return a != b && (a == policy.letter || b == policy.letter)
+        vec!(AUDIT_CSV_HEADER.to_string()).into_iter().chain(rows).collect::<Vec<String>>().join("\n")
+    }
+}
+
+/// Formats a summary of the audit, e.g.
+///
+/// ```text
+/// 5 lines, 1 failed to parse
+///
+/// Policy letters:
+///     a: 1
+///     b: 2
+///     c: 1
+///
+/// Failing sled rental (1): 2
+/// Failing official toboggan (2): 2, 3
+///
+/// Failed to parse (1):
+///     line 4: Expected a policy and password, e.g. '1-3 a: abcde'
+///
+/// Duplicate passwords (1):
+///     abcde: lines 1, 5
+/// ```
+impl Display for Audit<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let list = |lines: &[usize]| match lines {
+            [] => "none".to_string(),
+            lines => lines.iter().map(|line| line.to_string()).collect::<Vec<String>>().join(", "),
+        };
+
+        let errors = self.parse_errors();
+        writeln!(f, "{} lines, {} failed to parse", self.lines.len(), errors.len())?;
+
+        write!(f, "\nPolicy letters:")?;
+        for (letter, count) in self.letters() {
+            write!(f, "\n    {}: {}", letter, count)?;
+        }
+
+        let sled_rental = self.failing_sled_rental();
+        let official_toboggan = self.failing_official_toboggan();
+        writeln!(f, "\n\nFailing sled rental ({}): {}", sled_rental.len(), list(&sled_rental))?;
+        write!(f, "Failing official toboggan ({}): {}", official_toboggan.len(), list(&official_toboggan))?;
+
+        write!(f, "\n\nFailed to parse ({}):", errors.len())?;
+        for err in errors {
+            write!(f, "\n    line {}: {}", err.line.unwrap_or_default(), err.message)?;
+        }
+
+        let duplicates = self.duplicates();
+        write!(f, "\n\nDuplicate passwords ({}):", duplicates.len())?;
+        for (password, lines) in duplicates {
+            write!(f, "\n    {}: lines {}", password, list(&lines))?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use day_2::{parse_line, Policy, is_valid_sr, is_valid_ot};
+    use error::parse_lines;
+    use day_2::{
+        parse_line, parse_password_line, Policy, is_valid_sr, is_valid_ot, Audit, CharacterClass, PasswordGenerator,
+        PolicyEngine, Rule, Validator,
+    };
+    use error::Error;
 
     #[test]
     fn can_parse_line() {
-        assert_eq!(parse_line("1-3 a: abcde"), Some((Policy { min: 1, max: 3, letter: 'a' }, "abcde")));
-        assert_eq!(parse_line("1-3 b: cdefg"), Some((Policy { min: 1, max: 3, letter: 'b' }, "cdefg")));
-        assert_eq!(parse_line("2-9 c: ccccccccc"), Some((Policy { min: 2, max: 9, letter: 'c' }, "ccccccccc")));
-        assert_eq!(parse_line("29 c: ccccccccc"), None);
+        assert_eq!(parse_line("1-3 a: abcde"), Ok((Policy { min: 1, max: 3, letter: 'a' }, "abcde")));
+        assert_eq!(parse_line("1-3 b: cdefg"), Ok((Policy { min: 1, max: 3, letter: 'b' }, "cdefg")));
+        assert_eq!(parse_line("2-9 c: ccccccccc"), Ok((Policy { min: 2, max: 9, letter: 'c' }, "ccccccccc")));
+        assert_eq!(
+            parse_line("29 c: ccccccccc"),
+            Err(Error::invalid("29 c: ccccccccc", "Expected a policy and password, e.g. '1-3 a: abcde'"))
+        );
+        assert_eq!(parse_line("0-3 a: abcde").unwrap_err().column, Some(1));
+        assert_eq!(parse_line("3-1 a: abcde").unwrap_err().column, Some(3));
     }
 
     #[test]
     fn can_validate_sled_rental() {
-        assert_eq!(is_valid_sr(&Policy { min: 1, max: 3, letter: 'a' }, "abcde"), true);
-        assert_eq!(is_valid_sr(&Policy { min: 1, max: 3, letter: 'b' }, "cdefg"), false);
-        assert_eq!(is_valid_sr(&Policy { min: 2, max: 9, letter: 'c' }, "ccccccccc"), true);
+        assert!(is_valid_sr(&Policy { min: 1, max: 3, letter: 'a' }, "abcde"));
+        assert!(!is_valid_sr(&Policy { min: 1, max: 3, letter: 'b' }, "cdefg"));
+        assert!(is_valid_sr(&Policy { min: 2, max: 9, letter: 'c' }, "ccccccccc"));
     }
 
     #[test]
     fn can_validate_official_toboggan() {
-        assert_eq!(is_valid_ot(&Policy { min: 1, max: 3, letter: 'a' }, "abcde"), true);
-        assert_eq!(is_valid_ot(&Policy { min: 1, max: 3, letter: 'b' }, "cdefg"), false);
-        assert_eq!(is_valid_ot(&Policy { min: 2, max: 9, letter: 'c' }, "ccccccccc"), false);
+        assert!(is_valid_ot(&Policy { min: 1, max: 3, letter: 'a' }, "abcde"));
+        assert!(!is_valid_ot(&Policy { min: 1, max: 3, letter: 'b' }, "cdefg"));
+        assert!(!is_valid_ot(&Policy { min: 2, max: 9, letter: 'c' }, "ccccccccc"));
+
+        // positions are in characters, not bytes
+        assert!(!is_valid_ot(&Policy { min: 1, max: 3, letter: 'a' }, "éa"));
+        assert!(is_valid_ot(&Policy { min: 1, max: 3, letter: 'a' }, "éba"));
+        assert!(!is_valid_ot(&Policy { min: 0, max: 1, letter: 'a' }, "abc"));
+        assert!(!is_valid_ot(&Policy { min: 3, max: 2, letter: 'a' }, "ab"));
+
+        let xor = |min: usize, max: usize, password: &str|
+            Rule::PositionalXor.check(&Policy { min, max, letter: 'a' }, password);
+        assert_eq!(Err("password is shorter than position 3".to_string()), xor(1, 3, "éa"));
+        assert_eq!(Err("positions start at 1".to_string()), xor(0, 1, "abc"));
+    }
+
+    #[test]
+    fn can_check_rules() {
+        let policy = Policy { min: 2, max: 4, letter: 'c' };
+        let check = |rule: Rule, password: &str| rule.check(&policy, password);
+
+        assert_eq!(Ok(()), check(Rule::CountRange, "acbc"));
+        assert_eq!(Err("'c' appears 5 times, expected 2 to 4".to_string()), check(Rule::CountRange, "ccccc"));
+
+        assert_eq!(Ok(()), check(Rule::PositionalXor, "acbd"));
+        assert_eq!(Err("'c' is at both of positions 2 and 4".to_string()), check(Rule::PositionalXor, "acbc"));
+        assert_eq!(Err("'c' is at neither of positions 2 and 4".to_string()), check(Rule::PositionalXor, "cacb"));
+        assert_eq!(Err("password is shorter than position 4".to_string()), check(Rule::PositionalXor, "ac"));
+
+        assert_eq!(Ok(()), check(Rule::MinLength(4), "abcd"));
+        assert_eq!(Err("password has 3 characters, expected at least 4".to_string()), check(Rule::MinLength(4), "abc"));
+
+        assert_eq!(Ok(()), check(Rule::Contains(CharacterClass::Uppercase, 2), "aBcD"));
+        assert_eq!(
+            Err("contains 1 symbol, expected at least 2".to_string()),
+            check(Rule::Contains(CharacterClass::Symbol, 2), "a1!b c")
+        );
+        assert_eq!(
+            Err("contains 0 lowercase letters, expected at least 1".to_string()),
+            check(Rule::Contains(CharacterClass::Lowercase, 1), "ABC")
+        );
+
+        assert_eq!(Ok(()), check(Rule::Forbidden("abc".to_string()), "acbc"));
+        assert_eq!(Err("contains forbidden text 'cb'".to_string()), check(Rule::Forbidden("cb".to_string()), "acbc"));
+    }
+
+    #[test]
+    fn can_compose_rules() {
+        let policy = Policy { min: 1, max: 3, letter: 'a' };
+        let strong = Rule::All(vec!(
+            Rule::MinLength(6),
+            Rule::Any(vec!(Rule::Contains(CharacterClass::Digit, 1), Rule::Contains(CharacterClass::Symbol, 1))),
+        ));
+
+        assert_eq!(Ok(()), strong.check(&policy, "abcde1"));
+        assert_eq!(Ok(()), strong.check(&policy, "abcde!"));
+        assert_eq!(
+            Err("password has 5 characters, expected at least 6; \
+                none of: contains 0 digits, expected at least 1; contains 0 symbols, expected at least 1".to_string()),
+            strong.check(&policy, "abcde")
+        );
+
+        assert_eq!(Ok(()), Rule::All(vec!()).check(&policy, ""));
+        assert!(Rule::Any(vec!()).check(&policy, "").is_err());
+    }
+
+    #[test]
+    fn can_apply_policy_engine() {
+        let engine = PolicyEngine::puzzle().with("no repeats", Rule::Forbidden("cc".to_string()));
+        let report = engine.apply("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc");
+
+        assert_eq!(3, report.len());
+        assert_eq!(2, report.count_passing("sled rental"));
+        assert_eq!(1, report.count_passing("official toboggan"));
+        assert_eq!(2, report.count_passing("no repeats"));
+        assert_eq!(0, report.count_passing("unknown"));
+        assert!(report.lines[0].passed());
+        assert!(!report.lines[2].passed());
+
+        assert_eq!(
+            "line 1, 1-3 a: abcde
+    sled rental: passed
+    official toboggan: passed
+    no repeats: passed
+line 2, 1-3 b: cdefg
+    sled rental: failed - 'b' appears 0 times, expected 1 to 3
+    official toboggan: failed - 'b' is at neither of positions 1 and 3
+    no repeats: passed
+line 3, 2-9 c: ccccccccc
+    sled rental: passed
+    official toboggan: failed - 'c' is at both of positions 2 and 9
+    no repeats: failed - contains forbidden text 'cc'",
+            report.to_string()
+        );
+
+        assert!(PolicyEngine::new().apply("1-3 a: abcde").lines[0].results.is_empty());
+    }
+
+    #[test]
+    fn can_apply_policy_engine_to_any_passwords() {
+        let engine = PolicyEngine::new()
+            .with("uppercase", Rule::Contains(CharacterClass::Uppercase, 1))
+            .with("digits", Rule::Contains(CharacterClass::Digit, 2))
+            .with("symbol", Rule::Contains(CharacterClass::Symbol, 1))
+            .with("sled rental", Rule::CountRange);
+        let report = engine.apply("1-2 a: Passw0rd!\n1-3 s: s3cr3t$\n1-1 s: Résumé-42\n1-1 x: lowercase");
+
+        assert_eq!(4, report.len());
+        assert!(report.parse_errors().is_empty());
+        assert_eq!(2, report.count_passing("uppercase"));
+        assert_eq!(2, report.count_passing("digits"));
+        assert_eq!(3, report.count_passing("symbol"));
+        assert_eq!(3, report.count_passing("sled rental"));
+        assert!(report.lines[2].passed());
+        assert!(!report.lines[3].passed());
+    }
+
+    #[test]
+    fn can_parse_password_lines() {
+        assert_eq!(Ok((Policy { min: 1, max: 3, letter: 'a' }, "abcde")), parse_password_line("1-3 a: abcde"));
+        assert_eq!(
+            Ok((Policy { min: 2, max: 9, letter: 'c' }, "C0rrect-h0rse")),
+            parse_password_line("2-9 c: C0rrect-h0rse")
+        );
+        assert_eq!(Some(1), parse_password_line("0-3 a: Abc").unwrap_err().column);
+        assert!(parse_password_line("1-3 a: ").is_err());
+        assert!(parse_password_line("1-3 a: two words").is_err());
+    }
+
+    #[test]
+    fn can_report_lines_that_fail_to_parse() {
+        let report = PolicyEngine::puzzle().apply("1-3 a: abcde\n1-3 b cdefg\n2-9 c: ccccccccc");
+
+        assert_eq!(3, report.len());
+        assert_eq!(2, report.count_passing("sled rental"));
+        assert_eq!(1, report.count_passing("official toboggan"));
+        assert!(!report.lines[1].passed());
+        assert!(report.lines[1].results.is_empty());
+        assert!(report.lines[2].parsed.is_ok());
+
+        let errors = report.parse_errors();
+        assert_eq!(1, errors.len());
+        assert_eq!(Some(2), errors[0].line);
+
+        assert_eq!(
+            "line 1, 1-3 a: abcde
+    sled rental: passed
+    official toboggan: passed
+line 2, 1-3 b cdefg
+    failed to parse - Expected a policy and password, e.g. '1-3 a: abcde'
+line 3, 2-9 c: ccccccccc
+    sled rental: passed
+    official toboggan: failed - 'c' is at both of positions 2 and 9",
+            report.to_string()
+        );
+    }
+
+    #[test]
+    fn can_audit_password_file() {
+        let audit = Audit::new("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n1-3 a abcde\n1-2 b: abcde\n3-1 b: cdefg");
+
+        assert_eq!(6, audit.lines.len());
+        assert_eq!(vec!(('a', 1), ('b', 2), ('c', 1)), audit.letters().into_iter().collect::<Vec<(char, usize)>>());
+        assert_eq!(vec!(2), audit.failing_sled_rental());
+        assert_eq!(vec!(2, 3), audit.failing_official_toboggan());
+        assert_eq!(
+            vec!((Some(4), None), (Some(6), Some(3))),
+            audit.parse_errors().iter().map(|err| (err.line, err.column)).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&vec!(1, 5)), audit.duplicates().get("abcde"));
+        assert_eq!(1, audit.duplicates().len());
+
+        assert_eq!(
+            "6 lines, 2 failed to parse
+
+Policy letters:
+    a: 1
+    b: 2
+    c: 1
+
+Failing sled rental (1): 2
+Failing official toboggan (2): 2, 3
+
+Failed to parse (2):
+    line 4: Expected a policy and password, e.g. '1-3 a: abcde'
+    line 6: Policy maximum is less than 3
+
+Duplicate passwords (1):
+    abcde: lines 1, 5",
+            audit.to_string()
+        );
+
+        assert_eq!(
+            "line,min,max,letter,password,sled_rental,official_toboggan,duplicate_of,error
+1,1,3,a,abcde,pass,pass,,
+2,1,3,b,cdefg,fail,fail,,
+3,2,9,c,ccccccccc,pass,fail,,
+4,,,,,,,,\"Expected a policy and password, e.g. '1-3 a: abcde'\"
+5,1,2,b,abcde,pass,pass,1,
+6,,,,,,,,Policy maximum is less than 3",
+            audit.to_csv()
+        );
+    }
+
+    #[test]
+    fn can_audit_empty_file() {
+        let audit = Audit::new("");
+
+        assert_eq!(
+            "0 lines, 0 failed to parse
+
+Policy letters:
+
+Failing sled rental (0): none
+Failing official toboggan (0): none
+
+Failed to parse (0):
+
+Duplicate passwords (0):",
+            audit.to_string()
+        );
+        assert_eq!("line,min,max,letter,password,sled_rental,official_toboggan,duplicate_of,error", audit.to_csv());
+    }
+
+    #[test]
+    fn cannot_generate_passwords_for_invalid_positions() {
+        let mut generator = PasswordGenerator::new(0);
+
+        for policy in [Policy { min: 0, max: 3, letter: 'a' }, Policy { min: 4, max: 2, letter: 'a' }].iter() {
+            for &valid in [true, false].iter() {
+                assert_eq!(None, generator.generate(Validator::OfficialToboggan, policy, 5, valid));
+            }
+        }
+    }
+
+    #[test]
+    fn can_generate_passwords() {
+        let mut generator = PasswordGenerator::new(0);
+        let validators = [Validator::SledRental, Validator::OfficialToboggan];
+
+        for length in 1..=12 {
+            for min in 1..=length + 1 {
+                for max in min..=length + 1 {
+                    let policy = Policy { min, max, letter: 'q' };
+                    for &validator in validators.iter() {
+                        for &valid in [true, false].iter() {
+                            for _ in 0..5 {
+                                match generator.generate(validator, &policy, length, valid) {
+                                    Some(password) => {
+                                        assert_eq!(length, password.len());
+                                        assert!(password.chars().all(|c| c.is_ascii_lowercase()));
+                                        assert_eq!(
+                                            valid,
+                                            validator.is_valid(&policy, &password),
+                                            "{:?} {:?} {}", validator, policy, password
+                                        );
+                                    }
+                                    None => {
+                                        // only when there is no password that would do
+                                        let possible = match (validator, valid) {
+                                            (Validator::SledRental, true) => min <= length,
+                                            (Validator::SledRental, false) => min > 0 || max < length,
+                                            (Validator::OfficialToboggan, true) => max <= length && min != max,
+                                            (Validator::OfficialToboggan, false) => true,
+                                        };
+                                        assert!(!possible, "{:?} {:?} {} {}", validator, policy, length, valid);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generates_repeatable_inputs() {
+        let input = PasswordGenerator::new(2020).generate_input(1000, 30);
+
+        assert_eq!(input, PasswordGenerator::new(2020).generate_input(1000, 30));
+        assert_ne!(input, PasswordGenerator::new(2021).generate_input(1000, 30));
+
+        let lines = parse_lines(&input, parse_line).unwrap();
+        assert_eq!(1000, lines.len());
+
+        // both validators should have plenty to accept and reject
+        let sled_rental = lines.iter().filter(|(policy, password)| is_valid_sr(policy, password)).count();
+        let official_toboggan = lines.iter().filter(|(policy, password)| is_valid_ot(policy, password)).count();
+        assert!(sled_rental > 100 && sled_rental < 900, "{}", sled_rental);
+        assert!(official_toboggan > 100 && official_toboggan < 900, "{}", official_toboggan);
     }
 }
@@ -0,0 +1,553 @@
+//! This is my solution for [Advent of Code - Day 20](https://adventofcode.com/2020/day/20) -
+//! _Jurassic Jigsaw_
+//!
+//! Reassemble an image from square tiles that have been shuffled, rotated and flipped. The images
+//! are stored in the [`Grid`] from day 11, with `true` for `#`. Each tile can be in one of eight
+//! orientations, generated by [`orientations`] from a quarter turn ([`rotate`]) and a mirror image
+//! ([`flip`]).
+//!
+//! The puzzle input is constructed so that each edge only matches one other tile's edge, so the
+//! corners can be found without assembling anything, they are the tiles with two edges that don't
+//! match any other tile. Assembling the image then just needs one of those corners turned so that
+//! its unmatched edges are on the outside, after which every other tile has exactly one place it
+//! can go.
+//!
+//! __Part 1__ - [`parse_input`], [`find_corners`].
+//!
+//! __Part 2__ - [`assemble`], [`water_roughness`].
+
+use std::collections::{HashMap, HashSet};
+use regex::Regex;
+use day_11::Grid;
+use error::{parse_lines, parse_number, Error, Result};
+use solution::Solution;
+
+/// The [`Solution`] for day 20.
+///
+/// - Part one multiplies together the ids of the four corner tiles.
+/// - Part two assembles the image and counts the `#`s that aren't part of a sea monster.
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input<'a> = Vec<Tile>;
+    type PartOne = u64;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<Tile>> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, tiles: &Vec<Tile>) -> Result<u64> {
+        let corners = find_corners(tiles);
+        if corners.len() != 4 {
+            return Err(Error::new(format!("Expected 4 corner tiles, found {}", corners.len())));
+        }
+
+        Ok(corners.iter().product())
+    }
+
+    fn part_two(&self, tiles: &Vec<Tile>) -> Result<usize> {
+        water_roughness(&assemble(tiles)?)
+    }
+}
+
+/// A piece of the image, with its id.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Tile {
+    pub id: u64,
+    pub image: Grid<bool>,
+}
+
+/// Parse a block of `#` and `.` into a grid. Every row must be the same width.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_11::Grid;
+/// # use advent_of_code_2020::day_20::*;
+/// let image = parse_image("#.\n.#\n##").unwrap();
+///
+/// assert_eq!((2, 3), image.size());
+/// assert_eq!(Some(&true), image.get(0, 0));
+/// assert_eq!(Some(&false), image.get(1, 0));
+///
+/// assert_eq!(Some(2), parse_image("#.\n.").unwrap_err().line);
+/// ```
+pub fn parse_image(input: &str) -> Result<Grid<bool>> {
+    let rows: Vec<Vec<bool>> = parse_lines(input, |line| {
+        line.chars()
+            .enumerate()
+            .map(|(x, char)| match char {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                other => Err(Error::at_column(line, x + 1, format!("Invalid char '{}'", other))),
+            })
+            .collect()
+    })?;
+
+    let width = rows.first().map(|row| row.len()).unwrap_or(0);
+    if width == 0 {
+        return Err(Error::new("Expected at least one row of pixels"));
+    }
+
+    for (y, (row, line)) in rows.iter().zip(input.lines()).enumerate() {
+        if row.len() != width {
+            return Err(Error::invalid(line, format!("Expected {} pixels, found {}", width, row.len()))
+                .on_line(y + 1, line));
+        }
+    }
+
+    Ok(Grid::from_fn(width, rows.len(), |x, y| rows[y][x]))
+}
+
+/// Parse a tile, a `Tile <id>:` header followed by its image. The image must be square, and at least
+/// 3x3 so that there is something left once the borders are removed.
+pub fn parse_tile(section: &str) -> Result<Tile> {
+    let re = Regex::new(r"^Tile (\d+):$").expect("Invalid Regex");
+    let mut parts = section.splitn(2, '\n');
+    let header = parts.next().unwrap_or_default();
+
+    let cap = re.captures(header)
+        .ok_or_else(|| Error::invalid(header, "Expected a header, e.g. 'Tile 2311:'").on_line(1, header))?;
+    let id = parse_number(&cap[1], header, 6).map_err(|err| err.on_line(1, header))?;
+
+    let image = parse_image(parts.next().unwrap_or_default())
+        .map_err(|err| err.offset_lines(1))?;
+    let (width, height) = image.size();
+    if width != height {
+        return Err(Error::invalid(header, format!("Tile {} is {}x{}, expected a square", id, width, height))
+            .on_line(1, header));
+    }
+    if width < 3 {
+        return Err(Error::invalid(header, format!("Tile {} is {}x{}, expected at least 3x3", id, width, height))
+            .on_line(1, header));
+    }
+
+    Ok(Tile { id, image })
+}
+
+/// Parse the tiles from the puzzle input, which are separated by blank lines. All the tiles must
+/// be the same size.
+pub fn parse_input(input: &str) -> Result<Vec<Tile>> {
+    let mut tiles: Vec<Tile> = Vec::new();
+    // the line before the section starts
+    let mut offset = 0;
+
+    for section in input.split("\n\n") {
+        if !section.trim().is_empty() {
+            let tile = parse_tile(section).map_err(|err| err.offset_lines(offset))?;
+            if let Some(first) = tiles.first() {
+                if first.image.size() != tile.image.size() {
+                    let header = section.lines().next().unwrap_or_default();
+                    return Err(Error::invalid(header, "Tile is a different size to the first tile")
+                        .on_line(offset + 1, header));
+                }
+            }
+            tiles.push(tile);
+        }
+
+        // accounting for the blank separator line
+        offset += section.lines().count() + 1;
+    }
+
+    if tiles.is_empty() {
+        return Err(Error::new("Expected at least one tile"));
+    }
+
+    Ok(tiles)
+}
+
+/// Turn a grid a quarter turn clockwise.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_20::*;
+/// assert_eq!(parse_image("##\n.#\n.#").unwrap(), rotate(&parse_image("###\n#..").unwrap()));
+/// ```
+pub fn rotate<T: Clone>(grid: &Grid<T>) -> Grid<T> {
+    let (width, height) = grid.size();
+    Grid::from_fn(height, width, |x, y| grid.get(y, height - 1 - x).unwrap().clone())
+}
+
+/// Mirror a grid left to right.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_20::*;
+/// assert_eq!(parse_image("###\n..#").unwrap(), flip(&parse_image("###\n#..").unwrap()));
+/// ```
+pub fn flip<T: Clone>(grid: &Grid<T>) -> Grid<T> {
+    let (width, height) = grid.size();
+    Grid::from_fn(width, height, |x, y| grid.get(width - 1 - x, y).unwrap().clone())
+}
+
+/// All eight ways a grid can be turned and flipped, starting with the grid as it is. Each quarter
+/// turn is followed by its mirror image.
+pub fn orientations<T: Clone>(grid: &Grid<T>) -> Vec<Grid<T>> {
+    let mut result = Vec::with_capacity(8);
+    let mut current = grid.clone();
+
+    for _ in 0..4 {
+        let next = rotate(&current);
+        let flipped = flip(&current);
+        result.push(current);
+        result.push(flipped);
+        current = next;
+    }
+
+    result
+}
+
+/// The four edges of a grid.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Side {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+/// The cells along one edge of a grid. Top and bottom are read left to right, and left and right
+/// are read top to bottom, so that the edges of neighbouring tiles are equal when they line up.
+pub fn edge<T: Clone>(grid: &Grid<T>, side: Side) -> Vec<T> {
+    let (width, height) = grid.size();
+    let cell = |x: usize, y: usize| grid.get(x, y).unwrap().clone();
+
+    match side {
+        Side::Top => (0..width).map(|x| cell(x, 0)).collect(),
+        Side::Bottom => (0..width).map(|x| cell(x, height - 1)).collect(),
+        Side::Left => (0..height).map(|y| cell(0, y)).collect(),
+        Side::Right => (0..height).map(|y| cell(width - 1, y)).collect(),
+    }
+}
+
+/// An edge is the same whichever way round it is read, so use the smaller of the two readings.
+fn canonical_edge(edge: Vec<bool>) -> Vec<bool> {
+    let reversed: Vec<bool> = edge.iter().rev().copied().collect();
+    edge.min(reversed)
+}
+
+const SIDES: [Side; 4] = [Side::Top, Side::Right, Side::Bottom, Side::Left];
+
+/// Count how many tiles have each edge, in any orientation.
+fn count_edges(tiles: &[Tile]) -> HashMap<Vec<bool>, usize> {
+    let mut counts = HashMap::new();
+    for tile in tiles {
+        for side in SIDES.iter() {
+            *counts.entry(canonical_edge(edge(&tile.image, *side))).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Find the ids of the corner tiles, those with two edges that don't match any other tile.
+pub fn find_corners(tiles: &[Tile]) -> Vec<u64> {
+    let counts = count_edges(tiles);
+
+    tiles.iter()
+        .filter(|tile| {
+            SIDES.iter()
+                .filter(|side| counts[&canonical_edge(edge(&tile.image, **side))] == 1)
+                .count() == 2
+        })
+        .map(|tile| tile.id)
+        .collect()
+}
+
+/// Put the tiles back together, then remove each tile's border to get the full image. The
+/// orientation of the result depends on which corner is picked to start from.
+pub fn assemble(tiles: &[Tile]) -> Result<Grid<bool>> {
+    let size = (1..=tiles.len()).find(|size| size * size >= tiles.len()).unwrap_or(0);
+    if size * size != tiles.len() {
+        return Err(Error::new(format!("Can't make a square from {} tiles", tiles.len())));
+    }
+
+    let counts = count_edges(tiles);
+    let is_outside = |grid: &Grid<bool>, side: Side| counts[&canonical_edge(edge(grid, side))] == 1;
+
+    let first = find_corners(tiles).first().copied()
+        .and_then(|id| tiles.iter().position(|tile| tile.id == id))
+        .ok_or_else(|| Error::new("No corner tiles found"))?;
+    let start = orientations(&tiles[first].image).into_iter()
+        .find(|grid| is_outside(grid, Side::Top) && is_outside(grid, Side::Left))
+        .ok_or_else(|| Error::new(format!("Tile {} can't be placed in the top left corner", tiles[first].id)))?;
+
+    let mut used = vec!(false; tiles.len());
+    used[first] = true;
+    let mut placed = vec!(start);
+
+    for position in 1..tiles.len() {
+        let (x, y) = (position % size, position / size);
+        let fits = |grid: &Grid<bool>| {
+            (x == 0 || edge(grid, Side::Left) == edge(&placed[position - 1], Side::Right)) &&
+                (y == 0 || edge(grid, Side::Top) == edge(&placed[position - size], Side::Bottom))
+        };
+
+        let (index, grid) = tiles.iter()
+            .enumerate()
+            .filter(|(index, _)| !used[*index])
+            .flat_map(|(index, tile)| orientations(&tile.image).into_iter().map(move |grid| (index, grid)))
+            .find(|(_, grid)| fits(grid))
+            .ok_or_else(|| Error::new(format!("No tile fits at column {}, row {}", x + 1, y + 1)))?;
+
+        used[index] = true;
+        placed.push(grid);
+    }
+
+    let inner = placed[0].size().0 - 2;
+    Ok(Grid::from_fn(size * inner, size * inner, |x, y| {
+        *placed[(y / inner) * size + x / inner].get(x % inner + 1, y % inner + 1).unwrap()
+    }))
+}
+
+/// The sea monster to search the assembled image for. Only the `#`s matter, spaces can be
+/// anything.
+pub const SEA_MONSTER: &str = "                  # \n#    ##    ##    ###\n #  #  #  #  #  #   ";
+
+/// The `(x, y)` offsets of the `#`s in a pattern.
+fn pattern_cells(pattern: &str) -> Vec<(usize, usize)> {
+    pattern.lines()
+        .enumerate()
+        .flat_map(|(y, line)| line.char_indices().filter(|(_, c)| *c == '#').map(move |(x, _)| (x, y)))
+        .collect()
+}
+
+/// Find the top left corner of every place in the image that has a `#` under each of the
+/// pattern's `#`s.
+pub fn find_pattern(image: &Grid<bool>, pattern: &str) -> Vec<(usize, usize)> {
+    let cells = pattern_cells(pattern);
+    let pattern_width = cells.iter().map(|(x, _)| x + 1).max().unwrap_or(0);
+    let pattern_height = cells.iter().map(|(_, y)| y + 1).max().unwrap_or(0);
+    let (width, height) = image.size();
+
+    if pattern_width > width || pattern_height > height {
+        return Vec::new();
+    }
+
+    (0..=height - pattern_height)
+        .flat_map(|y| (0..=width - pattern_width).map(move |x| (x, y)))
+        .filter(|(x, y)| cells.iter().all(|(dx, dy)| image.get(x + dx, y + dy) == Some(&true)))
+        .collect()
+}
+
+/// Find the orientation of the image that contains sea monsters, and count the `#`s that aren't
+/// part of any of them.
+pub fn water_roughness(image: &Grid<bool>) -> Result<usize> {
+    let cells = pattern_cells(SEA_MONSTER);
+
+    for oriented in orientations(image) {
+        let monsters = find_pattern(&oriented, SEA_MONSTER);
+        if monsters.is_empty() {
+            continue;
+        }
+
+        let covered: HashSet<(usize, usize)> = monsters.iter()
+            .flat_map(|(x, y)| cells.iter().map(move |(dx, dy)| (x + dx, y + dy)))
+            .collect();
+        let (width, height) = oriented.size();
+        let rough = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|(x, y)| oriented.get(*x, *y) == Some(&true))
+            .count();
+
+        return Ok(rough - covered.len());
+    }
+
+    Err(Error::new("No sea monsters found in any orientation"))
+}
+
+#[cfg(test)]
+mod tests {
+    use day_20::{
+        assemble, edge, find_corners, find_pattern, orientations, parse_image, parse_input, water_roughness,
+        Side, Tile, SEA_MONSTER,
+    };
+
+    fn example() -> &'static str {
+        "Tile 2311:
+..##.#..#.
+##..#.....
+#...##..#.
+####.#...#
+##.##.###.
+##...#.###
+.#.#.#..##
+..#....#..
+###...#.#.
+..###..###
+
+Tile 1951:
+#.##...##.
+#.####...#
+.....#..##
+#...######
+.##.#....#
+.###.#####
+###.##.##.
+.###....#.
+..#.#..#.#
+#...##.#..
+
+Tile 1171:
+####...##.
+#..##.#..#
+##.#..#.#.
+.###.####.
+..###.####
+.##....##.
+.#...####.
+#.##.####.
+####..#...
+.....##...
+
+Tile 1427:
+###.##.#..
+.#..#.##..
+.#.##.#..#
+#.#.#.##.#
+....#...##
+...##..##.
+...#.#####
+.#.####.#.
+..#..###.#
+..##.#..#.
+
+Tile 1489:
+##.#.#....
+..##...#..
+.##..##...
+..#...#...
+#####...#.
+#..#.#.#.#
+...#.#.#..
+##.#...##.
+..##.##.##
+###.##.#..
+
+Tile 2473:
+#....####.
+#..#.##...
+#.##..#...
+######.#.#
+.#...#.#.#
+.#########
+.###.#..#.
+########.#
+##...##.#.
+..###.#.#.
+
+Tile 2971:
+..#.#....#
+#...###...
+#.#.###...
+##.##..#..
+.#####..##
+.#..####.#
+#..#.#..#.
+..####.###
+..#.#.###.
+...#.#.#.#
+
+Tile 2729:
+...#.#.#.#
+####.#....
+..#.#.....
+....#..#.#
+.##..##.#.
+.#.####...
+####.#.#..
+##.####...
+##..#.##..
+#.##...##.
+
+Tile 3079:
+#.#.#####.
+.#..######
+..#.......
+######....
+####.#..#.
+.#...#.##.
+#.#####.##
+..#.###...
+..#.......
+..#.###...
+"
+    }
+
+    #[test]
+    fn can_parse_input() {
+        let tiles = parse_input("Tile 2311:\n#.#\n...\n.##\n\nTile 1951:\n###\n#..\n.#.\n").unwrap();
+
+        assert_eq!(
+            vec!(
+                Tile { id: 2311, image: parse_image("#.#\n...\n.##").unwrap() },
+                Tile { id: 1951, image: parse_image("###\n#..\n.#.").unwrap() },
+            ),
+            tiles
+        );
+
+        let err = parse_input("Tile 2311:\n#.#\n...\n.##\n\nTile 1951:\n###\n#x.\n.#.").unwrap_err();
+        assert_eq!((Some(8), Some(2)), (err.line, err.column));
+        assert_eq!(Some(6), parse_input("Tile 1:\n#..\n.#.\n...\n\nTile 2:\n#...\n.#..\n....\n...#").unwrap_err().line);
+        assert_eq!(Some(6), parse_input("Tile 1:\n#..\n.#.\n...\n\nTile two:\n#..\n.#.\n...").unwrap_err().line);
+        assert_eq!(Some(1), parse_input("Tile 1:\n#..\n.#.").unwrap_err().line);
+        assert_eq!(Some(6), parse_input("Tile 1:\n#..\n.#.\n...\n\nTile 2:\n#.\n.#").unwrap_err().line);
+
+        let err = parse_input("Tile 1:\n#.\n.#").unwrap_err();
+        assert_eq!(Some(1), err.line);
+        assert_eq!("Tile 1 is 2x2, expected at least 3x3", err.message);
+    }
+
+    #[test]
+    fn can_orient() {
+        let grid = parse_image("#..\n##.").unwrap();
+        let all = orientations(&grid);
+
+        assert_eq!(8, all.len());
+        assert_eq!(grid, all[0]);
+        assert_eq!(
+            vec!(
+                "#..\n##.", "..#\n.##", "##\n#.\n..", "##\n.#\n..",
+                ".##\n..#", "##.\n#..", "..\n.#\n##", "..\n#.\n##",
+            ).into_iter().map(|image| parse_image(image).unwrap()).collect::<Vec<_>>(),
+            all
+        );
+
+        assert_eq!(vec!(true, false, false), edge(&grid, Side::Top));
+        assert_eq!(vec!(false, false), edge(&grid, Side::Right));
+        assert_eq!(vec!(true, true, false), edge(&grid, Side::Bottom));
+        assert_eq!(vec!(true, true), edge(&grid, Side::Left));
+    }
+
+    #[test]
+    fn can_find_corners() {
+        let tiles = parse_input(example()).unwrap();
+        let mut corners = find_corners(&tiles);
+        corners.sort();
+
+        assert_eq!(vec!(1171, 1951, 2971, 3079), corners);
+        assert_eq!(20899048083289u64, corners.iter().product());
+    }
+
+    #[test]
+    fn can_assemble() {
+        let tiles = parse_input(example()).unwrap();
+        let image = assemble(&tiles).unwrap();
+
+        assert_eq!((24, 24), image.size());
+        assert!(assemble(&tiles[1..]).is_err());
+    }
+
+    #[test]
+    fn can_find_sea_monsters() {
+        let image = assemble(&parse_input(example()).unwrap()).unwrap();
+
+        let monsters: Vec<usize> = orientations(&image).iter()
+            .map(|oriented| find_pattern(oriented, SEA_MONSTER).len())
+            .collect();
+        assert_eq!(2, monsters.iter().copied().max().unwrap());
+        assert_eq!(1, monsters.iter().filter(|&&count| count > 0).count());
+
+        assert_eq!(Ok(273), water_roughness(&image));
+        assert!(water_roughness(&parse_image("#.\n.#").unwrap()).is_err());
+    }
+}
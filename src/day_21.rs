@@ -0,0 +1,208 @@
+//! This is my solution for [Advent of Code - Day 21](https://adventofcode.com/2020/day/21) -
+//! _Allergen Assessment_
+//!
+//! Work out which ingredient contains each allergen. An allergen's ingredient must appear in every
+//! food that lists that allergen, so intersecting those foods' ingredients gives the candidates
+//! for each allergen ([`find_candidates`]). From there it is the same kind of problem as assigning
+//! ticket fields on day 16, so the candidates are narrowed down to one each by
+//! [`eliminate`](crate::elimination::eliminate).
+//!
+//! __Part 1__ - [`parse_input`], [`find_candidates`], [`count_safe_ingredients`].
+//!
+//! __Part 2__ - [`identify_allergens`], [`canonical_dangerous_list`].
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use regex::Regex;
+use elimination::eliminate;
+use error::{parse_lines, Error, Result};
+use solution::Solution;
+
+/// The [`Solution`] for day 21.
+///
+/// - Part one counts the appearances of ingredients that can't contain any allergen.
+/// - Part two lists the ingredient containing each allergen, sorted by allergen.
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input<'a> = Vec<Food<'a>>;
+    type PartOne = usize;
+    type PartTwo = String;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Vec<Food<'a>>> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, foods: &Vec<Food>) -> Result<usize> {
+        Ok(count_safe_ingredients(foods, &find_candidates(foods)))
+    }
+
+    fn part_two(&self, foods: &Vec<Food>) -> Result<String> {
+        Ok(canonical_dangerous_list(&identify_allergens(foods)?))
+    }
+}
+
+/// A line of the puzzle input, the food's ingredients and the allergens it is known to contain.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Food<'a> {
+    pub ingredients: Vec<&'a str>,
+    pub allergens: Vec<&'a str>,
+}
+
+impl<'a> Food<'a> {
+    /// Parse a line of the puzzle input, e.g. `mxmxvkd kfcds sqjhc nhms (contains dairy, fish)`.
+    /// Not all foods list their allergens.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_21::*;
+    /// assert_eq!(
+    ///     Ok(Food { ingredients: vec!("trh", "fvjkl", "sbzzf", "mxmxvkd"), allergens: vec!("dairy") }),
+    ///     Food::from_line("trh fvjkl sbzzf mxmxvkd (contains dairy)")
+    /// );
+    /// assert_eq!(
+    ///     Ok(Food { ingredients: vec!("sqjhc", "fvjkl"), allergens: vec!() }),
+    ///     Food::from_line("sqjhc fvjkl")
+    /// );
+    ///
+    /// assert!(Food::from_line("sqjhc fvjkl (contains)").is_err());
+    /// ```
+    pub fn from_line(line: &'a str) -> Result<Food<'a>> {
+        let re = Regex::new(r"^([a-z]+(?: [a-z]+)*)(?: \(contains ([a-z]+(?:, [a-z]+)*)\))?$")
+            .expect("Invalid Regex");
+        let cap = re.captures(line)
+            .ok_or_else(|| Error::invalid(line, "Expected ingredients, e.g. 'abc def (contains dairy, fish)'"))?;
+
+        Ok(Food {
+            ingredients: cap.get(1).unwrap().as_str().split(' ').collect(),
+            allergens: cap.get(2).map(|list| list.as_str().split(", ").collect()).unwrap_or_default(),
+        })
+    }
+}
+
+/// Parse each line of the puzzle input as a [`Food`].
+pub fn parse_input(input: &str) -> Result<Vec<Food<'_>>> {
+    parse_lines(input, Food::from_line)
+}
+
+/// For each allergen, the ingredients that appear in every food listing that allergen.
+pub fn find_candidates<'a>(foods: &[Food<'a>]) -> HashMap<&'a str, HashSet<&'a str>> {
+    let mut candidates: HashMap<&str, HashSet<&str>> = HashMap::new();
+
+    for food in foods {
+        let ingredients: HashSet<&str> = food.ingredients.iter().copied().collect();
+        for &allergen in &food.allergens {
+            candidates.entry(allergen)
+                .and_modify(|set| set.retain(|ingredient| ingredients.contains(ingredient)))
+                .or_insert_with(|| ingredients.clone());
+        }
+    }
+
+    candidates
+}
+
+/// Count how many times ingredients that aren't a candidate for any allergen appear in the foods.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_21::*;
+/// let foods = parse_input(
+///     "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+/// trh fvjkl sbzzf mxmxvkd (contains dairy)
+/// sqjhc fvjkl (contains soy)
+/// sqjhc mxmxvkd sbzzf (contains fish)"
+/// ).unwrap();
+///
+/// assert_eq!(5, count_safe_ingredients(&foods, &find_candidates(&foods)));
+/// ```
+pub fn count_safe_ingredients(foods: &[Food], candidates: &HashMap<&str, HashSet<&str>>) -> usize {
+    let unsafe_ingredients: HashSet<&str> = candidates.values().flatten().copied().collect();
+
+    foods.iter()
+        .flat_map(|food| food.ingredients.iter())
+        .filter(|ingredient| !unsafe_ingredients.contains(*ingredient))
+        .count()
+}
+
+/// Narrow down the candidates to find the ingredient containing each allergen.
+pub fn identify_allergens<'a>(foods: &[Food<'a>]) -> Result<HashMap<&'a str, &'a str>> {
+    eliminate(find_candidates(foods))
+        .ok_or_else(|| Error::new("Failed to find an allergen with only one candidate ingredient"))
+}
+
+/// The dangerous ingredients, sorted by the allergen they contain and joined with commas.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_21::*;
+/// let foods = parse_input(
+///     "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+/// trh fvjkl sbzzf mxmxvkd (contains dairy)
+/// sqjhc fvjkl (contains soy)
+/// sqjhc mxmxvkd sbzzf (contains fish)"
+/// ).unwrap();
+///
+/// assert_eq!("mxmxvkd,sqjhc,fvjkl", canonical_dangerous_list(&identify_allergens(&foods).unwrap()));
+/// ```
+pub fn canonical_dangerous_list(allergens: &HashMap<&str, &str>) -> String {
+    let sorted: BTreeMap<&str, &str> = allergens.iter().map(|(allergen, ingredient)| (*allergen, *ingredient)).collect();
+    sorted.values().copied().collect::<Vec<&str>>().join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use day_21::{
+        canonical_dangerous_list, count_safe_ingredients, find_candidates, identify_allergens, parse_input, Food,
+    };
+
+    fn example() -> &'static str {
+        "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)"
+    }
+
+    #[test]
+    fn can_parse() {
+        let foods = parse_input(example()).unwrap();
+
+        assert_eq!(4, foods.len());
+        assert_eq!(
+            Food { ingredients: vec!("mxmxvkd", "kfcds", "sqjhc", "nhms"), allergens: vec!("dairy", "fish") },
+            foods[0]
+        );
+        assert_eq!(Some(2), parse_input("abc (contains soy)\nabc (contains Soy)").unwrap_err().line);
+        assert!(parse_input("abc (contains soy, )").is_err());
+    }
+
+    #[test]
+    fn can_find_candidates() {
+        let foods = parse_input(example()).unwrap();
+        let set = |ingredients: &[&'static str]| ingredients.iter().copied().collect::<HashSet<&str>>();
+
+        let mut expected = HashMap::new();
+        expected.insert("dairy", set(&["mxmxvkd"]));
+        expected.insert("fish", set(&["mxmxvkd", "sqjhc"]));
+        expected.insert("soy", set(&["sqjhc", "fvjkl"]));
+
+        let candidates = find_candidates(&foods);
+        assert_eq!(expected, candidates);
+        assert_eq!(5, count_safe_ingredients(&foods, &candidates));
+    }
+
+    #[test]
+    fn can_identify_allergens() {
+        let foods = parse_input(example()).unwrap();
+
+        let mut expected = HashMap::new();
+        expected.insert("dairy", "mxmxvkd");
+        expected.insert("fish", "sqjhc");
+        expected.insert("soy", "fvjkl");
+
+        let allergens = identify_allergens(&foods).unwrap();
+        assert_eq!(expected, allergens);
+        assert_eq!("mxmxvkd,sqjhc,fvjkl", canonical_dangerous_list(&allergens));
+
+        assert!(identify_allergens(&parse_input("abc def (contains soy, fish)").unwrap()).is_err());
+    }
+}
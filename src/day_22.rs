@@ -0,0 +1,341 @@
+//! This is my solution for [Advent of Code - Day 22](https://adventofcode.com/2020/day/22) -
+//! _Crab Combat_
+//!
+//! Simulate a card game against a crab. Part two makes the game recursive, with sub-games played
+//! on copies of the top of each deck, so the decks are persistent [`Vector`]s that can be copied
+//! cheaply. Recursive games also need the rule that a repeated deck state ends the game, otherwise
+//! some games would loop forever. Plain games don't have that rule, so if one of those repeats it is
+//! reported as an error instead.
+//!
+//! [`Combat`] can optionally record every round it plays, which is useful for comparing against the
+//! worked example in the puzzle description.
+//!
+//! __Part 1__ - [`parse_input`], [`Combat::play`], [`score`].
+//!
+//! __Part 2__ - as part 1, with [`Combat::recursive`].
+
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+use im::Vector;
+use error::{parse_number, Error, Result};
+use solution::Solution;
+
+/// The [`Solution`] for day 22.
+///
+/// - Both parts score the winning deck, from a plain and a recursive game respectively.
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input<'a> = (Deck, Deck);
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<(Deck, Deck)> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, (one, two): &(Deck, Deck)) -> Result<usize> {
+        let (_, deck) = Combat::new().play(one, two)?;
+        Ok(score(&deck))
+    }
+
+    fn part_two(&self, (one, two): &(Deck, Deck)) -> Result<usize> {
+        let (_, deck) = Combat::recursive().play(one, two)?;
+        Ok(score(&deck))
+    }
+}
+
+/// A deck of cards, the front is the top of the deck.
+pub type Deck = Vector<usize>;
+
+/// The two players, you are player 1 and the crab is player 2.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Player {
+    One,
+    Two,
+}
+
+impl Display for Player {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Player::One => write!(f, "Player 1"),
+            Player::Two => write!(f, "Player 2"),
+        }
+    }
+}
+
+/// Parse the two decks, each a `Player N:` header followed by a card per line. Each card can only
+/// appear once across both decks, otherwise rounds could be drawn.
+///
+/// # Examples from Tests
+/// ```
+/// # extern crate im;
+/// # use im::vector;
+/// # use advent_of_code_2020::day_22::*;
+/// assert_eq!(
+///     Ok((vector!(9, 2, 6), vector!(5, 8, 4))),
+///     parse_input("Player 1:\n9\n2\n6\n\nPlayer 2:\n5\n8\n4")
+/// );
+///
+/// assert_eq!(Some(8), parse_input("Player 1:\n9\n2\n6\n\nPlayer 2:\n5\nJ\n4").unwrap_err().line);
+/// assert_eq!(Some(9), parse_input("Player 1:\n9\n2\n6\n\nPlayer 2:\n5\n8\n2").unwrap_err().line);
+/// ```
+pub fn parse_input(input: &str) -> Result<(Deck, Deck)> {
+    let mut decks = Vec::new();
+    let mut cards = HashSet::new();
+    // the line before the section starts
+    let mut offset = 0;
+
+    for (index, section) in input.split("\n\n").filter(|section| !section.trim().is_empty()).enumerate() {
+        let mut lines = section.lines();
+        let header = lines.next().unwrap_or_default();
+        let expected = format!("Player {}:", index + 1);
+        if header != expected {
+            return Err(Error::invalid(header, format!("Expected '{}'", expected)).on_line(offset + 1, header));
+        }
+
+        let mut deck = Deck::new();
+        for (line, text) in lines.enumerate() {
+            let card = parse_number(text, text, 1).map_err(|err| err.on_line(offset + line + 2, text))?;
+            if !cards.insert(card) {
+                return Err(
+                    Error::invalid(text, format!("Card {} is already in a deck", card)).on_line(offset + line + 2, text)
+                );
+            }
+            deck.push_back(card);
+        }
+        decks.push(deck);
+
+        // accounting for the blank separator line
+        offset += section.lines().count() + 1;
+    }
+
+    match decks.len() {
+        2 => Ok((decks[0].clone(), decks[1].clone())),
+        count => Err(Error::new(format!("Expected decks for 2 players, found {}", count))),
+    }
+}
+
+/// A round that has been played, see [`Combat::log`].
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Round {
+    /// Games are numbered in the order they start, the main game is game 1.
+    pub game: usize,
+    /// Rounds are numbered from 1 within each game.
+    pub round: usize,
+    /// The decks at the start of the round.
+    pub decks: (Deck, Deck),
+    /// The cards each player drew.
+    pub cards: (usize, usize),
+    pub winner: Player,
+}
+
+/// Formats the round in the same way as the example in the puzzle description, without the
+/// details of any sub-game.
+impl Display for Round {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let list = |deck: &Deck| deck.iter().map(|card| card.to_string()).collect::<Vec<String>>().join(", ");
+
+        writeln!(f, "-- Round {} (Game {}) --", self.round, self.game)?;
+        writeln!(f, "Player 1's deck: {}", list(&self.decks.0))?;
+        writeln!(f, "Player 2's deck: {}", list(&self.decks.1))?;
+        writeln!(f, "Player 1 plays: {}", self.cards.0)?;
+        writeln!(f, "Player 2 plays: {}", self.cards.1)?;
+        write!(f, "{} wins round {} of game {}!", self.winner, self.round, self.game)
+    }
+}
+
+/// Plays games of Combat, or Recursive Combat.
+#[derive(Debug, Default)]
+pub struct Combat {
+    recursive: bool,
+    recording: bool,
+    games: usize,
+    log: Vec<Round>,
+}
+
+impl Combat {
+    /// A plain game, the player with the highest card wins each round.
+    pub fn new() -> Combat {
+        Combat::default()
+    }
+
+    /// A game of Recursive Combat, where if both players have enough cards left the round is
+    /// decided by a sub-game.
+    pub fn recursive() -> Combat {
+        Combat { recursive: true, ..Combat::default() }
+    }
+
+    /// Record each round played in the [`Combat::log`].
+    pub fn recording(self) -> Combat {
+        Combat { recording: true, ..self }
+    }
+
+    /// The rounds played so far, if recording. Rounds are logged when they finish, so a round
+    /// decided by a sub-game comes after all of the sub-game's rounds.
+    pub fn log(&self) -> &[Round] {
+        &self.log
+    }
+
+    /// Play a game with the starting decks, returning the winner and their final deck. A plain game
+    /// that gets back to a state it has already been in would go on forever, so is an error.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # extern crate im;
+    /// # use im::vector;
+    /// # use advent_of_code_2020::day_22::*;
+    /// let (one, two) = (vector!(9, 2, 6, 3, 1), vector!(5, 8, 4, 7, 10));
+    ///
+    /// assert_eq!(Ok((Player::Two, vector!(3, 2, 10, 6, 8, 5, 9, 4, 7, 1))), Combat::new().play(&one, &two));
+    /// assert_eq!(Ok((Player::Two, vector!(7, 5, 6, 2, 4, 1, 10, 8, 9, 3))), Combat::recursive().play(&one, &two));
+    ///
+    /// assert!(Combat::new().play(&vector!(1, 2, 4), &vector!(3, 5)).is_err());
+    /// ```
+    pub fn play(&mut self, one: &Deck, two: &Deck) -> Result<(Player, Deck)> {
+        self.play_game(one.clone(), two.clone())
+    }
+
+    fn play_game(&mut self, mut one: Deck, mut two: Deck) -> Result<(Player, Deck)> {
+        self.games += 1;
+        let game = self.games;
+        let mut seen: HashSet<(Deck, Deck)> = HashSet::new();
+        let mut round = 0;
+
+        loop {
+            if one.is_empty() {
+                return Ok((Player::Two, two));
+            }
+            if two.is_empty() {
+                return Ok((Player::One, one));
+            }
+            // prevent infinite games, if this state has been seen before player 1 wins a recursive
+            // game, but a plain game has no winner
+            if !seen.insert((one.clone(), two.clone())) {
+                return if self.recursive {
+                    Ok((Player::One, one))
+                } else {
+                    Err(Error::new(format!("Game {} repeats after round {} so never ends", game, round)))
+                };
+            }
+
+            round += 1;
+            let decks = (one.clone(), two.clone());
+            let (card_one, card_two) = (one.pop_front().unwrap(), two.pop_front().unwrap());
+
+            let winner = if self.recursive && one.len() >= card_one && two.len() >= card_two {
+                self.play_game(one.take(card_one), two.take(card_two))?.0
+            } else if card_one > card_two {
+                Player::One
+            } else {
+                Player::Two
+            };
+
+            match winner {
+                Player::One => {
+                    one.push_back(card_one);
+                    one.push_back(card_two);
+                }
+                Player::Two => {
+                    two.push_back(card_two);
+                    two.push_back(card_one);
+                }
+            }
+
+            if self.recording {
+                self.log.push(Round { game, round, decks, cards: (card_one, card_two), winner });
+            }
+        }
+    }
+}
+
+/// Score a deck, each card is multiplied by its position counting up from the bottom of the deck.
+pub fn score(deck: &Deck) -> usize {
+    deck.iter().rev().enumerate().map(|(index, card)| (index + 1) * card).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use im::vector;
+    use day_22::{parse_input, score, Combat, Player, Round};
+
+    #[test]
+    fn can_parse() {
+        let input = "Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10
+";
+        assert_eq!(Ok((vector!(9, 2, 6, 3, 1), vector!(5, 8, 4, 7, 10))), parse_input(input));
+
+        assert_eq!(Some(4), parse_input("Player 1:\n9\n\nPlayer 1:\n5").unwrap_err().line);
+        assert_eq!(Some(6), parse_input("Player 1:\n9\n\nPlayer 2:\n5\nJ").unwrap_err().line);
+        assert!(parse_input("Player 1:\n9").is_err());
+
+        let error = parse_input("Player 1:\n9\n2\n\nPlayer 2:\n5\n9").unwrap_err();
+        assert_eq!(Some(7), error.line);
+        assert_eq!(Some("9".to_string()), error.text);
+        assert!(parse_input("Player 1:\n9\n9\n\nPlayer 2:\n5").is_err());
+    }
+
+    #[test]
+    fn can_play_combat() {
+        let mut combat = Combat::new().recording();
+        let (winner, deck) = combat.play(&vector!(9, 2, 6, 3, 1), &vector!(5, 8, 4, 7, 10)).unwrap();
+
+        assert_eq!(Player::Two, winner);
+        assert_eq!(306, score(&deck));
+        assert_eq!(29, combat.log().len());
+        assert_eq!(
+            Round {
+                game: 1,
+                round: 1,
+                decks: (vector!(9, 2, 6, 3, 1), vector!(5, 8, 4, 7, 10)),
+                cards: (9, 5),
+                winner: Player::One,
+            },
+            combat.log()[0]
+        );
+        assert_eq!(
+            "-- Round 1 (Game 1) --
+Player 1's deck: 9, 2, 6, 3, 1
+Player 2's deck: 5, 8, 4, 7, 10
+Player 1 plays: 9
+Player 2 plays: 5
+Player 1 wins round 1 of game 1!",
+            combat.log()[0].to_string()
+        );
+    }
+
+    #[test]
+    fn can_play_recursive_combat() {
+        let mut combat = Combat::recursive().recording();
+        let (winner, deck) = combat.play(&vector!(9, 2, 6, 3, 1), &vector!(5, 8, 4, 7, 10)).unwrap();
+
+        assert_eq!(Player::Two, winner);
+        assert_eq!(291, score(&deck));
+        assert_eq!(17, combat.log().iter().filter(|round| round.game == 1).count());
+
+        let sub_game = combat.log().iter().find(|round| round.game == 2).unwrap();
+        assert_eq!((vector!(9, 8, 5, 2), vector!(10, 1, 7)), sub_game.decks);
+    }
+
+    #[test]
+    fn prevents_infinite_games() {
+        let (winner, _) = Combat::recursive().play(&vector!(43, 19), &vector!(2, 29, 14)).unwrap();
+
+        assert_eq!(Player::One, winner);
+
+        let error = Combat::new().play(&vector!(1, 2, 4), &vector!(3, 5)).unwrap_err();
+        assert_eq!(None, error.line);
+    }
+}
@@ -0,0 +1,248 @@
+//! This is my solution for [Advent of Code - Day 23](https://adventofcode.com/2020/day/23) -
+//! _Crab Cups_
+//!
+//! Another puzzle where part two is the same game scaled up until the data structure matters, a
+//! million cups and ten million moves. Each move takes three cups out of a circle and puts them
+//! back in somewhere else, which is what a linked list is good at, but finding where to put them
+//! back needs a lookup by label. As with [`play_memory_game`](crate::day_15::play_memory_game) on
+//! day 15 the trick is a dense `Vec` indexed by the cup's label, in this case storing the label of
+//! the next cup clockwise. That makes both moving the cups and finding the destination cup
+//! constant time, without any allocations once the circle is set up.
+//!
+//! The runner's timings for each part show the difference the scale makes, part two is still
+//! comfortably under a second with a release build.
+//!
+//! __Part 1__ - [`parse_input`], [`Cups::play`], [`Cups::labels_after_one`].
+//!
+//! __Part 2__ - [`Cups::new`] with extra cups, [`Cups::play`], [`Cups::after_one`].
+
+use error::{parse_number, Error, Result};
+use solution::Solution;
+
+/// The [`Solution`] for day 23.
+///
+/// - Part one plays 100 moves with the cups as given, and lists the labels after cup 1.
+/// - Part two plays 10,000,000 moves with the cups padded out to 1,000,000, and multiplies the two
+///   labels after cup 1.
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input<'a> = Vec<u32>;
+    type PartOne = String;
+    type PartTwo = u64;
+
+    fn parse(&self, input: &str) -> Result<Vec<u32>> {
+        parse_input(input.trim())
+    }
+
+    fn part_one(&self, labels: &Vec<u32>) -> Result<String> {
+        let mut cups = Cups::new(labels, labels.len());
+        cups.play(100);
+        Ok(cups.labels_after_one())
+    }
+
+    fn part_two(&self, labels: &Vec<u32>) -> Result<u64> {
+        let mut cups = Cups::new(labels, 1_000_000);
+        cups.play(10_000_000);
+        let (first, second) = cups.after_one();
+        Ok(first as u64 * second as u64)
+    }
+}
+
+/// Parse the cup labels, a single digit for each cup, e.g. `389125467`. The labels must be the
+/// numbers from 1 up to the number of cups, each used once, and there must be at least one cup.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_23::*;
+/// assert_eq!(Ok(vec!(3, 8, 9, 1, 2, 5, 4, 6, 7)), parse_input("389125467"));
+///
+/// assert_eq!(Some(4), parse_input("312x").unwrap_err().column);
+/// assert_eq!(Some(3), parse_input("121").unwrap_err().column);
+/// assert!(parse_input("").is_err());
+/// ```
+pub fn parse_input(input: &str) -> Result<Vec<u32>> {
+    if input.is_empty() {
+        return Err(Error::new("Expected at least one cup"));
+    }
+
+    let mut seen = vec!(false; input.len() + 1);
+
+    input.char_indices()
+        .map(|(index, char)| {
+            let label: u32 = parse_number(&char.to_string(), input, index + 1)
+                .map_err(|err| err.on_line(1, input))?;
+
+            match seen.get_mut(label as usize) {
+                Some(seen) if label > 0 && !*seen => {
+                    *seen = true;
+                    Ok(label)
+                }
+                _ => Err(Error::at_column(
+                    input,
+                    index + 1,
+                    format!("Expected each of the labels 1 to {} once", input.len())
+                ).on_line(1, input))
+            }
+        })
+        .collect()
+}
+
+/// The circle of cups, as a linked list stored in a `Vec` indexed by label.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Cups {
+    /// The label of the cup clockwise of each cup. Labels start at 1, so index 0 is unused.
+    next: Vec<u32>,
+    /// The label of the current cup.
+    current: u32,
+}
+
+impl Cups {
+    /// Arrange the cups in a circle, in the order given, followed by cups labelled from one more
+    /// than the highest given label, until there are `total` cups. The first cup is the current
+    /// cup.
+    pub fn new(labels: &[u32], total: usize) -> Cups {
+        let extra = (labels.len() as u32 + 1)..=(total as u32);
+        let order: Vec<u32> = labels.iter().copied().chain(extra).collect();
+
+        let mut next = vec!(0; order.len() + 1);
+        for (index, &label) in order.iter().enumerate() {
+            next[label as usize] = order[(index + 1) % order.len()];
+        }
+
+        Cups { next, current: order.first().copied().unwrap_or(0) }
+    }
+
+    /// The number of cups in the circle.
+    pub fn len(&self) -> usize {
+        self.next.len() - 1
+    }
+
+    /// Are there no cups?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Make one move:
+    ///
+    /// - Pick up the three cups clockwise of the current cup.
+    /// - The destination is the cup labelled one less than the current cup, skipping any labels
+    ///   picked up, and wrapping round to the highest label.
+    /// - Put the picked up cups back, in order, clockwise of the destination cup.
+    /// - The next current cup is the one clockwise of the current cup.
+    pub fn make_move(&mut self) {
+        let max = self.len() as u32;
+        // with fewer than 5 cups there's nowhere else to put the picked up cups back
+        if max < 5 {
+            return;
+        }
+
+        let first = self.next[self.current as usize];
+        let second = self.next[first as usize];
+        let third = self.next[second as usize];
+
+        let mut destination = self.current;
+        loop {
+            destination = if destination == 1 { max } else { destination - 1 };
+            if destination != first && destination != second && destination != third {
+                break;
+            }
+        }
+
+        // unlink the picked up cups, then relink them after the destination
+        self.next[self.current as usize] = self.next[third as usize];
+        self.next[third as usize] = self.next[destination as usize];
+        self.next[destination as usize] = first;
+
+        self.current = self.next[self.current as usize];
+    }
+
+    /// Make a number of moves.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_23::*;
+    /// let mut cups = Cups::new(&[3, 8, 9, 1, 2, 5, 4, 6, 7], 9);
+    /// cups.play(10);
+    /// assert_eq!("92658374", cups.labels_after_one());
+    ///
+    /// cups.play(90);
+    /// assert_eq!("67384529", cups.labels_after_one());
+    /// ```
+    pub fn play(&mut self, moves: usize) {
+        for _ in 0..moves {
+            self.make_move()
+        }
+    }
+
+    /// The labels going clockwise, starting from `label`.
+    pub fn clockwise_from(&self, label: u32) -> impl Iterator<Item=u32> + '_ {
+        let mut cup = label;
+        (0..self.len()).map(move |_| {
+            let current = cup;
+            cup = self.next[cup as usize];
+            current
+        })
+    }
+
+    /// The labels of the other cups, clockwise from cup 1, as a string.
+    pub fn labels_after_one(&self) -> String {
+        self.clockwise_from(1).skip(1).map(|label| label.to_string()).collect()
+    }
+
+    /// The two cups clockwise of cup 1.
+    pub fn after_one(&self) -> (u32, u32) {
+        let first = self.next[1];
+        (first, self.next[first as usize])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use day_23::{parse_input, Cups};
+
+    #[test]
+    fn can_parse() {
+        assert_eq!(Ok(vec!(3, 8, 9, 1, 2, 5, 4, 6, 7)), parse_input("389125467"));
+
+        assert_eq!(Some(1), parse_input("30").unwrap_err().column);
+        assert_eq!(Some(3), parse_input("120").unwrap_err().column);
+        assert_eq!(Some(1), parse_input("4").unwrap_err().column);
+        assert!(parse_input("").is_err());
+    }
+
+    #[test]
+    fn can_set_up_cups() {
+        let cups = Cups::new(&[3, 8, 9, 1, 2, 5, 4, 6, 7], 12);
+
+        assert_eq!(12, cups.len());
+        assert_eq!(
+            vec!(3, 8, 9, 1, 2, 5, 4, 6, 7, 10, 11, 12),
+            cups.clockwise_from(3).collect::<Vec<u32>>()
+        );
+    }
+
+    #[test]
+    fn can_play_cups() {
+        let mut cups = Cups::new(&[3, 8, 9, 1, 2, 5, 4, 6, 7], 9);
+
+        cups.make_move();
+        assert_eq!(vec!(2, 8, 9, 1, 5, 4, 6, 7, 3), cups.clockwise_from(2).collect::<Vec<u32>>());
+        cups.make_move();
+        assert_eq!(vec!(5, 4, 6, 7, 8, 9, 1, 3, 2), cups.clockwise_from(5).collect::<Vec<u32>>());
+
+        cups.play(8);
+        assert_eq!("92658374", cups.labels_after_one());
+        cups.play(90);
+        assert_eq!("67384529", cups.labels_after_one());
+    }
+
+    #[test]
+    #[ignore = "slow without optimisations, the answer is checked by the runner's answers"]
+    fn can_play_a_million_cups() {
+        let mut cups = Cups::new(&[3, 8, 9, 1, 2, 5, 4, 6, 7], 1_000_000);
+        cups.play(10_000_000);
+
+        assert_eq!((934001, 159792), cups.after_one());
+    }
+}
@@ -0,0 +1,335 @@
+//! This is my solution for [Advent of Code - Day 24](https://adventofcode.com/2020/day/24) -
+//! _Lobby Layout_
+//!
+//! Flip tiles on a hexagonal grid, then run another Game of Life on it. Hex grids are easiest to
+//! work with using [axial coordinates](https://www.redblobgames.com/grids/hexagons/#coordinates-axial),
+//! where each tile has a `q` and `r` coordinate like a square grid that has been skewed, so that
+//! the six neighbours are each a step of one or both coordinates.
+//!
+//! The grid itself follows the sparse style of [`ThreeDGrid`](crate::day_17::ThreeDGrid) from
+//! day 17, only storing the black tiles and tracking the bounds they have reached, so that each day
+//! only needs to check the tiles within one step of the bounds.
+//!
+//! __Part 1__ - [`parse_input`], [`Direction::follow`], [`flip_tiles`].
+//!
+//! __Part 2__ - [`iterate_grid`].
+
+use std::collections::HashSet;
+use error::{parse_lines, Error, Result};
+use solution::Solution;
+
+/// The [`Solution`] for day 24.
+///
+/// - Part one counts the black tiles after following the list of tiles to flip.
+/// - Part two counts the black tiles after 100 days of flipping them with the Game of Life rules.
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input<'a> = HexGrid;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<HexGrid> {
+        Ok(flip_tiles(&parse_input(input)?))
+    }
+
+    fn part_one(&self, grid: &HexGrid) -> Result<usize> {
+        Ok(grid.count_black())
+    }
+
+    fn part_two(&self, grid: &HexGrid) -> Result<usize> {
+        let mut grid = grid.clone();
+        for _ in 0..100 {
+            grid = iterate_grid(&grid)
+        }
+        Ok(grid.count_black())
+    }
+}
+
+/// The six directions to a neighbouring tile.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Direction {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl Direction {
+    /// All of the directions, i.e. the offsets to each neighbour.
+    pub const ALL: [Direction; 6] = [
+        Direction::East,
+        Direction::SouthEast,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+        Direction::NorthEast,
+    ];
+
+    /// The `(q, r)` change when stepping in this direction.
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::East => (1, 0),
+            Direction::SouthEast => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (0, -1),
+            Direction::NorthEast => (1, -1),
+        }
+    }
+
+    /// The tile reached by following a list of directions from the reference tile at `(0, 0)`.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_24::*;
+    /// assert_eq!((0, 1), Direction::follow(&parse_line("esew").unwrap()));
+    /// assert_eq!((0, 0), Direction::follow(&parse_line("nwwswee").unwrap()));
+    /// ```
+    pub fn follow(path: &[Direction]) -> (isize, isize) {
+        path.iter()
+            .map(|direction| direction.offset())
+            .fold((0, 0), |(q, r), (dq, dr)| (q + dq, r + dr))
+    }
+}
+
+/// Parse a line of directions, e.g. `esenee`. There is no separator between directions, but `e`
+/// and `w` are always a single letter, and `n` and `s` are always followed by an `e` or a `w`.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_24::*;
+/// # use advent_of_code_2020::day_24::Direction::*;
+/// assert_eq!(Ok(vec!(East, SouthEast, NorthEast, East)), parse_line("esenee"));
+///
+/// assert_eq!(Some(2), parse_line("esnee").unwrap_err().column);
+/// ```
+pub fn parse_line(line: &str) -> Result<Vec<Direction>> {
+    let mut directions = Vec::new();
+    let mut chars = line.char_indices();
+
+    while let Some((index, char)) = chars.next() {
+        let direction = match char {
+            'e' => Direction::East,
+            'w' => Direction::West,
+            'n' | 's' => match (char, chars.next()) {
+                ('n', Some((_, 'e'))) => Direction::NorthEast,
+                ('n', Some((_, 'w'))) => Direction::NorthWest,
+                ('s', Some((_, 'e'))) => Direction::SouthEast,
+                ('s', Some((_, 'w'))) => Direction::SouthWest,
+                _ => return Err(Error::at_column(line, index + 1, format!("Expected '{}e' or '{}w'", char, char))),
+            },
+            other => return Err(Error::at_column(line, index + 1, format!("Invalid direction '{}'", other))),
+        };
+        directions.push(direction);
+    }
+
+    Ok(directions)
+}
+
+/// Parse each line of the puzzle input into a path to a tile.
+pub fn parse_input(input: &str) -> Result<Vec<Vec<Direction>>> {
+    parse_lines(input, parse_line)
+}
+
+/// An infinite hexagonal grid of tiles, which are white unless flipped to black.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct HexGrid {
+    /// The `(q, r)` coordinates of the black tiles
+    black: HashSet<(isize, isize)>,
+    /// Lower bound of black tiles in the q dimension
+    q_min: isize,
+    /// Upper bound of black tiles in the q dimension
+    q_max: isize,
+    /// Lower bound of black tiles in the r dimension
+    r_min: isize,
+    /// Upper bound of black tiles in the r dimension
+    r_max: isize,
+}
+
+impl HexGrid {
+    pub fn new() -> HexGrid {
+        HexGrid::default()
+    }
+
+    /// Is the tile at `q`, `r` black?
+    pub fn is_black(&self, q: isize, r: isize) -> bool {
+        self.black.contains(&(q, r))
+    }
+
+    /// Set the colour of the tile at `q`, `r`.
+    pub fn set_tile(&mut self, q: isize, r: isize, black: bool) {
+        if black {
+            self.black.insert((q, r));
+
+            self.q_min = self.q_min.min(q);
+            self.q_max = self.q_max.max(q);
+
+            self.r_min = self.r_min.min(r);
+            self.r_max = self.r_max.max(r);
+        } else {
+            self.black.remove(&(q, r));
+        }
+    }
+
+    /// Turn the tile at `q`, `r` over, so black becomes white and white becomes black.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_24::HexGrid;
+    /// let mut grid = HexGrid::new();
+    ///
+    /// grid.flip_tile(1, -1);
+    /// grid.flip_tile(-2, 1);
+    /// assert!(grid.is_black(1, -1));
+    /// assert_eq!(2, grid.count_black());
+    ///
+    /// grid.flip_tile(1, -1);
+    /// assert!(!grid.is_black(1, -1));
+    /// assert_eq!(1, grid.count_black());
+    ///
+    /// assert_eq!(((-2, 1), (-1, 1)), grid.bounds());
+    /// ```
+    pub fn flip_tile(&mut self, q: isize, r: isize) {
+        let black = self.is_black(q, r);
+        self.set_tile(q, r, !black)
+    }
+
+    /// The number of black tiles.
+    pub fn count_black(&self) -> usize {
+        self.black.len()
+    }
+
+    /// The inclusive `(min, max)` bounds of the black tiles seen so far in the q and r dimensions.
+    pub fn bounds(&self) -> ((isize, isize), (isize, isize)) {
+        ((self.q_min, self.q_max), (self.r_min, self.r_max))
+    }
+
+    /// How many of the six tiles adjacent to `q`, `r` are black.
+    pub fn count_adjacent(&self, q: isize, r: isize) -> usize {
+        Direction::ALL.iter()
+            .map(|direction| direction.offset())
+            .filter(|(dq, dr)| self.is_black(q + dq, r + dr))
+            .count()
+    }
+}
+
+/// Flip the tile at the end of each path, starting with all of the tiles white.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_24::*;
+/// let grid = flip_tiles(&parse_input("esew\nnwwswee\nee\nnwwswee").unwrap());
+///
+/// assert_eq!(2, grid.count_black());
+/// assert!(grid.is_black(0, 1));
+/// assert!(grid.is_black(2, 0));
+/// assert!(!grid.is_black(0, 0));
+/// ```
+pub fn flip_tiles(paths: &[Vec<Direction>]) -> HexGrid {
+    let mut grid = HexGrid::new();
+    for path in paths {
+        let (q, r) = Direction::follow(path);
+        grid.flip_tile(q, r);
+    }
+    grid
+}
+
+/// Produce the next day's grid by applying the rules to the current grid.
+///
+/// > - Any black tile with zero or more than 2 black tiles immediately adjacent to it is flipped
+/// >   to white.
+/// > - Any white tile with exactly 2 black tiles immediately adjacent to it is flipped to black.
+pub fn iterate_grid(grid: &HexGrid) -> HexGrid {
+    let mut new_grid = grid.clone();
+    for r in (grid.r_min - 1)..=(grid.r_max + 1) {
+        for q in (grid.q_min - 1)..=(grid.q_max + 1) {
+            let adjacent = grid.count_adjacent(q, r);
+            let black = if grid.is_black(q, r) {
+                adjacent == 1 || adjacent == 2
+            } else {
+                adjacent == 2
+            };
+            new_grid.set_tile(q, r, black)
+        }
+    }
+
+    new_grid
+}
+
+#[cfg(test)]
+mod tests {
+    use day_24::{flip_tiles, iterate_grid, parse_input, parse_line, Direction};
+    use day_24::Direction::*;
+
+    fn example() -> &'static str {
+        "sesenwnenenewseeswwswswwnenewsewsw
+neeenesenwnwwswnenewnwwsewnenwseswesw
+seswneswswsenwwnwse
+nwnwneseeswswnenewneswwnewseswneseene
+swweswneswnenwsewnwneneseenw
+eesenwseswswnenwswnwnwsewwnwsene
+sewnenenenesenwsewnenwwwse
+wenwwweseeeweswwwnwwe
+wsweesenenewnwwnwsenewsenwwsesesenwne
+neeswseenwwswnwswswnw
+nenwswwsewswnenenewsenwsenwnesesenew
+enewnwewneswsewnwswenweswnenwsenwsw
+sweneswneswneneenwnewenewwneswswnese
+swwesenesewenwneswnwwneseswwne
+enesenwswwswneneswsenwnewswseenwsese
+wnwnesenesenenwwnenwsewesewsesesew
+nenewswnwewswnenesenwnesewesw
+eneswnwswnwsenenwnwnwwseeswneewsenese
+neswnwewnwnwseenwseesewsenwsweewe
+wseweeenwnesenwwwswnew"
+    }
+
+    #[test]
+    fn can_parse() {
+        assert_eq!(Ok(vec!(NorthWest, West, SouthWest, East, East)), parse_line("nwwswee"));
+        assert_eq!(Ok(vec!()), parse_line(""));
+
+        assert_eq!(Some(3), parse_line("nes").unwrap_err().column);
+        assert_eq!(Some(2), parse_line("ex").unwrap_err().column);
+
+        let err = parse_input("esew\nnwwsxee").unwrap_err();
+        assert_eq!((Some(2), Some(4)), (err.line, err.column));
+        assert_eq!(20, parse_input(example()).unwrap().len());
+    }
+
+    #[test]
+    fn can_follow_paths() {
+        assert_eq!((0, 0), Direction::follow(&[]));
+        for direction in Direction::ALL.iter() {
+            assert_eq!(1, flip_tiles(&[vec!(*direction)]).count_adjacent(0, 0));
+        }
+        assert_eq!((0, 0), Direction::follow(&[East, SouthWest, NorthWest]));
+        assert_eq!((0, 0), Direction::follow(&[NorthEast, SouthEast, West]));
+    }
+
+    #[test]
+    fn can_flip_tiles() {
+        let grid = flip_tiles(&parse_input(example()).unwrap());
+
+        assert_eq!(10, grid.count_black());
+    }
+
+    #[test]
+    fn can_iterate_grid() {
+        let mut grid = flip_tiles(&parse_input(example()).unwrap());
+        let expected = vec!(15, 12, 25, 14, 23, 28, 41, 37, 49, 37);
+
+        for count in expected {
+            grid = iterate_grid(&grid);
+            assert_eq!(count, grid.count_black());
+        }
+
+        for _ in 10..100 {
+            grid = iterate_grid(&grid);
+        }
+        assert_eq!(2208, grid.count_black());
+    }
+}
@@ -0,0 +1,245 @@
+//! This is my solution for [Advent of Code - Day 25](https://adventofcode.com/2020/day/25) -
+//! _Combo Breaker_
+//!
+//! Break a Diffie-Hellman style handshake between a door and a key card. Each device has a secret
+//! loop size, and its public key is `7^loop_size mod 20201227`. Recovering the loop size is a
+//! [discrete logarithm](https://en.wikipedia.org/wiki/Discrete_logarithm), which the puzzle is
+//! designed to make feasible by just repeating the transform until it matches
+//! ([`find_loop_size`]).
+//!
+//! That takes tens of millions of steps for a real input though, so [`discrete_log`] uses
+//! [baby-step giant-step](https://en.wikipedia.org/wiki/Baby-step_giant-step) instead. This trades
+//! memory for time, storing the first `√20201227 ≈ 4495` powers of 7 so that the loop size can be
+//! found in `4495` jumps of `4495` steps, with [`mod_pow`] for the jumps.
+//!
+//! There is only one puzzle today, part two is a freebie for having completed every other day.
+//!
+//! __Part 1__ - [`parse_input`], [`discrete_log`], [`encryption_key`].
+
+use std::collections::HashMap;
+use error::{parse_lines, parse_number, Error, Result};
+use solution::Solution;
+
+/// The [`Solution`] for day 25.
+///
+/// - Part one is the encryption key the devices agree on.
+/// - There is no part two puzzle, so it returns a festive message instead.
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input<'a> = (u64, u64);
+    type PartOne = u64;
+    type PartTwo = &'static str;
+
+    fn parse(&self, input: &str) -> Result<(u64, u64)> {
+        parse_input(input)
+    }
+
+    fn part_one(&self, (card_key, door_key): &(u64, u64)) -> Result<u64> {
+        encryption_key(*card_key, *door_key)
+    }
+
+    fn part_two(&self, _: &(u64, u64)) -> Result<&'static str> {
+        Ok("Merry Christmas!")
+    }
+}
+
+/// The subject number used to generate the public keys.
+pub const SUBJECT: u64 = 7;
+/// All the transforms are modulo this prime.
+pub const MODULUS: u64 = 20201227;
+
+/// Parse the two public keys, the card's then the door's.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_25::*;
+/// assert_eq!(Ok((5764801, 17807724)), parse_input("5764801\n17807724"));
+///
+/// assert_eq!(Some(2), parse_input("5764801\n1780772x").unwrap_err().line);
+/// assert!(parse_input("5764801").is_err());
+/// ```
+pub fn parse_input(input: &str) -> Result<(u64, u64)> {
+    let keys: Vec<u64> = parse_lines(input, |line| {
+        let key = parse_number(line, line, 1)?;
+        if key == 0 || key >= MODULUS {
+            return Err(Error::at_column(line, 1, format!("Public keys must be between 1 and {}", MODULUS - 1)));
+        }
+        Ok(key)
+    })?;
+
+    match keys.as_slice() {
+        [card, door] => Ok((*card, *door)),
+        _ => Err(Error::new(format!("Expected 2 public keys, found {}", keys.len()))),
+    }
+}
+
+/// Apply the handshake's transform `loop_size` times one step at a time, as described in the
+/// puzzle.
+///
+/// > - Set the value to itself multiplied by the subject number.
+/// > - Set the value to the remainder after dividing the value by `20201227`.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_25::*;
+/// assert_eq!(5764801, transform(SUBJECT, 8));
+/// assert_eq!(17807724, transform(SUBJECT, 11));
+/// ```
+pub fn transform(subject: u64, loop_size: u64) -> u64 {
+    (0..loop_size).fold(1, |value, _| value * subject % MODULUS)
+}
+
+/// Calculate `base^exponent mod modulus` by repeated squaring, the same result as [`transform`]
+/// but in `log2(exponent)` steps. Works for any `modulus` that fits in a `u64`.
+///
+/// # Panics
+///
+/// If `modulus` is 0.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_25::*;
+/// assert_eq!(transform(SUBJECT, 8), mod_pow(SUBJECT, 8, MODULUS));
+/// assert_eq!(14897079, mod_pow(17807724, 8, MODULUS));
+/// assert_eq!(1, mod_pow(5, 0, 13));
+/// assert_eq!(1, mod_pow(u64::MAX - 1, 2, u64::MAX));
+/// ```
+pub fn mod_pow(base: u64, exponent: u64, modulus: u64) -> u64 {
+    assert!(modulus > 0, "The modulus must be at least 1");
+
+    let mut result = 1 % modulus;
+    let mut base = base % modulus;
+    let mut exponent = exponent;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+
+    result
+}
+
+/// `a * b mod modulus`, with the product widened so that it can't overflow.
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+/// Find the loop size for a public key by transforming the subject number one step at a time
+/// until it matches, the brute force approach. Returns `None` if the key is never reached.
+pub fn find_loop_size(public_key: u64) -> Option<u64> {
+    let mut value = 1;
+    for loop_size in 0..MODULUS {
+        if value == public_key {
+            return Some(loop_size);
+        }
+        value = value * SUBJECT % MODULUS;
+    }
+
+    None
+}
+
+/// Find the smallest `x` such that `base^x mod modulus = target` using baby-step giant-step.
+/// `modulus` must be prime, so that the giant step can be inverted with Fermat's little theorem.
+/// Returns `None` if there is no such `x`. This stores `√modulus` baby steps, so is only practical
+/// for moduli up to around `2^40`.
+///
+/// # Panics
+///
+/// If `modulus` is 0.
+///
+/// With `m = ⌈√modulus⌉`, any `x < modulus` can be written as `i * m + j` with `i, j < m`. The baby
+/// steps record `base^j` for each `j`, then the giant steps check `target * base^(-m * i)` against
+/// them for each `i`.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_25::*;
+/// assert_eq!(Some(8), discrete_log(SUBJECT, 5764801, MODULUS));
+/// assert_eq!(Some(11), discrete_log(SUBJECT, 17807724, MODULUS));
+/// assert_eq!(None, discrete_log(2, 3, 7));
+/// assert_eq!(Some(0), discrete_log(2, 3, 1));
+/// ```
+pub fn discrete_log(base: u64, target: u64, modulus: u64) -> Option<u64> {
+    assert!(modulus > 0, "The modulus must be at least 1");
+    // everything is 0 mod 1, including base^0
+    if modulus == 1 {
+        return Some(0);
+    }
+
+    let steps = (1..).find(|&m: &u64| m as u128 * m as u128 >= modulus as u128).unwrap_or(1);
+
+    let mut baby_steps: HashMap<u64, u64> = HashMap::with_capacity(steps as usize);
+    let mut value = 1 % modulus;
+    for j in 0..steps {
+        baby_steps.entry(value).or_insert(j);
+        value = mul_mod(value, base, modulus);
+    }
+
+    // base^(-m) = base^(m * (p - 2)), as base^(p - 1) = 1 mod p
+    let giant_step = mod_pow(mod_pow(base, steps, modulus), modulus - 2, modulus);
+    let mut gamma = target % modulus;
+    for i in 0..steps {
+        if let Some(j) = baby_steps.get(&gamma) {
+            return Some(i * steps + j);
+        }
+        gamma = mul_mod(gamma, giant_step, modulus);
+    }
+
+    None
+}
+
+/// The encryption key, the door's public key transformed by the card's loop size. This is the
+/// same as the card's public key transformed by the door's loop size.
+pub fn encryption_key(card_key: u64, door_key: u64) -> Result<u64> {
+    let card_loop_size = discrete_log(SUBJECT, card_key, MODULUS)
+        .ok_or_else(|| Error::new(format!("Failed to find the loop size for the card's key {}", card_key)))?;
+
+    Ok(mod_pow(door_key, card_loop_size, MODULUS))
+}
+
+#[cfg(test)]
+mod tests {
+    use day_25::{discrete_log, encryption_key, find_loop_size, mod_pow, transform, MODULUS, SUBJECT};
+
+    #[test]
+    fn can_transform() {
+        assert_eq!(5764801, transform(SUBJECT, 8));
+        assert_eq!(17807724, transform(SUBJECT, 11));
+        assert_eq!(14897079, transform(17807724, 8));
+        assert_eq!(14897079, transform(5764801, 11));
+
+        for exponent in 0..50 {
+            assert_eq!(transform(SUBJECT, exponent), mod_pow(SUBJECT, exponent, MODULUS));
+        }
+    }
+
+    #[test]
+    fn can_find_loop_sizes() {
+        assert_eq!(Some(8), find_loop_size(5764801));
+        assert_eq!(Some(11), find_loop_size(17807724));
+        assert_eq!(Some(0), find_loop_size(1));
+
+        assert_eq!(Some(8), discrete_log(SUBJECT, 5764801, MODULUS));
+        assert_eq!(Some(11), discrete_log(SUBJECT, 17807724, MODULUS));
+        assert_eq!(Some(0), discrete_log(SUBJECT, 1, MODULUS));
+
+        let key = mod_pow(SUBJECT, 12345678, MODULUS);
+        assert_eq!(Some(12345678), discrete_log(SUBJECT, key, MODULUS));
+
+        // a prime above 2^32, where the products no longer fit in a u64
+        let modulus = 4_294_967_311;
+        let key = mod_pow(3, 123_456_789, modulus);
+        let loop_size = discrete_log(3, key, modulus).unwrap();
+        assert_eq!(key, mod_pow(3, loop_size, modulus));
+    }
+
+    #[test]
+    fn can_find_encryption_key() {
+        assert_eq!(Ok(14897079), encryption_key(5764801, 17807724));
+        assert_eq!(Ok(14897079), encryption_key(17807724, 5764801));
+    }
+}
@@ -1,49 +1,363 @@
+//! This is my solution for [Advent of Code - Day 3](https://adventofcode.com/2020/day/3) -
+//! _Toboggan Trajectory_
+//!
+//! Count the trees hit sliding down a map that repeats infinitely to the right. A [`Slope`] can
+//! move left as well as right, with the map repeating in both directions, and isn't reduced to its
+//! simplest form, as `(2, 2)` only visits every other row that `(1, 1)` does.
+//!
+//! __Part 1__ - [`parse_line`], [`count_trees`].
+//!
+//! __Part 2__ - as part 1 for each of the slopes. [`search_slopes`] goes further and finds the
+//! best and worst slopes.
+//!
+//! For debugging there is also a [`Route`], which draws the path over the map in the same way as
+//! the puzzle description, as text, coloured text for a terminal, or a PPM image.
+
+use std::fmt::{self, Display, Formatter};
 use std::fs;
+use std::path::Path;
+use error::{parse_lines, Error, Result};
+use solution::Solution;
+
+/// The [`Solution`] for day 3, counts the trees hit on the way down the slope.
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input<'a> = Vec<Vec<bool>>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<Vec<bool>>> {
+        parse_lines(input, parse_line)
+    }
+
+    fn part_one(&self, lines: &Vec<Vec<bool>>) -> Result<usize> {
+        Ok(count_trees(lines, Slope::new(3, 1)))
+    }
+
+    fn part_two(&self, lines: &Vec<Vec<bool>>) -> Result<usize> {
+        Ok(
+            vec!(Slope::new(1, 1), Slope::new(3, 1), Slope::new(5, 1), Slope::new(7, 1), Slope::new(1, 2))
+                .into_iter()
+                .map(|slope| count_trees(lines, slope))
+                .product()
+        )
+    }
+}
 
-pub fn run() {
-    let contents = fs::read_to_string("res/day-3-input").expect("Failed to read file");
-    let lines: Vec<Vec<bool>> = contents.lines().map(|l| parse_line(l)).collect();
-    let count31 = count_trees(lines.clone(), 3, 1);
-    println!("Encountered {} trees.", count31);
-
-    let count11 = count_trees(lines.clone(), 1, 1);
-    let count51 = count_trees(lines.clone(), 5, 1);
-    let count71 = count_trees(lines.clone(), 7, 1);
-    let count12 = count_trees(lines.clone(), 1, 2);
-
-    println!(
-        "Encountered {} x {} x {} x {} x {} = {} trees.",
-        count11, count31, count51, count71, count12,
-        count11 * count31 * count51 * count71 * count12
-    );
+/// How far the toboggan moves each step, `right` is negative for moving left.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub struct Slope {
+    pub right: isize,
+    pub down: usize,
 }
 
-fn count_trees(lines: Vec<Vec<bool>>, slope: usize, speed: usize) -> usize {
-    lines.iter().fold(
-        (0usize, 0usize, 0usize),
-        |(pos_x, pos_y, acc), line|
-            if pos_y % speed == 0 {
-                (
-                    (pos_x + slope) % line.len(),
-                    pos_y + 1,
-                    acc + (line.get(pos_x).map(|b| match b {
-                        true => 1,
-                        false => 0
-                    }).unwrap_or(0))
-                )
-            } else {
-                (pos_x, pos_y + 1, acc)
+impl Slope {
+    pub fn new(right: isize, down: usize) -> Slope {
+        Slope { right, down }
+    }
+}
+
+/// The squares visited going down the map from the top left, as `(x, y, is_tree)`. `x` is the
+/// position within the row, after wrapping round the repeated map. A slope that doesn't move down
+/// only visits the starting square, rather than sliding sideways forever.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_3::*;
+/// let map = vec!(
+///     parse_line("..#").unwrap(),
+///     parse_line("#..").unwrap(),
+///     parse_line(".#.").unwrap(),
+/// );
+///
+/// assert_eq!(
+///     vec!((0, 0, false), (2, 1, false), (1, 2, true)),
+///     path(&map, Slope::new(-1, 1)).collect::<Vec<(usize, usize, bool)>>()
+/// );
+/// assert_eq!(vec!((0, 0, false)), path(&map, Slope::new(1, 0)).collect::<Vec<(usize, usize, bool)>>());
+/// ```
+pub fn path(map: &[Vec<bool>], slope: Slope) -> impl Iterator<Item=(usize, usize, bool)> + '_ {
+    let rows = if slope.down == 0 { map.len().min(1) } else { map.len() };
+
+    (0..rows)
+        .step_by(slope.down.max(1))
+        .enumerate()
+        .map(move |(step, y)| {
+            let row = &map[y];
+            let x = (step as isize * slope.right).rem_euclid(row.len() as isize) as usize;
+            (x, y, row[x])
+        })
+}
+
+/// Count the trees hit going down the map with the given slope.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_3::*;
+/// let map = vec!(
+///     parse_line("..#").unwrap(),
+///     parse_line("#..").unwrap(),
+///     parse_line(".#.").unwrap(),
+/// );
+///
+/// assert_eq!(0, count_trees(&map, Slope::new(1, 1)));
+/// assert_eq!(1, count_trees(&map, Slope::new(2, 1)));
+/// ```
+pub fn count_trees(map: &[Vec<bool>], slope: Slope) -> usize {
+    path(map, slope).filter(|(_, _, tree)| *tree).count()
+}
+
+/// The slopes that hit the fewest and most trees, see [`search_slopes`].
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct SlopeSearch {
+    /// The slope hitting the fewest trees, and how many it hits.
+    pub fewest: (Slope, usize),
+    /// The slope hitting the most trees, and how many it hits.
+    pub most: (Slope, usize),
+}
+
+/// Count the trees for every slope moving up to `bound` squares left or right, and from 1 up to
+/// `bound` squares down. Where slopes tie, the one that moves down the least is chosen, then the
+/// one furthest left. Returns `None` if `bound` is 0, as there are no slopes to try.
+///
+/// # Examples from Tests
+/// ```
+/// # use advent_of_code_2020::day_3::*;
+/// let map = vec!(
+///     parse_line("..#").unwrap(),
+///     parse_line("#..").unwrap(),
+///     parse_line(".#.").unwrap(),
+/// );
+///
+/// let search = search_slopes(&map, 1).unwrap();
+/// assert_eq!((Slope::new(1, 1), 0), search.fewest);
+/// assert_eq!((Slope::new(-1, 1), 1), search.most);
+/// ```
+pub fn search_slopes(map: &[Vec<bool>], bound: usize) -> Option<SlopeSearch> {
+    let bound = bound as isize;
+    let slopes = (1..=bound).flat_map(|down| (-bound..=bound).map(move |right| Slope::new(right, down as usize)));
+
+    let mut search: Option<SlopeSearch> = None;
+    for slope in slopes {
+        let trees = count_trees(map, slope);
+        search = Some(match search {
+            None => SlopeSearch { fewest: (slope, trees), most: (slope, trees) },
+            Some(SlopeSearch { fewest, most }) => SlopeSearch {
+                fewest: if trees < fewest.1 { (slope, trees) } else { fewest },
+                most: if trees > most.1 { (slope, trees) } else { most },
             },
-    ).2
+        });
+    }
+
+    search
+}
+
+/// A square of a [`Route`].
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Square {
+    /// Open ground that isn't on the path, `.`.
+    Open,
+    /// A tree that isn't on the path, `#`.
+    Tree,
+    /// Open ground the path crosses, `O`.
+    Clear,
+    /// A tree the path hits, `X`.
+    Hit,
+}
+
+impl Square {
+    fn symbol(&self) -> char {
+        match self {
+            Square::Open => '.',
+            Square::Tree => '#',
+            Square::Clear => 'O',
+            Square::Hit => 'X',
+        }
+    }
+
+    /// The escape code to colour this square in a terminal.
+    fn ansi_colour(&self) -> &'static str {
+        match self {
+            Square::Open => "\x1b[2m",
+            Square::Tree => "\x1b[32m",
+            Square::Clear => "\x1b[1;33m",
+            Square::Hit => "\x1b[1;31m",
+        }
+    }
+
+    /// The RGB colour of this square in an image.
+    fn rgb(&self) -> (u8, u8, u8) {
+        match self {
+            Square::Open => (255, 255, 255),
+            Square::Tree => (34, 139, 34),
+            Square::Clear => (255, 200, 0),
+            Square::Hit => (220, 20, 60),
+        }
+    }
+}
+
+/// How to draw a [`Route`].
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum RenderFormat {
+    /// The characters used by the puzzle description, one line per row.
+    Text,
+    /// As [`RenderFormat::Text`], coloured with ANSI escape codes for a terminal.
+    Ansi,
+    /// A plain [PPM](https://netpbm.sourceforge.net/doc/ppm.html) image, with each square drawn
+    /// as a block of `scale` by `scale` pixels.
+    Ppm { scale: usize },
+}
+
+/// The map with the path for a slope drawn over it. The map is repeated to the left and right as
+/// far as the path goes, starting from the copy the path starts in.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Route {
+    squares: Vec<Vec<Square>>,
+}
+
+impl Route {
+    /// Draw the path for `slope` over the map.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_3::*;
+    /// let map = vec!(
+    ///     parse_line("..#").unwrap(),
+    ///     parse_line("#..").unwrap(),
+    ///     parse_line(".#.").unwrap(),
+    /// );
+    ///
+    /// assert_eq!("O.#..#..#\n#..X..#..\n.#..#.O#.", Route::new(&map, Slope::new(3, 1)).to_string());
+    /// assert_eq!("..#O.#\n#.O#..\n.X..#.", Route::new(&map, Slope::new(-1, 1)).to_string());
+    /// ```
+    pub fn new(map: &[Vec<bool>], slope: Slope) -> Route {
+        // the path visits at most one square per row, this is its x position before wrapping
+        let mut visited: Vec<Option<isize>> = vec!(None; map.len());
+        for (step, (_, y, _)) in path(map, slope).enumerate() {
+            visited[y] = Some(step as isize * slope.right);
+        }
+
+        // whole copies of the map, starting at the one the path starts in, that cover the path
+        let width = map.iter().map(|row| row.len()).max().unwrap_or(0) as isize;
+        let copies = |x: isize| if width == 0 { 0 } else { x.div_euclid(width) };
+        let first_copy = visited.iter().flatten().map(|&x| copies(x)).min().unwrap_or(0);
+        let last_copy = visited.iter().flatten().map(|&x| copies(x)).max().unwrap_or(0);
+        let columns = (first_copy * width)..((last_copy + 1) * width);
+
+        let squares = map.iter()
+            .zip(visited.iter())
+            .map(|(row, visited)| {
+                columns.clone()
+                    .map(|x| {
+                        let tree = row[x.rem_euclid(row.len() as isize) as usize];
+                        match (*visited == Some(x), tree) {
+                            (false, false) => Square::Open,
+                            (false, true) => Square::Tree,
+                            (true, false) => Square::Clear,
+                            (true, true) => Square::Hit,
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Route { squares }
+    }
+
+    /// The drawn squares, a row at a time.
+    pub fn squares(&self) -> &[Vec<Square>] {
+        &self.squares
+    }
+
+    /// Draw the route in the given format.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_3::*;
+    /// let map = vec!(parse_line("#.").unwrap(), parse_line(".#").unwrap());
+    /// let route = Route::new(&map, Slope::new(0, 1));
+    ///
+    /// assert_eq!("X.\nO#", route.render(RenderFormat::Text));
+    /// assert_eq!(
+    ///     "P3\n2 2\n255\n220 20 60 255 255 255\n255 200 0 34 139 34\n",
+    ///     route.render(RenderFormat::Ppm { scale: 1 })
+    /// );
+    /// ```
+    pub fn render(&self, format: RenderFormat) -> String {
+        match format {
+            RenderFormat::Text => self.to_string(),
+            RenderFormat::Ansi => self.squares.iter()
+                .map(|row| {
+                    let squares: String = row.iter()
+                        .map(|square| format!("{}{}", square.ansi_colour(), square.symbol()))
+                        .collect();
+                    format!("{}\x1b[0m", squares)
+                })
+                .collect::<Vec<String>>()
+                .join("\n"),
+            RenderFormat::Ppm { scale } => {
+                let width = self.squares.first().map(|row| row.len()).unwrap_or(0);
+                let mut image = format!("P3\n{} {}\n255\n", width * scale, self.squares.len() * scale);
+
+                for row in &self.squares {
+                    let pixels: Vec<String> = row.iter()
+                        .flat_map(|square| {
+                            let (r, g, b) = square.rgb();
+                            (0..scale).map(move |_| format!("{} {} {}", r, g, b))
+                        })
+                        .collect();
+                    let line = pixels.join(" ");
+                    for _ in 0..scale {
+                        image.push_str(&line);
+                        image.push('\n');
+                    }
+                }
+
+                image
+            }
+        }
+    }
+
+    /// Draw the route in the given format to `path`, replacing any existing file.
+    pub fn save<P: AsRef<Path>>(&self, path: P, format: RenderFormat) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.render(format))
+            .map_err(|err| Error::new(format!("Failed to write '{}': {}", path.display(), err)))
+    }
+}
+
+/// Formats the route as text, as in [`RenderFormat::Text`].
+impl Display for Route {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let rows: Vec<String> = self.squares.iter()
+            .map(|row| row.iter().map(|square| square.symbol()).collect())
+            .collect();
+        write!(f, "{}", rows.join("\n"))
+    }
 }
 
-fn parse_line(line: &str) -> Vec<bool> {
-    line.chars().map(|c| c == '#').collect()
+/// Parse a line of the map, `true` marks a tree (`#`) and `false` open ground (`.`).
+pub fn parse_line(line: &str) -> Result<Vec<bool>> {
+    if line.is_empty() {
+        return Err(Error::invalid(line, "Empty row in map"));
+    }
+
+    line.chars()
+        .enumerate()
+        .map(|(index, c)| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            other => Err(Error::at_column(line, index + 1, format!("Invalid map square '{}'", other))),
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use day_3::{count_trees, parse_line};
+    use std::{env, fs};
+    use day_3::{count_trees, parse_line, path, search_slopes, RenderFormat, Route, Slope, Square};
+    use error::Error;
 
     fn test_lines() -> Vec<&'static str> {
         vec!(
@@ -64,32 +378,128 @@ mod tests {
     #[test]
     fn can_parse_file() {
         assert_eq!(
-            vec!(false, false, true, true, false, false, false, false, false, false, false),
-            parse_line(test_lines().get(0).unwrap())
+            Ok(vec!(false, false, true, true, false, false, false, false, false, false, false)),
+            parse_line(test_lines().first().unwrap())
         );
+        assert_eq!(Err(Error::at_column("..#o.", 4, "Invalid map square 'o'")), parse_line("..#o."));
+    }
+
+    fn test_map() -> Vec<Vec<bool>> {
+        test_lines().iter().map(|line| parse_line(line).unwrap()).collect()
     }
 
     #[test]
     fn can_count_trees() {
+        let map = test_map();
+
+        assert_eq!(2usize, count_trees(&map, Slope::new(1, 1)));
+        assert_eq!(7usize, count_trees(&map, Slope::new(3, 1)));
+        assert_eq!(3usize, count_trees(&map, Slope::new(5, 1)));
+        assert_eq!(4usize, count_trees(&map, Slope::new(7, 1)));
+        assert_eq!(2usize, count_trees(&map, Slope::new(1, 2)));
+    }
+
+    #[test]
+    fn can_count_trees_on_any_slope() {
+        let map = test_map();
+        let width = map[0].len() as isize;
+
+        // moving left is the same as moving right by the rest of the width
+        for right in 1..width {
+            assert_eq!(count_trees(&map, Slope::new(right - width, 1)), count_trees(&map, Slope::new(right, 1)));
+            assert_eq!(count_trees(&map, Slope::new(right - width, 2)), count_trees(&map, Slope::new(right, 2)));
+        }
+
+        // steps that aren't coprime skip rows
+        assert_eq!(vec!(0, 2, 4, 6, 8, 10), path(&map, Slope::new(2, 2)).map(|(_, y, _)| y).collect::<Vec<usize>>());
+        assert_eq!(vec!(0, 5, 10, 4), path(&map, Slope::new(-6, 3)).map(|(x, _, _)| x).collect::<Vec<usize>>());
+
+        assert_eq!(1, path(&map, Slope::new(3, 0)).count());
+        assert_eq!(0, path(&[], Slope::new(3, 1)).count());
+        assert_eq!(3, count_trees(&map, Slope::new(0, 1)));
+    }
+
+    #[test]
+    fn can_search_slopes() {
+        let map = test_map();
+
+        assert_eq!(None, search_slopes(&map, 0));
+
+        let search = search_slopes(&map, 3).unwrap();
+        assert_eq!((Slope::new(1, 3), 0), search.fewest);
+        assert_eq!((Slope::new(3, 1), 7), search.most);
+
+        let search = search_slopes(&map, 11).unwrap();
+        for down in 1..=11 {
+            for right in -11..=11 {
+                let trees = count_trees(&map, Slope::new(right, down));
+                assert!(trees >= search.fewest.1 && trees <= search.most.1);
+            }
+        }
+    }
+
+    #[test]
+    fn can_draw_route() {
+        let route = Route::new(&test_map(), Slope::new(3, 1));
+
+        // the example in the puzzle description, as far as the path goes
         assert_eq!(
-            2usize,
-            count_trees(test_lines().iter().map(|l| parse_line(l)).collect(), 1, 1)
-        );
-        assert_eq!(
-            7usize,
-            count_trees(test_lines().iter().map(|l| parse_line(l)).collect(), 3, 1)
+            "O.##.........##.........##.......
+#..O#...#..#...#...#..#...#...#..
+.#....X..#..#....#..#..#....#..#.
+..#.#...#O#..#.#...#.#..#.#...#.#
+.#...##..#..X...##..#..#...##..#.
+..#.##.......#.X#.......#.##.....
+.#.#.#....#.#.#.#.O..#.#.#.#....#
+.#........#.#........X.#........#
+#.##...#...#.##...#...#.X#...#...
+#...##....##...##....##...#X....#
+.#..#...#.#.#..#...#.#.#..#...X.#",
+            route.to_string()
         );
+        assert_eq!(7, route.squares().iter().flatten().filter(|square| **square == Square::Hit).count());
+    }
+
+    #[test]
+    fn can_draw_route_to_the_left() {
+        let route = Route::new(&test_map(), Slope::new(-1, 2));
+
+        // one copy of the map to the left of the starting one
+        assert_eq!(22, route.squares()[0].len());
         assert_eq!(
-            3usize,
-            count_trees(test_lines().iter().map(|l| parse_line(l)).collect(), 5, 1)
+            vec!((0, 11), (2, 10), (4, 9), (6, 8), (8, 7), (10, 6)),
+            route.squares().iter()
+                .enumerate()
+                .flat_map(|(y, row)| row.iter().enumerate()
+                    .filter(|(_, square)| **square == Square::Clear || **square == Square::Hit)
+                    .map(move |(x, _)| (y, x)))
+                .collect::<Vec<(usize, usize)>>()
         );
+    }
+
+    #[test]
+    fn can_render_routes() {
+        let map = vec!(parse_line("#.").unwrap(), parse_line(".#").unwrap());
+        let route = Route::new(&map, Slope::new(1, 1));
+
+        assert_eq!("X.\n.X", route.render(RenderFormat::Text));
         assert_eq!(
-            4usize,
-            count_trees(test_lines().iter().map(|l| parse_line(l)).collect(), 7, 1)
+            "\x1b[1;31mX\x1b[2m.\x1b[0m\n\x1b[2m.\x1b[1;31mX\x1b[0m",
+            route.render(RenderFormat::Ansi)
         );
         assert_eq!(
-            2usize,
-            count_trees(test_lines().iter().map(|l| parse_line(l)).collect(), 1, 2)
+            "P3\n4 4\n255
+220 20 60 220 20 60 255 255 255 255 255 255
+220 20 60 220 20 60 255 255 255 255 255 255
+255 255 255 255 255 255 220 20 60 220 20 60
+255 255 255 255 255 255 220 20 60 220 20 60
+",
+            route.render(RenderFormat::Ppm { scale: 2 })
         );
+
+        let path = env::temp_dir().join(format!("day-3-route-{}.ppm", std::process::id()));
+        route.save(&path, RenderFormat::Ppm { scale: 1 }).unwrap();
+        assert_eq!(route.render(RenderFormat::Ppm { scale: 1 }), fs::read_to_string(&path).unwrap());
+        fs::remove_file(&path).unwrap();
     }
 }
@@ -1,150 +1,422 @@
+//! This is my solution for [Advent of Code - Day 4](https://adventofcode.com/2020/day/4) -
+//! _Passport Processing_
+//!
+//! Part two's rules for each field are described by a [`Schema`] rather than hard coded, so that
+//! they can be changed by loading a different schema file. The puzzle's rules are the
+//! [`DEFAULT_SCHEMA`], which [`Day4`] uses unless there is a [`SCHEMA_FILE`] in the input
+//! directory. Each line of a schema describes a field, whether it is required, and the
+//! rule its value must pass:
+//!
+//! ```text
+//! # comments and blank lines are ignored
+//! byr required year 1920-2002
+//! hgt required measure cm:150-193 in:59-76
+//! hcl required pattern #[0-9a-f]{6}
+//! ecl required one-of amb blu brn gry grn hzl oth
+//! cid optional any
+//! ```
+//!
+//! - `any` accepts any value.
+//! - `year <min>-<max>` accepts four digit years in the range.
+//! - `measure <unit>:<min>-<max> ...` accepts a number followed by one of the units, in the range
+//!   for that unit.
+//! - `pattern <regex>` accepts values that the whole regex matches. The regex is the rest of the
+//!   line, so can contain spaces.
+//! - `one-of <value> ...` accepts exactly one of the values.
+//!
+//! Fields that aren't in the schema are ignored.
+//!
+//! __Part 1__ - [`parse_passports`], [`Schema::has_required_fields`].
+//!
+//! __Part 2__ - [`Schema::is_valid`].
+
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use regex::Regex;
+use error::{parse_number, Error, Result};
+use solution::Solution;
 
+/// The fields of a passport, each may be missing.
 #[derive(Debug, Eq, PartialEq)]
-struct Passport<'a> {
-    byr: Option<&'a str>,
-    cid: Option<&'a str>,
-    ecl: Option<&'a str>,
-    eyr: Option<&'a str>,
-    hcl: Option<&'a str>,
-    hgt: Option<&'a str>,
-    iyr: Option<&'a str>,
-    pid: Option<&'a str>,
+pub struct Passport<'a> {
+    fields: BTreeMap<&'a str, &'a str>,
 }
 
 impl<'a> Passport<'a> {
-    fn from_map(map: HashMap<&str, &'a str>) -> Passport<'a> {
-        Passport {
-            byr: map.get("byr").map(|str| *str),
-            cid: map.get("cid").map(|str| *str),
-            ecl: map.get("ecl").map(|str| *str),
-            eyr: map.get("eyr").map(|str| *str),
-            hcl: map.get("hcl").map(|str| *str),
-            hgt: map.get("hgt").map(|str| *str),
-            iyr: map.get("iyr").map(|str| *str),
-            pid: map.get("pid").map(|str| *str),
-        }
+    /// Build a passport from the `key:value` pairs parsed from the input.
+    pub fn from_map(map: HashMap<&'a str, &'a str>) -> Passport<'a> {
+        Passport { fields: map.into_iter().collect() }
     }
 
-    // all fields required except cid
-    fn has_valid_fields(&self) -> bool {
-        self.byr.is_some() &&
-            self.ecl.is_some() &&
-            self.eyr.is_some() &&
-            self.hcl.is_some() &&
-            self.hgt.is_some() &&
-            self.iyr.is_some() &&
-            self.pid.is_some()
+    /// The value of the field called `name`, if the passport has one.
+    pub fn get(&self, name: &str) -> Option<&'a str> {
+        self.fields.get(name).copied()
     }
 
-    // You can continue to ignore the cid field, but each other field has strict rules about what values are valid for automatic validation:
-    //
-    //     byr (Birth Year) - four digits; at least 1920 and at most 2002.
-    //     iyr (Issue Year) - four digits; at least 2010 and at most 2020.
-    //     eyr (Expiration Year) - four digits; at least 2020 and at most 2030.
-    //     hgt (Height) - a number followed by either cm or in:
-    //         If cm, the number must be at least 150 and at most 193.
-    //         If in, the number must be at least 59 and at most 76.
-    //     hcl (Hair Color) - a # followed by exactly six characters 0-9 or a-f.
-    //     ecl (Eye Color) - exactly one of: amb blu brn gry grn hzl oth.
-    //     pid (Passport ID) - a nine-digit number, including leading zeroes.
-    //     cid (Country ID) - ignored, missing or not.
-    fn is_valid(&self) -> bool {
-        match self.byr {
-            None => return false,
-            Some(byr) if !<Passport<'a>>::is_valid_year(byr, 1920, 2002) => return false,
-            _ => ()
-        };
+    /// Part one, are all of the fields required by the [`DEFAULT_SCHEMA`] present?
+    pub fn has_valid_fields(&self) -> bool {
+        default_schema().has_required_fields(self)
+    }
 
-        match self.iyr {
-            None => return false,
-            Some(iyr) if !<Passport<'a>>::is_valid_year(iyr, 2010, 2020) => return false,
-            _ => ()
-        };
+    /// Part two, are all of the required fields present, and are their values valid under the
+    /// [`DEFAULT_SCHEMA`]?
+    pub fn is_valid(&self) -> bool {
+        default_schema().is_valid(self)
+    }
+}
 
-        match self.eyr {
-            None => return false,
-            Some(byr) if !<Passport<'a>>::is_valid_year(byr, 2020, 2030) => return false,
-            _ => ()
-        };
+/// The puzzle's rules, used by [`Schema::default`].
+///
+/// > - `byr` (Birth Year) - four digits; at least `1920` and at most `2002`.
+/// > - `iyr` (Issue Year) - four digits; at least `2010` and at most `2020`.
+/// > - `eyr` (Expiration Year) - four digits; at least `2020` and at most `2030`.
+/// > - `hgt` (Height) - a number followed by either `cm` or `in`:
+/// >   - If `cm`, the number must be at least `150` and at most `193`.
+/// >   - If `in`, the number must be at least `59` and at most `76`.
+/// > - `hcl` (Hair Color) - a `#` followed by exactly six characters `0`-`9` or `a`-`f`.
+/// > - `ecl` (Eye Color) - exactly one of: `amb` `blu` `brn` `gry` `grn` `hzl` `oth`.
+/// > - `pid` (Passport ID) - a nine-digit number, including leading zeroes.
+/// > - `cid` (Country ID) - ignored, missing or not.
+pub const DEFAULT_SCHEMA: &str = "\
+byr required year 1920-2002
+iyr required year 2010-2020
+eyr required year 2020-2030
+hgt required measure cm:150-193 in:59-76
+hcl required pattern #[0-9a-f]{6}
+ecl required one-of amb blu brn gry grn hzl oth
+pid required pattern [0-9]{9}
+cid optional any";
+
+/// The name of the file in the input directory that replaces the [`DEFAULT_SCHEMA`], see [`Day4`].
+pub const SCHEMA_FILE: &str = "day-4-schema";
+
+/// The [`DEFAULT_SCHEMA`], parsed once and shared.
+fn default_schema() -> &'static Schema {
+    static SCHEMA: OnceLock<Schema> = OnceLock::new();
+    SCHEMA.get_or_init(|| Schema::parse(DEFAULT_SCHEMA).expect("Invalid default schema"))
+}
+
+/// The rule a field's value must pass, see the [module documentation](self) for the syntax.
+#[derive(Debug, Clone)]
+pub enum FieldRule {
+    Any,
+    Year { min: u32, max: u32 },
+    /// The units and the range allowed for each.
+    Measure(Vec<(String, u32, u32)>),
+    /// A regex anchored to match the whole value.
+    Pattern(Regex),
+    OneOf(Vec<String>),
+}
+
+impl FieldRule {
+    /// Does the value pass the rule?
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_4::*;
+    /// let hgt = FieldRule::Measure(vec!(("cm".to_string(), 150, 193), ("in".to_string(), 59, 76)));
+    ///
+    /// assert!(hgt.accepts("60in"));
+    /// assert!(hgt.accepts("190cm"));
+    /// assert!(!hgt.accepts("190in"));
+    /// assert!(!hgt.accepts("190"));
+    /// ```
+    pub fn accepts(&self, value: &str) -> bool {
+        match self {
+            FieldRule::Any => true,
+            FieldRule::Year { min, max } => value.len() == 4 && in_range(value, *min, *max),
+            FieldRule::Measure(units) => units.iter().any(|(unit, min, max)| {
+                value.strip_suffix(unit.as_str()).map(|number| in_range(number, *min, *max)).unwrap_or(false)
+            }),
+            FieldRule::Pattern(re) => re.is_match(value),
+            FieldRule::OneOf(values) => values.iter().any(|allowed| allowed == value),
+        }
+    }
+}
+
+/// Is `text` all digits, and a number between `min` and `max` inclusive?
+fn in_range(text: &str, min: u32, max: u32) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_ascii_digit()) &&
+        text.parse::<u32>().map(|number| min <= number && number <= max).unwrap_or(false)
+}
+
+/// How a single field of a passport is validated.
+#[derive(Debug, Clone)]
+pub struct FieldSchema {
+    pub name: String,
+    /// Whether a passport without this field is invalid.
+    pub required: bool,
+    pub rule: FieldRule,
+}
+
+/// The fields a passport should have, and the rules their values must follow.
+#[derive(Debug, Clone)]
+pub struct Schema {
+    pub fields: Vec<FieldSchema>,
+}
 
-        let hgt_re = Regex::new(r"^(\d{2,3})(cm|in)$").unwrap();
-        let hgt =
-            self.hgt
-                .map(|s| hgt_re.captures(s))
-                .flatten()
-                .map(|cap| (
-                    cap.get(1).unwrap().as_str().parse::<u8>().unwrap(),
-                    cap.get(2).unwrap().as_str())
+impl Default for Schema {
+    /// The puzzle's rules, see [`DEFAULT_SCHEMA`].
+    fn default() -> Schema {
+        default_schema().clone()
+    }
+}
+
+impl Schema {
+    /// Parse a schema, one field per line, see the [module documentation](self) for the syntax.
+    /// Each field can only be described once.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use advent_of_code_2020::day_4::*;
+    /// let schema = Schema::parse("# heights in cm only\nhgt required measure cm:150-193").unwrap();
+    /// assert_eq!(1, schema.fields.len());
+    ///
+    /// let err = Schema::parse("byr required\nhgt required measure cm:150").unwrap_err();
+    /// assert_eq!((Some(1), None), (err.line, err.column));
+    /// let err = Schema::parse("hgt required measure cm:150").unwrap_err();
+    /// assert_eq!((Some(1), Some(25)), (err.line, err.column));
+    /// let err = Schema::parse("hgt required any\nhgt optional any").unwrap_err();
+    /// assert_eq!((Some(2), Some(1)), (err.line, err.column));
+    /// ```
+    pub fn parse(schema: &str) -> Result<Schema> {
+        let mut fields: Vec<FieldSchema> = Vec::new();
+
+        for (index, line) in schema.lines().enumerate() {
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+
+            let field = parse_field(line).map_err(|err| err.on_line(index + 1, line))?;
+            if fields.iter().any(|existing| existing.name == field.name) {
+                let column = line.len() - line.trim_start().len() + 1;
+                return Err(
+                    Error::at_column(line, column, format!("Field '{}' is already in the schema", field.name))
+                        .on_line(index + 1, line)
                 );
-        
-        match hgt {
-            Some((cm, "cm")) if cm >= 150 && cm <= 193 => (),
-            Some((inch, "in")) if inch >= 59 && inch <= 76 => (),
-            _ => return false
+            }
+            fields.push(field);
         }
-        
-        let hcl_re = Regex::new(r"^#[a-f0-9]{6}$").unwrap();
-        match self.hcl {
-            Some(hcl) if hcl_re.is_match(hcl) => (),
-            _ => return false
+
+        Ok(Schema { fields })
+    }
+
+    /// Load a schema from the file at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Schema> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|err| Error::new(format!("Failed to read '{}': {}", path.display(), err)))?;
+
+        Schema::parse(&contents)
+            .map_err(|err| Error { message: format!("{} in '{}'", err.message, path.display()), ..err })
+    }
+
+    /// Does the passport have all of the required fields?
+    pub fn has_required_fields(&self, passport: &Passport) -> bool {
+        self.fields.iter().all(|field| !field.required || passport.get(&field.name).is_some())
+    }
+
+    /// Why the passport isn't valid, a message for each field that is missing or fails its rule.
+    ///
+    /// # Examples from Tests
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use advent_of_code_2020::day_4::*;
+    /// let passport = &parse_passports("iyr:2019 hcl:#602927 eyr:1967 hgt:170cm").unwrap()[0];
+    ///
+    /// assert_eq!(
+    ///     vec!(
+    ///         "Missing required field 'byr'".to_string(),
+    ///         "Invalid eyr '1967'".to_string(),
+    ///         "Missing required field 'ecl'".to_string(),
+    ///         "Missing required field 'pid'".to_string(),
+    ///     ),
+    ///     Schema::default().validate(passport)
+    /// );
+    /// ```
+    pub fn validate(&self, passport: &Passport) -> Vec<String> {
+        self.fields.iter()
+            .filter_map(|field| match passport.get(&field.name) {
+                None if field.required => Some(format!("Missing required field '{}'", field.name)),
+                None => None,
+                Some(value) if !field.rule.accepts(value) => Some(format!("Invalid {} '{}'", field.name, value)),
+                Some(_) => None,
+            })
+            .collect()
+    }
+
+    /// Are all of the required fields present, and do all of the fields pass their rules?
+    pub fn is_valid(&self, passport: &Passport) -> bool {
+        self.validate(passport).is_empty()
+    }
+}
+
+/// Parse one line of a schema, e.g. `byr required year 1920-2002`.
+fn parse_field(line: &str) -> Result<FieldSchema> {
+    let tokens: Vec<(usize, &str)> = Regex::new(r"\S+").unwrap()
+        .find_iter(line)
+        .map(|token| (token.start() + 1, token.as_str()))
+        .collect();
+
+    let (name, required, (rule_column, rule), args) = match tokens.as_slice() {
+        [(_, name), required, rule, args @ ..] => (*name, *required, *rule, args),
+        [(_, name), required] if required.1 == "optional" || required.1 == "required" =>
+            return Err(Error::invalid(line, format!("Expected a rule for '{}'", name))),
+        _ => return Err(Error::invalid(line, "Expected a field, e.g. 'byr required year 1920-2002'")),
+    };
+
+    let required = match required {
+        (_, "required") => true,
+        (_, "optional") => false,
+        (column, other) => return Err(Error::at_column(
+            line,
+            column,
+            format!("Expected 'required' or 'optional', found '{}'", other),
+        )),
+    };
+
+    let expect_args = |min: usize, max: usize| {
+        if args.len() < min || args.len() > max {
+            let expected = if min == max { min.to_string() } else if max == usize::MAX {
+                format!("at least {}", min)
+            } else {
+                format!("{} to {}", min, max)
+            };
+            Err(Error::at_column(line, rule_column, format!("'{}' expects {} arguments", rule, expected)))
+        } else {
+            Ok(())
+        }
+    };
+
+    let rule = match rule {
+        "any" => {
+            expect_args(0, 0)?;
+            FieldRule::Any
+        }
+        "year" => {
+            expect_args(1, 1)?;
+            let (min, max) = parse_range(line, args[0])?;
+            FieldRule::Year { min, max }
         }
-        
-        let ecl_re = Regex::new(r"^(amb|blu|brn|gry|grn|hzl|oth)$").unwrap();
-        match self.ecl {
-            Some(ecl) if ecl_re.is_match(ecl) => (),
-            _ => return false
+        "measure" => {
+            expect_args(1, usize::MAX)?;
+            let units = args.iter()
+                .map(|&(column, arg)| match arg.split_once(':') {
+                    Some((unit, range)) if !unit.is_empty() => {
+                        let (min, max) = parse_range(line, (column + unit.len() + 1, range))?;
+                        Ok((unit.to_string(), min, max))
+                    }
+                    _ => Err(Error::at_column(line, column, format!("Expected '<unit>:<min>-<max>', found '{}'", arg))),
+                })
+                .collect::<Result<Vec<(String, u32, u32)>>>()?;
+            FieldRule::Measure(units)
         }
-        
-        let pid_re = Regex::new(r"^[0-9]{9}$").unwrap();
-        match self.pid {
-            Some(pid) if pid_re.is_match(pid) => (),
-            _ => return false
+        "pattern" => {
+            expect_args(1, usize::MAX)?;
+            let column = args[0].0;
+            let pattern = line[column - 1..].trim_end();
+            let re = Regex::new(&format!("^(?:{})$", pattern))
+                .map_err(|_| Error::at_column(line, column, format!("Invalid regex '{}'", pattern)))?;
+            FieldRule::Pattern(re)
         }
+        "one-of" => {
+            expect_args(1, usize::MAX)?;
+            FieldRule::OneOf(args.iter().map(|(_, arg)| arg.to_string()).collect())
+        }
+        other => return Err(Error::at_column(
+            line,
+            rule_column,
+            format!("Unknown rule '{}', expected any, year, measure, pattern or one-of", other),
+        )),
+    };
+
+    Ok(FieldSchema { name: name.to_string(), required, rule })
+}
+
+/// Parse an inclusive range, e.g. `1920-2002`, that starts at `column` of `line`.
+fn parse_range(line: &str, (column, range): (usize, &str)) -> Result<(u32, u32)> {
+    let (min_text, max_text) = range.split_once('-')
+        .ok_or_else(|| Error::at_column(line, column, format!("Expected a range '<min>-<max>', found '{}'", range)))?;
+    let min: u32 = parse_number(min_text, line, column)?;
+    let max: u32 = parse_number(max_text, line, column + min_text.len() + 1)?;
 
-        true
+    if max < min {
+        return Err(Error::at_column(line, column, format!("Range maximum is less than {}", min)));
     }
 
-    fn is_valid_year(year: &str, min: u16, max: u16) -> bool {
-        if Regex::new(r"^\d{4}$").unwrap().is_match(year) {
-            let as_num = year.parse::<u16>().unwrap();
-            return min <= as_num && as_num <= max
-        }
+    Ok((min, max))
+}
+
+/// The [`Solution`] for day 4, counts the valid passports.
+///
+/// - Both parts use the schema in [`SCHEMA_FILE`] if the input directory has one, otherwise the
+///   [`DEFAULT_SCHEMA`].
+#[derive(Debug, Default)]
+pub struct Day4 {
+    schema_path: Option<PathBuf>,
+}
+
+impl Day4 {
+    /// Always use the [`DEFAULT_SCHEMA`].
+    pub fn new() -> Day4 {
+        Day4::default()
+    }
 
-        false
+    /// Use the [`SCHEMA_FILE`] in `dir` if there is one, otherwise the [`DEFAULT_SCHEMA`].
+    pub fn in_dir<P: AsRef<Path>>(dir: P) -> Day4 {
+        Day4 { schema_path: Some(dir.as_ref().join(SCHEMA_FILE)) }
+    }
+
+    /// The schema to check passports against, loaded each time so that it can be edited between
+    /// runs.
+    fn schema(&self) -> Result<Schema> {
+        match &self.schema_path {
+            Some(path) if path.exists() => Schema::load(path),
+            _ => Ok(Schema::default()),
+        }
     }
 }
 
-pub fn run() {
-    let contents = fs::read_to_string("res/day-4-input").expect("Failed to read file");
-    let data = contents.as_str();
-    let count = parse_passports(data)
-        .iter()
-        .filter(|pass| pass.has_valid_fields())
-        .count();
+impl Solution for Day4 {
+    type Input<'a> = Vec<Passport<'a>>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-    println!("There are {} passports with 'valid' fields", count);
+    fn parse<'a>(&self, input: &'a str) -> Result<Vec<Passport<'a>>> {
+        parse_passports(input)
+    }
 
-    let count = parse_passports(data)
-        .iter()
-        .filter(|pass| pass.is_valid())
-        .count();
+    fn part_one(&self, passports: &Vec<Passport>) -> Result<usize> {
+        let schema = self.schema()?;
+        Ok(passports.iter().filter(|pass| schema.has_required_fields(pass)).count())
+    }
 
-    println!("There are {} 'valid' passports", count);
+    fn part_two(&self, passports: &Vec<Passport>) -> Result<usize> {
+        let schema = self.schema()?;
+        Ok(passports.iter().filter(|pass| schema.is_valid(pass)).count())
+    }
 }
 
-fn parse_passports<'a>(data: &'a str) -> Vec<Passport> {
+/// Parse the blank line separated passports from the puzzle input. Each field must be of the form
+/// `key:value`, any key is accepted as it is up to the [`Schema`] which fields matter, and the values
+/// are only checked by the schema.
+pub fn parse_passports<'a>(data: &'a str) -> Result<Vec<Passport<'a>>> {
     let mut passports: Vec<Passport> = Vec::new();
     let mut building: HashMap<&str, &'a str> = HashMap::new();
-    let re = Regex::new(r"([a-z]{3}):([^\s]+)").unwrap();
-    for line in data.lines() {
+    let re = Regex::new(r"\S+").unwrap();
+    let field_re = Regex::new(r"^([^:\s]+):(\S+)$").unwrap();
+    for (index, line) in data.lines().enumerate() {
         if line.is_empty() {
             passports.push(Passport::from_map(building.clone()));
             building = HashMap::new();
         } else {
-            for capture in re.captures_iter(line) {
+            for field in re.find_iter(line) {
+                let capture = field_re.captures(field.as_str()).ok_or_else(||
+                    Error::at_column(line, field.start() + 1, format!("Expected a 'key:value' field, found '{}'", field.as_str()))
+                        .on_line(index + 1, line)
+                )?;
                 building.insert(
                     capture.get(1).unwrap().as_str(),
                     capture.get(2).unwrap().as_str(),
@@ -157,12 +429,14 @@ fn parse_passports<'a>(data: &'a str) -> Vec<Passport> {
         passports.push(Passport::from_map(building));
     }
 
-    passports
+    Ok(passports)
 }
 
 #[cfg(test)]
 mod tests {
-    use day_4::{parse_passports, Passport};
+    use std::{env, fs};
+    use day_4::{parse_passports, Day4, FieldRule, Passport, Schema, DEFAULT_SCHEMA, SCHEMA_FILE};
+    use solution::{DaySolution, Part, Solution};
 
     static PART_1_DATA: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
 byr:1937 iyr:2017 cid:147 hgt:183cm
@@ -205,59 +479,64 @@ eyr:2022
 
 iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
 
+    fn passport(fields: &[(&'static str, &'static str)]) -> Passport<'static> {
+        Passport::from_map(fields.iter().copied().collect())
+    }
+
     #[test]
     fn can_parse_passports() {
         assert_eq!(
             vec!(
-                Passport {
-                    byr: Some("1937"),
-                    cid: Some("147"),
-                    ecl: Some("gry"),
-                    eyr: Some("2020"),
-                    hcl: Some("#fffffd"),
-                    hgt: Some("183cm"),
-                    iyr: Some("2017"),
-                    pid: Some("860033327"),
-                },
-                Passport {
-                    byr: Some("1929"),
-                    cid: Some("350"),
-                    ecl: Some("amb"),
-                    eyr: Some("2023"),
-                    hcl: Some("#cfa07d"),
-                    hgt: None,
-                    iyr: Some("2013"),
-                    pid: Some("028048884"),
-                },
-                Passport {
-                    byr: Some("1931"),
-                    cid: None,
-                    ecl: Some("brn"),
-                    eyr: Some("2024"),
-                    hcl: Some("#ae17e1"),
-                    hgt: Some("179cm"),
-                    iyr: Some("2013"),
-                    pid: Some("760753108"),
-                },
-                Passport {
-                    byr: None,
-                    cid: None,
-                    ecl: Some("brn"),
-                    eyr: Some("2025"),
-                    hcl: Some("#cfa07d"),
-                    hgt: Some("59in"),
-                    iyr: Some("2011"),
-                    pid: Some("166559648"),
-                },
+                passport(&[
+                    ("byr", "1937"),
+                    ("cid", "147"),
+                    ("ecl", "gry"),
+                    ("eyr", "2020"),
+                    ("hcl", "#fffffd"),
+                    ("hgt", "183cm"),
+                    ("iyr", "2017"),
+                    ("pid", "860033327"),
+                ]),
+                passport(&[
+                    ("byr", "1929"),
+                    ("cid", "350"),
+                    ("ecl", "amb"),
+                    ("eyr", "2023"),
+                    ("hcl", "#cfa07d"),
+                    ("iyr", "2013"),
+                    ("pid", "028048884"),
+                ]),
+                passport(&[
+                    ("byr", "1931"),
+                    ("ecl", "brn"),
+                    ("eyr", "2024"),
+                    ("hcl", "#ae17e1"),
+                    ("hgt", "179cm"),
+                    ("iyr", "2013"),
+                    ("pid", "760753108"),
+                ]),
+                passport(&[
+                    ("ecl", "brn"),
+                    ("eyr", "2025"),
+                    ("hcl", "#cfa07d"),
+                    ("hgt", "59in"),
+                    ("iyr", "2011"),
+                    ("pid", "166559648"),
+                ]),
             ),
-            parse_passports(PART_1_DATA)
-        )
+            parse_passports(PART_1_DATA).unwrap()
+        );
+
+        let err = parse_passports("ecl:gry pid:860033327\nbyr:1937 hcl #fffffd").unwrap_err();
+        assert_eq!((Some(2), Some(10)), (err.line, err.column));
+        let err = parse_passports("ecl:gry :860033327").unwrap_err();
+        assert_eq!((Some(1), Some(9)), (err.line, err.column));
     }
 
     #[test]
     fn can_validate_passport_fields() {
         let invalids: Vec<bool> =
-            parse_passports(PART_2_INVALID)
+            parse_passports(PART_2_INVALID).unwrap()
                 .iter()
                 .map(|pass| pass.is_valid())
                 .collect();
@@ -268,7 +547,7 @@ iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
         );
 
         let valids: Vec<bool> =
-            parse_passports(PART_2_VALID)
+            parse_passports(PART_2_VALID).unwrap()
                 .iter()
                 .map(|pass| pass.is_valid())
                 .collect();
@@ -278,4 +557,131 @@ iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
             valids
         )
     }
+
+    #[test]
+    fn can_check_required_fields() {
+        let passports = parse_passports(PART_1_DATA).unwrap();
+        let schema = Schema::default();
+
+        assert_eq!(
+            vec!(true, false, true, false),
+            passports.iter().map(|pass| schema.has_required_fields(pass)).collect::<Vec<bool>>()
+        );
+        assert_eq!(
+            vec!(true, false, true, false),
+            passports.iter().map(|pass| pass.has_valid_fields()).collect::<Vec<bool>>()
+        );
+    }
+
+    #[test]
+    fn can_apply_field_rules() {
+        let schema = Schema::default();
+        let rule = |name: &str| &schema.fields.iter().find(|field| field.name == name).unwrap().rule;
+
+        assert!(rule("byr").accepts("2002"));
+        assert!(!rule("byr").accepts("2003"));
+        assert!(!rule("byr").accepts("02002"));
+        assert!(rule("hgt").accepts("60in"));
+        assert!(rule("hgt").accepts("190cm"));
+        assert!(!rule("hgt").accepts("190in"));
+        assert!(!rule("hgt").accepts("190"));
+        assert!(!rule("hgt").accepts("cm"));
+        assert!(rule("hcl").accepts("#123abc"));
+        assert!(!rule("hcl").accepts("#123abz"));
+        assert!(!rule("hcl").accepts("123abc"));
+        assert!(rule("ecl").accepts("brn"));
+        assert!(!rule("ecl").accepts("wat"));
+        assert!(rule("pid").accepts("000000001"));
+        assert!(!rule("pid").accepts("0123456789"));
+        assert!(rule("cid").accepts("anything"));
+
+        assert!(FieldRule::OneOf(vec!("a b".to_string())).accepts("a b"));
+    }
+
+    #[test]
+    fn can_use_custom_schema() {
+        let passports = parse_passports("pid:012345678 hgt:5ft\n\npid:012345678 hgt:190cm\n\nhgt:6ft").unwrap();
+        let schema = Schema::parse("
+# only the passport id is needed, heights in feet are allowed
+pid required pattern [0-9]{9}|x
+hgt optional measure ft:4-7 cm:120-220
+").unwrap();
+
+        assert_eq!(
+            vec!(true, true, false),
+            passports.iter().map(|pass| schema.is_valid(pass)).collect::<Vec<bool>>()
+        );
+        assert_eq!(vec!("Missing required field 'pid'".to_string()), schema.validate(&passports[2]));
+        assert!(schema.is_valid(&passport(&[("pid", "x")])));
+        assert!(!schema.is_valid(&passport(&[("pid", "x"), ("hgt", "3ft")])));
+
+        let passports = parse_passports("email:a@b.com hgt2:190cm pid:1\n\nEmail:x@y.z hgt2:6ft").unwrap();
+        let schema = Schema::parse("email required pattern \\S+@\\S+\nhgt2 optional measure cm:150-193").unwrap();
+        assert_eq!(Some("190cm"), passports[0].get("hgt2"));
+        assert_eq!(
+            vec!(true, false),
+            passports.iter().map(|pass| schema.is_valid(pass)).collect::<Vec<bool>>()
+        );
+
+        let spaced = Schema::parse("nam required pattern [a-z]+ [a-z]+  ").unwrap();
+        assert!(spaced.is_valid(&passport(&[("nam", "jeff horton")])));
+        assert!(!spaced.is_valid(&passport(&[("nam", "jeff")])));
+    }
+
+    #[test]
+    fn can_load_schema() {
+        let path = env::temp_dir().join(format!("day-4-schema-{}", std::process::id()));
+        fs::write(&path, DEFAULT_SCHEMA).unwrap();
+        let schema = Schema::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(8, schema.fields.len());
+        assert_eq!(
+            vec!(true, true, true, true),
+            parse_passports(PART_2_VALID).unwrap().iter().map(|pass| schema.is_valid(pass)).collect::<Vec<bool>>()
+        );
+
+        assert!(Schema::load(&path).is_err());
+    }
+
+    #[test]
+    fn can_run_with_schema_file() {
+        let dir = env::temp_dir().join(format!("day-4-schemas-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let day = Day4::in_dir(&dir);
+        let passports = parse_passports(PART_2_VALID).unwrap();
+
+        assert_eq!(Ok(4), day.part_two(&passports));
+
+        fs::write(dir.join(SCHEMA_FILE), "pid required pattern 0[0-9]{8}\nhgt required measure cm:150-193").unwrap();
+        let custom = day.run(PART_2_VALID, &[Part::One, Part::Two]);
+
+        fs::write(dir.join(SCHEMA_FILE), "pid required pattern [").unwrap();
+        let invalid = day.part_two(&passports);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(Ok(vec!((Part::One, "4".to_string()), (Part::Two, "1".to_string()))), custom);
+        assert_eq!(Some(1), invalid.unwrap_err().line);
+        assert_eq!(Ok(4), day.part_two(&passports));
+    }
+
+    #[test]
+    fn rejects_invalid_schemas() {
+        let error_at = |schema: &str| {
+            let err = Schema::parse(schema).unwrap_err();
+            (err.line, err.column)
+        };
+
+        assert_eq!((Some(1), None), error_at("byr"));
+        assert_eq!((Some(2), Some(5)), error_at("byr required any\nbyr needed any"));
+        assert_eq!((Some(1), Some(14)), error_at("byr required yearly 1920-2002"));
+        assert_eq!((Some(1), Some(14)), error_at("byr required year"));
+        assert_eq!((Some(1), Some(24)), error_at("byr required year 1920-20x2"));
+        assert_eq!((Some(1), Some(19)), error_at("byr required year 2002-1920"));
+        assert_eq!((Some(1), Some(29)), error_at("hgt required measure cm:1-2 :3-4"));
+        assert_eq!((Some(1), Some(22)), error_at("hcl required pattern #[0-9"));
+        assert_eq!((Some(1), Some(14)), error_at("ecl required one-of"));
+        assert_eq!((Some(3), Some(14)), error_at("\n# comment\ncid optional any thing"));
+        assert_eq!((Some(3), Some(3)), error_at("byr required any\ncid optional any\n  byr optional year 1920-2002"));
+    }
 }
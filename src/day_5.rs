@@ -1,49 +1,68 @@
-use std::fs;
+//! This is my solution for [Advent of Code - Day 5](https://adventofcode.com/2020/day/5) -
+//! _Binary Boarding_
+
 use std::collections::HashSet;
+use error::{parse_lines, Error, Result};
+use solution::Solution;
 
+/// A boarding pass, where the seat id is the binary space partitioning code read as a binary number.
 #[derive(Debug, Eq, PartialEq)]
-struct Seat { id: usize }
+pub struct Seat { pub id: usize }
 
 impl Seat {
-    fn from_line(line: &str) -> Seat {
-        Seat {
-            id: line.chars().fold(
-                0,
-                |acc, char| (acc << 1) | match char {
-                    'F' | 'L' => 0b0,
-                    'B' | 'R' => 0b1,
-                    unexpected => panic!("Unexpected input char {}", unexpected)
-                },
-            )
+    /// Decode a boarding pass, e.g. `FBFBBFFRLR`.
+    pub fn from_line(line: &str) -> Result<Seat> {
+        if line.len() != 10 {
+            return Err(Error::invalid(line, "Expected a 10 character boarding pass"));
         }
+
+        let id = line.chars().enumerate().try_fold(
+            0,
+            |acc, (index, char)| match char {
+                'F' | 'L' => Ok(acc << 1),
+                'B' | 'R' => Ok((acc << 1) | 0b1),
+                unexpected => Err(Error::at_column(line, index + 1, format!("Unexpected input char {}", unexpected)))
+            },
+        )?;
+
+        Ok(Seat { id })
     }
 
-    #[allow(dead_code)] // used only by tests
-    fn row(&self) -> usize {
+    /// The row of the seat on the plane.
+    pub fn row(&self) -> usize {
         self.id >> 3
     }
 
-    #[allow(dead_code)] // used only by tests
-    fn column(&self) -> usize {
+    /// The column of the seat within its row.
+    pub fn column(&self) -> usize {
         self.id & 7
     }
 }
 
-pub fn run() {
-    let contents = fs::read_to_string("res/day-5-input").expect("Failed to read file");
-    let allocated_ids: HashSet<usize> =
-        contents.lines()
-            .map(|line| Seat::from_line(line))
-            .map(|seat| seat.id).collect();
+/// The [`Solution`] for day 5, finds the highest seat id and your seat.
+pub struct Day5;
 
-    let max_id = allocated_ids.iter().max().unwrap();
-    println!("Max Seat ID: {} ", max_id);
+impl Solution for Day5 {
+    type Input<'a> = HashSet<usize>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(&self, input: &str) -> Result<HashSet<usize>> {
+        let seats = parse_lines(input, Seat::from_line)?;
+        Ok(seats.into_iter().map(|seat| seat.id).collect())
+    }
 
-    let seat_id = find_seat(&allocated_ids).unwrap();
-    println!("My Seat ID: {} ", seat_id);
+    fn part_one(&self, allocated_ids: &HashSet<usize>) -> Result<usize> {
+        allocated_ids.iter().max().copied().ok_or_else(|| Error::new("No seats allocated"))
+    }
+
+    fn part_two(&self, allocated_ids: &HashSet<usize>) -> Result<usize> {
+        find_seat(allocated_ids).ok_or_else(|| Error::new("Failed to find an empty seat"))
+    }
 }
 
-fn find_seat(allocated_ids: &HashSet<usize>) -> Option<usize> {
+/// Find the missing seat id where the seats either side of it are allocated.
+pub fn find_seat(allocated_ids: &HashSet<usize>) -> Option<usize> {
     for i in 1..(1 << 11) as usize {
         if !allocated_ids.contains(&i) && allocated_ids.contains(&(i - 1)) && allocated_ids.contains(&(i + 1)) {
             return Some(i)
@@ -59,26 +78,29 @@ mod tests {
 
     #[test]
     fn can_parse_seat_code() {
-        assert_eq!(Seat { id: 357 }, Seat::from_line("FBFBBFFRLR"));
-        assert_eq!(Seat { id: 567 }, Seat::from_line("BFFFBBFRRR"));
-        assert_eq!(Seat { id: 119 }, Seat::from_line("FFFBBBFRRR"));
-        assert_eq!(Seat { id: 820 }, Seat::from_line("BBFFBBFRLL"));
+        assert_eq!(Ok(Seat { id: 357 }), Seat::from_line("FBFBBFFRLR"));
+        assert_eq!(Ok(Seat { id: 567 }), Seat::from_line("BFFFBBFRRR"));
+        assert_eq!(Ok(Seat { id: 119 }), Seat::from_line("FFFBBBFRRR"));
+        assert_eq!(Ok(Seat { id: 820 }), Seat::from_line("BBFFBBFRLL"));
+
+        assert_eq!(Some(4), Seat::from_line("FBFXBFFRLR").unwrap_err().column);
+        assert!(Seat::from_line("FBFBBFFRL").is_err());
     }
 
     #[test]
     fn can_extract_row() {
-        assert_eq!(44, Seat::from_line("FBFBBFFRLR").row());
-        assert_eq!(70, Seat::from_line("BFFFBBFRRR").row());
-        assert_eq!(14, Seat::from_line("FFFBBBFRRR").row());
-        assert_eq!(102, Seat::from_line("BBFFBBFRLL").row());
+        assert_eq!(44, Seat::from_line("FBFBBFFRLR").unwrap().row());
+        assert_eq!(70, Seat::from_line("BFFFBBFRRR").unwrap().row());
+        assert_eq!(14, Seat::from_line("FFFBBBFRRR").unwrap().row());
+        assert_eq!(102, Seat::from_line("BBFFBBFRLL").unwrap().row());
     }
 
     #[test]
     fn can_extract_column() {
-        assert_eq!(5, Seat::from_line("FBFBBFFRLR").column());
-        assert_eq!(7, Seat::from_line("BFFFBBFRRR").column());
-        assert_eq!(7, Seat::from_line("FFFBBBFRRR").column());
-        assert_eq!(4, Seat::from_line("BBFFBBFRLL").column());
+        assert_eq!(5, Seat::from_line("FBFBBFFRLR").unwrap().column());
+        assert_eq!(7, Seat::from_line("BFFFBBFRRR").unwrap().column());
+        assert_eq!(7, Seat::from_line("FFFBBBFRRR").unwrap().column());
+        assert_eq!(4, Seat::from_line("BBFFBBFRLL").unwrap().column());
     }
 
     #[test]
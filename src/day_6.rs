@@ -1,33 +1,63 @@
-use std::fs;
+//! This is my solution for [Advent of Code - Day 6](https://adventofcode.com/2020/day/6) -
+//! _Custom Customs_
+
 use std::collections::HashSet;
 use std::hash::Hash;
+use error::{parse_lines, Error, Result};
+use solution::Solution;
+
+/// The [`Solution`] for day 6, sums the answers given by each group.
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input<'a> = &'a str;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-pub fn run() {
-    let contents = fs::read_to_string("res/day-6-input").expect("Failed to read file");
+    fn parse<'a>(&self, input: &'a str) -> Result<&'a str> {
+        parse_lines(input, parse_answers)?;
+        Ok(input)
+    }
 
-    let union_groups = sum_counts(&parse_union_groups(contents.as_str()));
-    println!("Sum of union group counts: {}", union_groups);
+    fn part_one(&self, input: &&str) -> Result<usize> {
+        Ok(sum_counts(&parse_union_groups(input)))
+    }
 
-    let intersect_groups = sum_counts(&parse_intersect_groups(contents.as_str()));
-    println!("Sum of intersect group counts: {}", intersect_groups);
+    fn part_two(&self, input: &&str) -> Result<usize> {
+        Ok(sum_counts(&parse_intersect_groups(input)))
+    }
 }
 
-fn parse_union_groups(input: &str) -> Vec<HashSet<char>> {
-    input.split("\n\n").into_iter().map(|str| union_group_from_lines(str)).collect()
+/// Parse one person's answers, the questions are labelled `a` to `z`. Blank lines separate groups
+/// so are allowed, and have no answers.
+pub fn parse_answers(line: &str) -> Result<HashSet<char>> {
+    line.chars()
+        .enumerate()
+        .map(|(index, chr)| match chr {
+            'a'..='z' => Ok(chr),
+            other => Err(Error::at_column(line, index + 1, format!("Invalid question '{}'", other))),
+        })
+        .collect()
+}
+
+/// Part one, the questions anyone in each group answered yes to.
+pub fn parse_union_groups(input: &str) -> Vec<HashSet<char>> {
+    input.split("\n\n").map(union_group_from_lines).collect()
 }
 
 fn union_group_from_lines(lines: &str) -> HashSet<char> {
     let mut group = HashSet::new();
 
     lines.chars()
-        .filter(|chr| ('a'..='z').contains(chr))
+        .filter(|chr: &char| chr.is_ascii_lowercase())
         .for_each(|chr| { group.insert(chr); });
 
     group
 }
 
-fn parse_intersect_groups(input: &str) -> Vec<HashSet<char>> {
-    input.split("\n\n").into_iter().map(|str| intersect_group_from_lines(str)).collect()
+/// Part two, the questions everyone in each group answered yes to.
+pub fn parse_intersect_groups(input: &str) -> Vec<HashSet<char>> {
+    input.split("\n\n").map(intersect_group_from_lines).collect()
 }
 
 fn intersect<T: Hash + Eq + Copy>(a: HashSet<T>, b: HashSet<T>) -> HashSet<T> {
@@ -39,8 +69,8 @@ fn intersect<T: Hash + Eq + Copy>(a: HashSet<T>, b: HashSet<T>) -> HashSet<T> {
 }
 
 fn intersect_group_from_lines(lines: &str) -> HashSet<char> {
-    let mut iter = lines.lines().map(|line| union_group_from_lines(line)).into_iter();
-    let mut group = iter.next().unwrap();
+    let mut iter = lines.lines().map(union_group_from_lines);
+    let mut group = iter.next().unwrap_or_default();
     for i in iter {
         group = intersect(group, i)
     }
@@ -48,14 +78,15 @@ fn intersect_group_from_lines(lines: &str) -> HashSet<char> {
     group
 }
 
-fn sum_counts(groups: &Vec<HashSet<char>>) -> usize {
+/// Sum the number of questions answered in each group.
+pub fn sum_counts(groups: &[HashSet<char>]) -> usize {
     groups.iter().map(|group| group.len()).sum()
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
-    use day_6::{union_group_from_lines, parse_union_groups, sum_counts, intersect_group_from_lines, parse_intersect_groups};
+    use day_6::{union_group_from_lines, parse_union_groups, sum_counts, intersect_group_from_lines, parse_intersect_groups, parse_answers};
 
     //noinspection SpellCheckingInspection
     #[test]
@@ -71,6 +102,13 @@ abcz";
         );
     }
 
+    #[test]
+    fn can_parse_answers() {
+        assert_eq!(Ok(vec!('a', 'b', 'c').into_iter().collect()), parse_answers("abc"));
+        assert_eq!(Ok(HashSet::new()), parse_answers(""));
+        assert_eq!(Some(3), parse_answers("abC").unwrap_err().column);
+    }
+
     #[test]
     fn can_parse_and_count_union_groups() {
         let input = "abc
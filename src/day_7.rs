@@ -1,56 +1,94 @@
-use std::fs;
+//! This is my solution for [Advent of Code - Day 7](https://adventofcode.com/2020/day/7) -
+//! _Handy Haversacks_
+
 use regex::Regex;
 use std::collections::{HashMap, HashSet, LinkedList};
+use error::{parse_lines, parse_number, Error, Result};
+use solution::Solution;
 
-type Label = str;
+/// The colour of a bag, e.g. `shiny gold`.
+pub type Label = str;
 
+/// A rule listing how many of each other bag a bag must contain.
 #[derive(Debug, Eq, PartialEq)]
-struct Rule<'a> {
-    label: &'a Label,
-    contents: HashMap<&'a Label, usize>,
+pub struct Rule<'a> {
+    pub label: &'a Label,
+    pub contents: HashMap<&'a Label, usize>,
 }
 
 impl<'a> Rule<'a> {
-    fn from_line(line: &'a str) -> Rule<'a> {
-        let parts: Vec<&str> = line.split(" bags contain ").into_iter().collect();
-        let &bag = parts.get(0).unwrap();
-        let &content_str = parts.get(1).unwrap();
-        let re = Regex::new(r"(\d+) ([a-z]+ [a-z]+)").unwrap();
+    /// Parse a rule, e.g. `light red bags contain 1 bright white bag, 2 muted yellow bags.`
+    pub fn from_line(line: &'a str) -> Result<Rule<'a>> {
+        let separator = " bags contain ";
+        let split = line.find(separator)
+            .ok_or_else(|| Error::invalid(line, "Expected '<colour> bags contain <contents>'"))?;
+        let bag = &line[..split];
+        let contents_start = split + separator.len();
+        let content_str = &line[contents_start..];
+        let re = Regex::new(r"(\d+) ([a-z]+ [a-z]+) bags?[,.]").unwrap();
 
         let contents: HashMap<&'a Label, usize> = match content_str {
             "no other bags." => HashMap::new(),
             str => {
                 let mut map = HashMap::new();
-                re.captures_iter(str).for_each(|cap| {
+                // Each item must immediately follow the previous one to catch anything unexpected
+                let mut expected_start = 0;
+                for cap in re.captures_iter(str) {
+                    let item = cap.get(0).unwrap();
+                    if item.start() != expected_start {
+                        break;
+                    }
+
+                    let count = cap.get(1).unwrap();
                     map.insert(
                         cap.get(2).unwrap().as_str(),
-                        cap.get(1).unwrap().as_str().parse::<usize>().unwrap(),
+                        parse_number(count.as_str(), line, contents_start + count.start() + 1)?,
                     );
-                });
+                    expected_start = item.end() + 1;
+                }
+
+                if expected_start != str.len() + 1 || !str.ends_with('.') {
+                    return Err(Error::at_column(
+                        line,
+                        contents_start + expected_start + 1,
+                        "Expected '<count> <colour> bags' or 'no other bags.'",
+                    ));
+                }
 
                 map
             }
         };
 
-        Rule {
+        Ok(Rule {
             label: bag,
             contents,
-        }
+        })
     }
 }
 
-pub fn run() {
-    let contents = fs::read_to_string("res/day-7-input").expect("Failed to read file");
-    let rules = contents.lines().map(|line| Rule::from_line(line)).into_iter().collect();
+/// The [`Solution`] for day 7, works out which bags can hold, and must be held by, a shiny gold bag.
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input<'a> = Vec<Rule<'a>>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-    let containers = find_all_containers(&rules, "shiny gold");
-    println!("There are {} possible containers.", containers.len());
+    fn parse<'a>(&self, input: &'a str) -> Result<Vec<Rule<'a>>> {
+        parse_lines(input, Rule::from_line)
+    }
+
+    fn part_one(&self, rules: &Vec<Rule>) -> Result<usize> {
+        Ok(find_all_containers(rules, "shiny gold").len())
+    }
 
-    let count = count_bag_contents(&rules, "shiny gold");
-    println!("There are {} bags in a shiny gold bag.", count);
+    fn part_two(&self, rules: &Vec<Rule>) -> Result<usize> {
+        Ok(count_bag_contents(rules, "shiny gold"))
+    }
 }
 
-fn build_direct_containers<'a>(rules: &Vec<Rule<'a>>) -> HashMap<&'a Label, HashSet<&'a Label>> {
+/// Invert the rules, mapping each bag to the bags that can directly contain it.
+pub fn build_direct_containers<'a>(rules: &Vec<Rule<'a>>) -> HashMap<&'a Label, HashSet<&'a Label>> {
     let mut parent_map = HashMap::new();
 
     rules.iter()
@@ -67,7 +105,8 @@ fn build_direct_containers<'a>(rules: &Vec<Rule<'a>>) -> HashMap<&'a Label, Hash
 }
 
 
-fn find_all_containers<'a>(rules: &Vec<Rule<'a>>, seed: &'a Label) -> HashSet<&'a Label> {
+/// Part one, find all of the bags that can eventually contain the `seed` bag.
+pub fn find_all_containers<'a>(rules: &Vec<Rule<'a>>, seed: &'a Label) -> HashSet<&'a Label> {
     let direct_containers = build_direct_containers(rules);
 
     let mut possible_containers: HashSet<&Label> = HashSet::new();
@@ -94,7 +133,8 @@ fn find_all_containers<'a>(rules: &Vec<Rule<'a>>, seed: &'a Label) -> HashSet<&'
     possible_containers
 }
 
-fn count_bag_contents(rules: &Vec<Rule>, outer_bag: &Label) -> usize {
+/// Part two, count how many bags are required inside the `outer_bag`.
+pub fn count_bag_contents(rules: &Vec<Rule>, outer_bag: &Label) -> usize {
     let mut rule_map: HashMap<&Label, Vec<(&Label, usize)>> = HashMap::new();
     rules.iter().for_each(
         |rule| {
@@ -196,11 +236,17 @@ mod tests {
     fn can_parse_rule() {
         assert_eq!(
             Rule::from_line("light red bags contain 1 bright white bag, 2 muted yellow bags."),
-            Rule {
+            Ok(Rule {
                 label: "light red",
                 contents: map!("bright white" => 1usize, "muted yellow" => 2usize),
-            }
-        )
+            })
+        );
+
+        assert!(Rule::from_line("light red bags hold 1 bright white bag.").is_err());
+        assert_eq!(
+            Some(44),
+            Rule::from_line("light red bags contain 1 bright white bag, two muted yellow bags.").unwrap_err().column
+        );
     }
 
     #[test]
@@ -219,7 +265,7 @@ dotted black bags contain no other bags.";
 
         assert_eq!(
             expected,
-            input.lines().map(|line| Rule::from_line(line)).into_iter().collect::<Vec<Rule>>()
+            input.lines().map(Rule::from_line).collect::<Result<Vec<Rule>, _>>().unwrap()
         )
     }
 
@@ -258,7 +304,7 @@ dark green bags contain 2 dark blue bags.
 dark blue bags contain 2 dark violet bags.
 dark violet bags contain no other bags.";
 
-        let rainbow_rules = input.lines().map(|line| Rule::from_line(line)).into_iter().collect::<Vec<Rule>>();
+        let rainbow_rules = input.lines().map(Rule::from_line).collect::<Result<Vec<Rule>, _>>().unwrap();
 
         assert_eq!(0, count_bag_contents(&small_rules(), "shiny gold"));
         assert_eq!(4, count_bag_contents(&small_rules(), "light red"));
@@ -1,49 +1,80 @@
-use std::fs;
+//! This is my solution for [Advent of Code - Day 8](https://adventofcode.com/2020/day/8) -
+//! _Handheld Halting_
+
 use regex::Regex;
 use day_8::Instruction::*;
 use std::collections::HashSet;
 use day_8::ProgramResult::*;
 use im::Vector;
+use error::{self, parse_number, Error, Result};
+use solution::Solution;
 
+/// An instruction in the handheld's boot code.
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Eq, PartialEq, Clone)]
-enum Instruction {
+pub enum Instruction {
     ACC(isize),
     JMP(isize),
     NOP(isize),
 }
 
+/// How a program finished, along with the value of the accumulator at that point.
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Eq, PartialEq)]
-enum ProgramResult {
+pub enum ProgramResult {
     INFINITE(isize),
     COMPLETE(isize),
 }
 
-pub fn run() {
-    let contents = fs::read_to_string("res/day-8-input").expect("Failed to read file");
-    let program = parse_lines(contents.as_str());
+/// The [`Solution`] for day 8, finds the infinite loop in the boot code, and then fixes it.
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input<'a> = Vector<Instruction>;
+    type PartOne = isize;
+    type PartTwo = isize;
 
-    let original_result = run_program(&program);
-    println!("Original result = {:?}", original_result);
+    fn parse(&self, input: &str) -> Result<Vector<Instruction>> {
+        parse_lines(input)
+    }
 
-    let fixed_result = find_finite_program(&program);
-    println!("Fixed result = {:?}", fixed_result);
+    fn part_one(&self, program: &Vector<Instruction>) -> Result<isize> {
+        match run_program(program)? {
+            INFINITE(acc) => Ok(acc),
+            COMPLETE(acc) => Ok(acc),
+        }
+    }
+
+    fn part_two(&self, program: &Vector<Instruction>) -> Result<isize> {
+        find_finite_program(program).ok_or_else(|| Error::new("Failed to find a finite program"))
+    }
 }
 
-fn parse_lines(input: &str) -> Vector<Instruction> {
-    let re = Regex::new(r"(acc|jmp|nop) ([+-]\d+)").unwrap();
-
-    input.lines()
-        .flat_map(|line| re.captures(line))
-        .map(|cap| match (cap.get(1).unwrap().as_str(), cap.get(2).unwrap().as_str().parse::<isize>().unwrap()) {
-            ("acc", v) => ACC(v),
-            ("jmp", v) => JMP(v),
-            ("nop", v) => NOP(v),
-            _ => panic!("unexpected instruction '{}'", cap.get(0).unwrap().as_str())
-        })
-        .collect()
+/// Parse the boot code, one instruction per line, e.g. `jmp -3`.
+pub fn parse_lines(input: &str) -> Result<Vector<Instruction>> {
+    let re = Regex::new(r"^(\w+) ([+-]\d+)$").unwrap();
+
+    let instructions = error::parse_lines(input, |line| {
+        let cap = re.captures(line)
+            .ok_or_else(|| Error::invalid(line, "Expected an operation and argument, e.g. 'jmp -3'"))?;
+        let arg = cap.get(2).unwrap();
+        let value = parse_number(arg.as_str(), line, arg.start() + 1)?;
+
+        match cap.get(1).unwrap().as_str() {
+            "acc" => Ok(ACC(value)),
+            "jmp" => Ok(JMP(value)),
+            "nop" => Ok(NOP(value)),
+            op => Err(Error::at_column(line, 1, format!("Unexpected operation '{}'", op))),
+        }
+    })?;
+
+    Ok(instructions.into_iter().collect())
 }
 
-fn run_program(program: &Vector<Instruction>) -> ProgramResult {
+/// Run a program until it completes, or an instruction is about to be run a second time. It is an
+/// error for the program to jump outside of itself, other than to the instruction just after the
+/// end, which completes the program.
+pub fn run_program(program: &Vector<Instruction>) -> Result<ProgramResult> {
     let mut visited: HashSet<usize> = HashSet::new();
     let mut pos: usize = 0;
     let mut acc: isize = 0;
@@ -52,33 +83,41 @@ fn run_program(program: &Vector<Instruction>) -> ProgramResult {
         visited.insert(pos);
         if pos == program.len()
         {
-            return COMPLETE(acc);
+            return Ok(COMPLETE(acc));
         }
         match program.get(pos) {
             Some(ACC(v)) => {
-                acc = acc + v;
-                pos = pos + 1;
+                acc += v;
+                pos += 1;
             },
-            Some(JMP(v)) => pos = (pos as isize + v) as usize,
-            Some(NOP(_)) => pos = pos + 1,
-            None => panic!("No instruction at position {}", pos)
+            Some(JMP(v)) => {
+                let target = pos as isize + v;
+                if target < 0 {
+                    return Err(Error::new(format!("Instruction {} jumps to {}, before the start of the program", pos, target)));
+                }
+                pos = target as usize
+            },
+            Some(NOP(_)) => pos += 1,
+            None => return Err(Error::new(format!("No instruction at position {}", pos)))
         }
     }
 
-    INFINITE(acc)
+    Ok(INFINITE(acc))
 }
 
-fn find_finite_program(program: &Vector<Instruction>) -> Option<isize> {
+/// Part two, swap each `jmp` or `nop` in turn until the program completes, returning the
+/// accumulator for the first program that does. Swaps that cause the program to jump outside of
+/// itself are skipped.
+pub fn find_finite_program(program: &Vector<Instruction>) -> Option<isize> {
     for i in 0..program.len() {
         let result = match program.get(i) {
             Some(JMP(v)) => run_program(&program.update(i, NOP(*v))),
             Some(NOP(v)) => run_program(&program.update(i, JMP(*v))),
-            _ => INFINITE(0)
+            _ => Ok(INFINITE(0))
         };
 
-        match result {
-            INFINITE(_) => (),
-            COMPLETE(v) => return Some(v),
+        if let Ok(COMPLETE(v)) = result {
+            return Some(v);
         }
     }
 
@@ -107,15 +146,18 @@ acc +6"
     #[test]
     fn can_parse() {
         assert_eq!(
-            vector!(NOP(0), ACC(1), JMP(4), ACC(3), JMP(-3), ACC(-99), ACC(1), JMP(-4), ACC(6)),
+            Ok(vector!(NOP(0), ACC(1), JMP(4), ACC(3), JMP(-3), ACC(-99), ACC(1), JMP(-4), ACC(6))),
             parse_lines(get_input())
-        )
+        );
+
+        let err = parse_lines("nop +0\nacc +1\njpm +4").unwrap_err();
+        assert_eq!((Some(3), Some(1)), (err.line, err.column));
     }
 
     #[test]
     fn can_run_infinite_program() {
         assert_eq!(
-            INFINITE(5),
+            Ok(INFINITE(5)),
             run_program(&vector!(NOP(0), ACC(1), JMP(4), ACC(3), JMP(-3), ACC(-99), ACC(1), JMP(-4), ACC(6)))
         )
     }
@@ -123,9 +165,12 @@ acc +6"
     #[test]
     fn can_run_finite_program() {
         assert_eq!(
-            COMPLETE(8),
+            Ok(COMPLETE(8)),
             run_program(&vector!(NOP(0), ACC(1), JMP(4), ACC(3), JMP(-3), ACC(-99), ACC(1), NOP(-4), ACC(6)))
-        )
+        );
+
+        assert!(run_program(&vector!(NOP(0), JMP(-2))).is_err());
+        assert!(run_program(&vector!(NOP(0), JMP(3))).is_err());
     }
 
     #[test]
@@ -1,28 +1,45 @@
+//! This is my solution for [Advent of Code - Day 9](https://adventofcode.com/2020/day/9) -
+//! _Encoding Error_
+
 use std::collections::{LinkedList, HashSet};
-use std::fs;
 use im::vector::Vector;
 use std::ops::Add;
+use error::{parse_lines, parse_number, Error, Result};
+use solution::Solution;
+
+
+/// The [`Solution`] for day 9, finds the weakness in the XMAS encryption.
+pub struct Day9;
 
+impl Solution for Day9 {
+    type Input<'a> = Vec<usize>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-pub fn run() {
-    let contents = fs::read_to_string("res/day-9-input").expect("Failed to read file");
-    let input = contents.lines().map(|line| line.parse::<usize>().unwrap()).collect();
+    fn parse(&self, input: &str) -> Result<Vec<usize>> {
+        parse_lines(input, |line| parse_number(line, line, 1))
+    }
 
-    let result = find_first_invalid(&input, 25).unwrap();
-    println!("First invalid number is: {}", result);
+    fn part_one(&self, input: &Vec<usize>) -> Result<usize> {
+        find_first_invalid(input, 25).ok_or_else(|| Error::new("Failed to find an invalid number"))
+    }
 
-    let weakness = find_weakness(&input, result).unwrap();
-    println!("Encryption weakness: {}", weakness);
+    fn part_two(&self, input: &Vec<usize>) -> Result<usize> {
+        // part 2 needs the result from part 1
+        let invalid = self.part_one(input)?;
+        find_weakness(input, invalid).ok_or_else(|| Error::new("Failed to find the encryption weakness"))
+    }
 }
 
-fn find_first_invalid(input: &Vec<usize>, preamble: usize) -> Option<usize> {
+/// Part one, find the first number that is not the sum of two of the `preamble` numbers before it.
+pub fn find_first_invalid(input: &Vec<usize>, preamble: usize) -> Option<usize> {
     let mut cache: LinkedList<(usize, HashSet<usize>)> = LinkedList::new();
     for i in input {
         // if preamble used up, cache is full, check next number and then remove earliest
         if cache.len() == preamble {
             let mut found = false;
             for (_, sums) in &cache {
-                if sums.contains(&i) {
+                if sums.contains(i) {
                     found = true;
                     break
                 }
@@ -49,7 +66,9 @@ fn find_first_invalid(input: &Vec<usize>, preamble: usize) -> Option<usize> {
     None
 }
 
-fn find_weakness(input: &Vec<usize>, target: usize) -> Option<usize> {
+/// Part two, find a contiguous run of numbers that sum to `target` and add its smallest and largest
+/// numbers together.
+pub fn find_weakness(input: &Vec<usize>, target: usize) -> Option<usize> {
     fn find_weakness_iter(target: usize, cache: Vector<(usize, usize, usize)>, remaining: Vector<usize>) -> Option<usize> {
         match remaining.head() {
             Some(i) => {
@@ -0,0 +1,108 @@
+//! Solving a one-to-one assignment by elimination.
+//!
+//! A few puzzles give a set of candidate values for each key, and ask for the assignment where
+//! every key gets a different value, e.g. ticket fields to positions on day 16, and allergens to
+//! ingredients on day 21. The inputs are built so that there is always a key with only one
+//! candidate left, so fixing that key and removing its value from every other key's candidates
+//! eventually solves the whole thing without needing to backtrack.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Repeatedly fix the keys that only have one candidate left, removing that value from the other
+/// keys' candidates. Returns `None` if it gets stuck with no key having a single candidate, or if
+/// a key is left with no candidates at all.
+///
+/// # Examples
+/// ```
+/// # use std::collections::{HashMap, HashSet};
+/// # use advent_of_code_2020::elimination::eliminate;
+/// let mut candidates: HashMap<&str, HashSet<usize>> = HashMap::new();
+/// candidates.insert("class", vec!(1, 2).into_iter().collect());
+/// candidates.insert("row", vec!(0, 1, 2).into_iter().collect());
+/// candidates.insert("seat", vec!(2).into_iter().collect());
+///
+/// let mut expected = HashMap::new();
+/// expected.insert("class", 1);
+/// expected.insert("row", 0);
+/// expected.insert("seat", 2);
+///
+/// assert_eq!(Some(expected), eliminate(candidates.clone()));
+///
+/// candidates.insert("seat", vec!(1, 2).into_iter().collect());
+/// assert_eq!(None, eliminate(candidates));
+/// ```
+pub fn eliminate<K, V>(mut candidates: HashMap<K, HashSet<V>>) -> Option<HashMap<K, V>> where
+    K: Eq + Hash + Copy,
+    V: Eq + Hash + Copy,
+{
+    let mut solved = HashMap::new();
+
+    while !candidates.is_empty() {
+        let singletons: Vec<(K, V)> = candidates.iter()
+            .filter(|(_, values)| values.len() == 1)
+            .map(|(key, values)| (*key, *values.iter().next().expect("set has length 1")))
+            .collect();
+
+        if singletons.is_empty() {
+            return None;
+        }
+
+        for (key, value) in singletons {
+            // an earlier singleton in this pass may have claimed the same value
+            if !candidates.remove(&key)?.contains(&value) {
+                return None;
+            }
+
+            for values in candidates.values_mut() {
+                values.remove(&value);
+            }
+            solved.insert(key, value);
+        }
+    }
+
+    Some(solved)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use elimination::eliminate;
+
+    fn set(values: &[usize]) -> HashSet<usize> {
+        values.iter().copied().collect()
+    }
+
+    #[test]
+    fn can_eliminate() {
+        let mut candidates = HashMap::new();
+        candidates.insert('a', set(&[1, 2, 3]));
+        candidates.insert('b', set(&[2]));
+        candidates.insert('c', set(&[2, 3]));
+
+        let mut expected = HashMap::new();
+        expected.insert('a', 1);
+        expected.insert('b', 2);
+        expected.insert('c', 3);
+
+        assert_eq!(Some(expected), eliminate(candidates));
+        assert_eq!(Some(HashMap::new()), eliminate::<char, usize>(HashMap::new()));
+    }
+
+    #[test]
+    fn detects_unsolvable_candidates() {
+        let mut conflicting = HashMap::new();
+        conflicting.insert('a', set(&[1]));
+        conflicting.insert('b', set(&[1]));
+        assert_eq!(None, eliminate(conflicting));
+
+        let mut ambiguous = HashMap::new();
+        ambiguous.insert('a', set(&[1, 2]));
+        ambiguous.insert('b', set(&[1, 2]));
+        assert_eq!(None, eliminate(ambiguous));
+
+        let mut empty = HashMap::new();
+        empty.insert('a', set(&[]));
+        assert_eq!(None, eliminate(empty));
+    }
+}
@@ -0,0 +1,166 @@
+//! The error type shared by every day's parsers and solvers.
+//!
+//! Puzzle inputs are line based, so an [`Error`] records where in the input the problem was found
+//! as well as what went wrong. Parsers for a single line don't know which line they've been given,
+//! so they report the column and offending text, and [`parse_lines`] (or the caller) fills in the
+//! line number. The runner then adds the day before printing it as a diagnostic, e.g.
+//!
+//! ```text
+//! Day 12, line 3, column 1: Invalid instruction 'Z'
+//!     Z10
+//!     ^
+//! ```
+
+use std::error;
+use std::fmt::{self, Display, Formatter};
+use std::result;
+
+/// A problem with the puzzle input, or a failure to find an answer from it.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Error {
+    /// The day being run, if known.
+    pub day: Option<usize>,
+    /// The 1-indexed line of the input the error was found on, if it relates to a specific line.
+    pub line: Option<usize>,
+    /// The 1-indexed column within [`Error::text`] the error was found at, if known.
+    pub column: Option<usize>,
+    /// The offending input text, usually the whole line.
+    pub text: Option<String>,
+    /// A description of what went wrong.
+    pub message: String,
+}
+
+/// A [`result::Result`] with the error fixed to this crate's [`Error`].
+pub type Result<T> = result::Result<T, Error>;
+
+impl Error {
+    /// An error that isn't tied to a specific part of the input, e.g. a solver that failed to find
+    /// an answer.
+    pub fn new<S: Into<String>>(message: S) -> Error {
+        Error { day: None, line: None, column: None, text: None, message: message.into() }
+    }
+
+    /// An error found in `text`, e.g. a line that doesn't match the expected format.
+    pub fn invalid<S: Into<String>>(text: &str, message: S) -> Error {
+        Error { text: Some(text.to_string()), ..Error::new(message) }
+    }
+
+    /// An error found at a 1-indexed `column` of `text`.
+    pub fn at_column<S: Into<String>>(text: &str, column: usize, message: S) -> Error {
+        Error { column: Some(column), ..Error::invalid(text, message) }
+    }
+
+    /// Record the 1-indexed `line` the error was found on, and the text of that line if the error
+    /// doesn't already have some. Leaves any existing line number as is, so that the innermost
+    /// parser that knows the line wins.
+    pub fn on_line(self, line: usize, text: &str) -> Error {
+        Error {
+            line: self.line.or(Some(line)),
+            text: self.text.or_else(|| Some(text.to_string())),
+            ..self
+        }
+    }
+
+    /// Shift the line number by `offset`, for errors from parsing a section of the input that
+    /// doesn't start on the first line.
+    pub fn offset_lines(self, offset: usize) -> Error {
+        Error { line: self.line.map(|line| line + offset), ..self }
+    }
+
+    /// Record the day that was being run.
+    pub fn in_day(self, day: usize) -> Error {
+        Error { day: Some(day), ..self }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let location: Vec<String> = vec!(
+            self.day.map(|day| format!("Day {}", day)),
+            self.line.map(|line| format!("line {}", line)),
+            self.column.map(|column| format!("column {}", column)),
+        ).into_iter().flatten().collect();
+
+        if !location.is_empty() {
+            write!(f, "{}: ", location.join(", "))?;
+        }
+        write!(f, "{}", self.message)?;
+
+        if let Some(text) = &self.text {
+            write!(f, "\n    {}", text)?;
+            if let Some(column) = self.column {
+                write!(f, "\n    {}^", " ".repeat(column - 1))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl error::Error for Error {}
+
+/// Parse each line of `input` with `parser`, filling in the line number of any error.
+///
+/// # Examples
+/// ```
+/// # use advent_of_code_2020::error::{parse_lines, Error};
+/// let parse_number = |line: &str| line.parse::<u8>().map_err(|_| Error::invalid(line, "Not a number"));
+///
+/// assert_eq!(Ok(vec!(1, 2)), parse_lines("1\n2", parse_number));
+/// assert_eq!(Some(2), parse_lines("1\nx", parse_number).unwrap_err().line);
+/// ```
+pub fn parse_lines<'a, T, F>(input: &'a str, mut parser: F) -> Result<Vec<T>> where
+    F: FnMut(&'a str) -> Result<T>
+{
+    input.lines()
+        .enumerate()
+        .map(|(index, line)| parser(line).map_err(|err| err.on_line(index + 1, line)))
+        .collect()
+}
+
+/// Parse `text` as a number, reporting the `column` it started at if it isn't one.
+pub fn parse_number<T: ::std::str::FromStr>(text: &str, line: &str, column: usize) -> Result<T> {
+    text.parse::<T>()
+        .map_err(|_| Error::at_column(line, column, format!("Invalid number '{}'", text)))
+}
+
+#[cfg(test)]
+mod tests {
+    use error::{parse_lines, Error};
+
+    #[test]
+    fn can_build_location() {
+        let err = Error::at_column("Z10", 1, "Invalid instruction 'Z'").on_line(3, "ignored").in_day(12);
+
+        assert_eq!(Some(12), err.day);
+        assert_eq!(Some(3), err.line);
+        assert_eq!(Some(1), err.column);
+        assert_eq!(Some("Z10".to_string()), err.text);
+
+        assert_eq!(Some(5), Error::new("test").on_line(2, "a").on_line(1, "b").offset_lines(3).line);
+    }
+
+    #[test]
+    fn can_display() {
+        assert_eq!("No pair sums to 2020", Error::new("No pair sums to 2020").to_string());
+        assert_eq!(
+            "Day 12, line 3, column 2: Invalid number '1O'\n    F1O\n     ^",
+            Error::at_column("F1O", 2, "Invalid number '1O'").on_line(3, "F1O").in_day(12).to_string()
+        );
+        assert_eq!(
+            "line 2: Invalid line\n    abc",
+            Error::invalid("abc", "Invalid line").on_line(2, "abc").to_string()
+        );
+    }
+
+    #[test]
+    fn can_parse_lines() {
+        let parser = |line: &str| line.parse::<u8>().map_err(|_| Error::invalid(line, "Not a number"));
+
+        assert_eq!(Ok(vec!(1, 2, 3)), parse_lines("1\n2\n3", parser));
+        assert_eq!(
+            Err(Error::invalid("x", "Not a number").on_line(2, "x")),
+            parse_lines("1\nx\n3", parser)
+        );
+    }
+}
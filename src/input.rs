@@ -0,0 +1,242 @@
+//! Finding the puzzle input for each day.
+//!
+//! By default the input for day N is read from `res/day-N-input`, relative to the current working
+//! directory. The directory can be changed by setting the `AOC_INPUT_DIR` environment variable (or
+//! `--input-dir`), and a single day can be pointed at any file, or at stdin with `-`.
+//!
+//! Each day can also have any number of named input sets alongside the default one, e.g. to check
+//! the solutions against other people's inputs. These are stored in the same directory with the
+//! name as a suffix, so the `alice` input set for day 7 is `day-7-input-alice`.
+//!
+//! ```text
+//! res/
+//!   day-7-input        <- the default input set
+//!   day-7-input-alice  <- the "alice" input set
+//!   day-7-input-bob    <- the "bob" input set
+//! ```
+
+use std::env;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use error::{Error, Result};
+
+/// The environment variable that overrides the directory inputs are read from.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+/// The directory inputs are read from if [`INPUT_DIR_VAR`] isn't set.
+pub const DEFAULT_INPUT_DIR: &str = "res";
+/// The `--input` value that means read the input from stdin.
+pub const STDIN: &str = "-";
+
+/// Which input(s) to run each day against.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum InputSelection {
+    /// The default input set, i.e. `day-N-input`.
+    Default,
+    /// A specific file, or stdin if it is [`STDIN`].
+    Path(String),
+    /// A named input set, i.e. `day-N-input-<name>`.
+    Set(String),
+    /// Every input set found for the day, see [`InputResolver::discover`].
+    AllSets,
+}
+
+/// Where to read a single input from.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Read the whole input.
+    pub fn read(&self) -> Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)
+                    .map_err(|err| Error::new(format!("Failed to read stdin: {}", err)))?;
+                Ok(input)
+            }
+            InputSource::File(path) =>
+                fs::read_to_string(path)
+                    .map_err(|err| Error::new(format!("Failed to read '{}': {}", path.display(), err)))
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// An input to run a day against, with the name of the input set it came from. The default input
+/// set, and inputs given by path, have no name.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct NamedInput {
+    pub name: Option<String>,
+    pub source: InputSource,
+}
+
+/// Resolves an [`InputSelection`] to the input(s) for a given day.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct InputResolver {
+    dir: PathBuf,
+}
+
+impl InputResolver {
+    /// Resolve inputs within `dir`.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> InputResolver {
+        InputResolver { dir: dir.into() }
+    }
+
+    /// Resolve inputs within the directory set by [`INPUT_DIR_VAR`], or [`DEFAULT_INPUT_DIR`] if it
+    /// isn't set.
+    pub fn from_env() -> InputResolver {
+        InputResolver::new(env::var_os(INPUT_DIR_VAR).unwrap_or_else(|| DEFAULT_INPUT_DIR.into()))
+    }
+
+    /// The directory inputs are resolved within.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The path of the input set `name` for a day, or the default input set if `name` is `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::path::PathBuf;
+    /// # use advent_of_code_2020::input::InputResolver;
+    /// let resolver = InputResolver::new("inputs");
+    ///
+    /// assert_eq!(PathBuf::from("inputs/day-7-input"), resolver.path(7, None));
+    /// assert_eq!(PathBuf::from("inputs/day-7-input-alice"), resolver.path(7, Some("alice")));
+    /// ```
+    pub fn path(&self, day: usize, name: Option<&str>) -> PathBuf {
+        match name {
+            Some(name) => self.dir.join(format!("day-{}-input-{}", day, name)),
+            None => self.dir.join(format!("day-{}-input", day)),
+        }
+    }
+
+    /// Find the names of all the input sets in the directory for a day. The default input set, if
+    /// present, is first as `None`, followed by the named input sets in alphabetical order.
+    pub fn discover(&self, day: usize) -> Result<Vec<Option<String>>> {
+        let entries = fs::read_dir(&self.dir)
+            .map_err(|err| Error::new(format!("Failed to read '{}': {}", self.dir.display(), err)))?;
+
+        let default_name = format!("day-{}-input", day);
+        let prefix = format!("{}-", default_name);
+        let mut sets = Vec::new();
+
+        for entry in entries.flatten() {
+            if !entry.path().is_file() {
+                continue;
+            }
+
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            if file_name == default_name {
+                sets.push(None)
+            } else if let Some(name) = file_name.strip_prefix(&prefix) {
+                if !name.is_empty() {
+                    sets.push(Some(name.to_string()))
+                }
+            }
+        }
+
+        sets.sort();
+        Ok(sets)
+    }
+
+    /// The input(s) to run a day against.
+    pub fn resolve(&self, day: usize, selection: &InputSelection) -> Result<Vec<NamedInput>> {
+        let file = |name: Option<String>| {
+            let source = InputSource::File(self.path(day, name.as_deref()));
+            NamedInput { name, source }
+        };
+
+        match selection {
+            InputSelection::Default => Ok(vec!(file(None))),
+            InputSelection::Path(path) if path == STDIN =>
+                Ok(vec!(NamedInput { name: None, source: InputSource::Stdin })),
+            InputSelection::Path(path) =>
+                Ok(vec!(NamedInput { name: None, source: InputSource::File(PathBuf::from(path)) })),
+            InputSelection::Set(name) => Ok(vec!(file(Some(name.clone())))),
+            InputSelection::AllSets => {
+                let sets = self.discover(day)?;
+                if sets.is_empty() {
+                    return Err(Error::new(format!("No inputs found in '{}'", self.dir.display())));
+                }
+
+                Ok(sets.into_iter().map(file).collect())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use input::{InputResolver, InputSelection, InputSource, NamedInput};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("advent-of-code-2020-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn can_resolve_inputs() {
+        let resolver = InputResolver::new("inputs");
+        let file = |name: Option<&str>, path: &str| NamedInput {
+            name: name.map(|name| name.to_string()),
+            source: InputSource::File(PathBuf::from(path)),
+        };
+
+        assert_eq!(
+            Ok(vec!(file(None, "inputs/day-3-input"))),
+            resolver.resolve(3, &InputSelection::Default)
+        );
+        assert_eq!(
+            Ok(vec!(file(Some("bob"), "inputs/day-3-input-bob"))),
+            resolver.resolve(3, &InputSelection::Set("bob".to_string()))
+        );
+        assert_eq!(
+            Ok(vec!(file(None, "elsewhere/input.txt"))),
+            resolver.resolve(3, &InputSelection::Path("elsewhere/input.txt".to_string()))
+        );
+        assert_eq!(
+            Ok(vec!(NamedInput { name: None, source: InputSource::Stdin })),
+            resolver.resolve(3, &InputSelection::Path("-".to_string()))
+        );
+    }
+
+    #[test]
+    fn can_discover_input_sets() {
+        let dir = temp_dir("discover");
+        for file in &["day-1-input", "day-1-input-bob", "day-1-input-alice", "day-10-input", "day-1-input-"] {
+            fs::write(dir.join(file), "1").unwrap();
+        }
+
+        let resolver = InputResolver::new(&dir);
+
+        assert_eq!(
+            Ok(vec!(None, Some("alice".to_string()), Some("bob".to_string()))),
+            resolver.discover(1)
+        );
+        assert_eq!(Ok(vec!(None)), resolver.discover(10));
+        assert_eq!(Ok(vec!()), resolver.discover(2));
+        assert_eq!(3, resolver.resolve(1, &InputSelection::AllSets).unwrap().len());
+        assert!(resolver.resolve(2, &InputSelection::AllSets).is_err());
+        assert!(InputResolver::new(dir.join("missing")).discover(1).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
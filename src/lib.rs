@@ -0,0 +1,64 @@
+//! My solutions to [Advent of Code 2020](https://adventofcode.com/2020), as a library so that the
+//! parsers and solvers for each day can be reused outside of the day runner.
+//!
+//! - `day_N` - the parsers and solvers for each day, with a [`solution::Solution`] implementation
+//!   that ties them together, e.g. [`day_7::Day7`].
+//! - [`solution`] - the [`solution::Solution`] trait, and a [`solution::registry`] of all the days
+//!   keyed by day number.
+//! - [`error`] - the [`error::Error`] returned by parsers and solvers, with the location in the
+//!   input that caused it.
+//! - [`input`] - finds the puzzle input for each day.
+//! - [`runner`] - runs days from the registry, printing or verifying the answers.
+//! - [`answers`] - the store of expected answers used when verifying.
+//! - [`bench`] - benchmarks each day, and compares the timings with a saved baseline.
+//! - [`output`] - formats the runner's results as text, JSON lines or TSV.
+//! - [`cli`] - parses the command line arguments for the runner.
+//! - [`elimination`] - solves the one-to-one assignment puzzles shared by days 16 and 21.
+//!
+//! # Example
+//! ```
+//! use advent_of_code_2020::day_1::Day1;
+//! use advent_of_code_2020::solution::Solution;
+//!
+//! let expenses = Day1.parse("1721\n979\n366\n299\n675\n1456").unwrap();
+//! assert_eq!(Ok(514579), Day1.part_one(&expenses));
+//! ```
+
+pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_24;
+pub mod day_25;
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod elimination;
+pub mod error;
+pub mod input;
+pub mod output;
+pub mod runner;
+pub mod solution;
+
+extern crate regex;
+extern crate im;
+extern crate either;
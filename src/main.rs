@@ -1,71 +1,61 @@
-mod day_1;
-mod day_2;
-mod day_3;
-mod day_4;
-mod day_5;
-mod day_6;
-mod day_7;
-mod day_8;
-mod day_9;
-mod day_10;
-mod day_11;
-mod day_12;
-mod day_13;
-mod day_14;
-mod day_15;
-mod day_16;
-mod day_17;
+//! The day runner. All of the solutions live in the library crate, this just decides which days to
+//! run from the command line arguments, or by asking if there are none.
 
-use std::time::Instant;
-use std::io::{self, Write};
-
-extern crate core;
+extern crate advent_of_code_2020;
 
 #[macro_use]
 extern crate text_io;
-extern crate regex;
-extern crate proc_macro;
-extern crate im;
-extern crate either;
+
+use std::io::{self, Write};
+use std::{env, process};
+use advent_of_code_2020::{cli, runner, solution};
+use advent_of_code_2020::cli::{Command, RunOptions};
+use advent_of_code_2020::solution::Part;
 
 fn main() {
+    let days = solution::registry();
+
+    let program = env::args().next().unwrap_or_else(|| "advent-of-code-2020".to_string());
+
+    let options = match cli::parse_args(env::args().skip(1), days.len()) {
+        Ok(Command::Run(options)) => options,
+        Ok(Command::Interactive) => match prompt_for_day(days.len()) {
+            Some(options) => options,
+            None => process::exit(cli::EXIT_USAGE),
+        },
+        Ok(Command::Help) => {
+            println!("{}", cli::usage(&program, days.len()));
+            process::exit(cli::EXIT_SUCCESS)
+        }
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, cli::usage(&program, days.len()));
+            process::exit(cli::EXIT_USAGE)
+        }
+    };
+
+    // some days read extra files from the input directory, so they need to look in the one chosen
+    let days = match &options.input_dir {
+        Some(dir) => solution::registry_in(dir),
+        None => days,
+    };
+
+    process::exit(runner::run_days(&days, &options))
+}
+
+/// The original interface, used when no arguments are given. Asks for a single day, or 0 for all.
+fn prompt_for_day(day_count: usize) -> Option<RunOptions> {
     print!("Which day? (0 to run all): ");
     io::stdout().flush().unwrap();
 
     let day: i32 = read!();
-    let days:Vec<Box<dyn Fn()->()>> = vec!(
-        Box::new(|| day_1::run()),
-        Box::new(|| day_2::run()),
-        Box::new(|| day_3::run()),
-        Box::new(|| day_4::run()),
-        Box::new(|| day_5::run()),
-        Box::new(|| day_6::run()),
-        Box::new(|| day_7::run()),
-        Box::new(|| day_8::run()),
-        Box::new(|| day_9::run()),
-        Box::new(|| day_10::run()),
-        Box::new(|| day_11::run()),
-        Box::new(|| day_12::run()),
-        Box::new(|| day_13::run()),
-        Box::new(|| day_14::run()),
-        Box::new(|| day_15::run()),
-        Box::new(|| day_16::run()),
-        Box::new(|| day_17::run())
-    );
-
-    let start = Instant::now();
-    match days.get((day - 1) as usize) {
-        Some(solution) => solution(),
-        None if day == 0 => days.iter().enumerate().for_each(|(i, solution)| {
-            let start = Instant::now();
-            println!("==== Day {} ====", i + 1);
-            solution();
-            println!("-- took {:.2?}", start.elapsed());
-        }),
-        None => println!("Invalid Day {}", day)
+    let parts = vec!(Part::One, Part::Two);
+
+    match day {
+        0 => Some(RunOptions::new((1..=day_count).collect(), parts)),
+        day if day >= 1 && day as usize <= day_count => Some(RunOptions::new(vec!(day as usize), parts)),
+        day => {
+            eprintln!("Invalid Day {}", day);
+            None
+        }
     }
-
-    println!();
-    println!("Finished in {:.2?}", start.elapsed());
 }
-
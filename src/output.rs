@@ -0,0 +1,187 @@
+//! Formatting the runner's results.
+//!
+//! By default the runner prints a human readable summary of each day. For scripting it can instead
+//! print one [`Record`] per day and part, either as [JSON lines](https://jsonlines.org/), e.g.
+//!
+//! ```text
+//! {"day":7,"part":1,"input_set":null,"input":"res/day-7-input","answer":"172","parse_ms":1.102,"elapsed_ms":0.215}
+//! ```
+//!
+//! or as tab separated values, with the [`TSV_HEADER`] as the first row.
+//!
+//! Errors are still reported on stderr, so stdout only contains records.
+
+use std::time::Duration;
+
+/// How the runner prints its results.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Format {
+    /// Human readable text, with headings for each day and timings.
+    Text,
+    /// One JSON object per line for each day and part.
+    Json,
+    /// Tab separated values, with a header row.
+    Tsv,
+}
+
+/// The column names printed as the first row in [`Format::Tsv`].
+pub const TSV_HEADER: &str = "day\tpart\tinput_set\tinput\tanswer\tparse_ms\telapsed_ms";
+
+/// The result of running one part of a day against an input.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Record<'a> {
+    pub day: usize,
+    /// The part number, 1 or 2.
+    pub part: usize,
+    /// The name of the input set, if not the default one.
+    pub input_set: Option<&'a str>,
+    /// Where the input was read from, a path or `stdin`.
+    pub input: &'a str,
+    pub answer: &'a str,
+    /// How long it took to parse the input. This is shared by both parts.
+    pub parse: Duration,
+    /// How long it took to solve this part, excluding parsing.
+    pub elapsed: Duration,
+}
+
+impl<'a> Record<'a> {
+    /// Format the record as a single line of JSON.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::time::Duration;
+    /// # use advent_of_code_2020::output::Record;
+    /// let record = Record {
+    ///     day: 7,
+    ///     part: 1,
+    ///     input_set: Some("alice"),
+    ///     input: "res/day-7-input-alice",
+    ///     answer: "172",
+    ///     parse: Duration::from_micros(1102),
+    ///     elapsed: Duration::from_micros(215),
+    /// };
+    ///
+    /// assert_eq!(
+    ///     r#"{"day":7,"part":1,"input_set":"alice","input":"res/day-7-input-alice","answer":"172","parse_ms":1.102,"elapsed_ms":0.215}"#,
+    ///     record.to_json()
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"day\":{},\"part\":{},\"input_set\":{},\"input\":{},\"answer\":{},\"parse_ms\":{:.3},\"elapsed_ms\":{:.3}}}",
+            self.day,
+            self.part,
+            self.input_set.map(json_string).unwrap_or_else(|| "null".to_string()),
+            json_string(self.input),
+            json_string(self.answer),
+            millis(self.parse),
+            millis(self.elapsed),
+        )
+    }
+
+    /// Format the record as a single row of tab separated values, in the order of [`TSV_HEADER`].
+    pub fn to_tsv(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{:.3}\t{:.3}",
+            self.day,
+            self.part,
+            tsv_field(self.input_set.unwrap_or("")),
+            tsv_field(self.input),
+            tsv_field(self.answer),
+            millis(self.parse),
+            millis(self.elapsed),
+        )
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Quote and escape a string for JSON.
+fn json_string(text: &str) -> String {
+    let mut output = String::with_capacity(text.len() + 2);
+    output.push('"');
+
+    for char in text.chars() {
+        match char {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+
+    output.push('"');
+    output
+}
+
+/// TSV fields can't contain tabs or new lines, so these are escaped as `\t` and `\n`.
+fn tsv_field(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Quote a field for comma separated values, if it contains anything that needs quoting. Quotes
+/// within the field are doubled.
+///
+/// # Examples
+/// ```
+/// # use advent_of_code_2020::output::csv_field;
+/// assert_eq!("plain", csv_field("plain"));
+/// assert_eq!("\"a, \"\"b\"\"\"", csv_field("a, \"b\""));
+/// ```
+pub fn csv_field(text: &str) -> String {
+    if text.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use output::{csv_field, json_string, tsv_field, Record};
+
+    fn record<'a>(input_set: Option<&'a str>, answer: &'a str) -> Record<'a> {
+        Record {
+            day: 13,
+            part: 2,
+            input_set,
+            input: "res/day-13-input",
+            answer,
+            parse: Duration::from_micros(25),
+            elapsed: Duration::from_millis(1500),
+        }
+    }
+
+    #[test]
+    fn can_escape() {
+        assert_eq!("\"plain\"", json_string("plain"));
+        assert_eq!(r#""a \"quote\"\n\\ \u0001""#, json_string("a \"quote\"\n\\ \u{1}"));
+        assert_eq!("a\\tb\\nc\\\\d", tsv_field("a\tb\nc\\d"));
+        assert_eq!("\"a\nb\"", csv_field("a\nb"));
+    }
+
+    #[test]
+    fn can_format_records() {
+        assert_eq!(
+            r#"{"day":13,"part":2,"input_set":null,"input":"res/day-13-input","answer":"1068781","parse_ms":0.025,"elapsed_ms":1500.000}"#,
+            record(None, "1068781").to_json()
+        );
+        assert_eq!(
+            "13\t2\t\tres/day-13-input\t1068781\t0.025\t1500.000",
+            record(None, "1068781").to_tsv()
+        );
+        assert_eq!(
+            "13\t2\tbob\tres/day-13-input\ta\\tb\t0.025\t1500.000",
+            record(Some("bob"), "a\tb").to_tsv()
+        );
+    }
+}
@@ -0,0 +1,518 @@
+//! Runs days from the [`registry`](crate::solution::registry), printing the answers and timings.
+//!
+//! The results are printed in the [`Format`] chosen in the [`RunOptions`]. Anything other than the
+//! results themselves, e.g. headings and total timings, is only printed in [`Format::Text`] so
+//! that the other formats can be read by scripts.
+//!
+//! In [`Mode::Verify`] the answers are compared with the expected [`Answers`] instead of being
+//! printed, and [`Mode::Record`] then stores them as the new expected answers. [`Mode::Bench`]
+//! prints timing statistics for each day instead, see [`bench`](crate::bench).
+//!
+//! When [`RunOptions::jobs`] is more than one, days are run on a pool of worker threads, and the
+//! parts of each day on their own threads. The results are still handled on the main thread in
+//! day order, so the output is the same as running them one after another apart from the timings.
+
+use std::collections::BTreeMap;
+use std::panic;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+use answers::{Answers, ANSWERS_FILE};
+use bench::{self, Baseline, Comparison};
+use cli::{self, Mode, RunOptions};
+use error::{Error, Result};
+use input::{InputResolver, NamedInput};
+use output::{Format, Record, TSV_HEADER};
+use solution::{DaySolution, Part, TimedRun};
+
+/// Counts of how the answers compared to the expected answers.
+#[derive(Debug, Default)]
+struct Tally {
+    same: usize,
+    different: usize,
+    missing: usize,
+}
+
+impl Tally {
+    fn total(&self) -> usize {
+        self.same + self.different + self.missing
+    }
+}
+
+/// The result of running a day against one input.
+struct Outcome {
+    /// The answers, or the error that stopped them being found. The error is `None` if the day
+    /// panicked, as the panic will already have been reported.
+    run: std::result::Result<TimedRun, Option<Error>>,
+    /// How long it took, including reading the input.
+    elapsed: Duration,
+}
+
+/// A day to run, and the input to run it against.
+type Job<'a> = (&'a dyn DaySolution, &'a NamedInput);
+
+/// Run each of the requested days, returning the exit code for the process.
+///
+/// An error (or panic) in one day is reported on stderr and the remaining days are still run, but
+/// the process will exit with [`cli::EXIT_FAILURE`]. When running a day against several input sets,
+/// each set is run even if an earlier one fails. When verifying, an answer that differs from the
+/// expected answer is also a failure, but one that hasn't been recorded yet isn't.
+pub fn run_days(days: &BTreeMap<usize, Box<dyn DaySolution>>, options: &RunOptions) -> i32 {
+    let start = Instant::now();
+    let resolver = options.input_dir.as_ref()
+        .map(InputResolver::new)
+        .unwrap_or_else(InputResolver::from_env);
+    let answers_path = options.answers.as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| resolver.dir().join(ANSWERS_FILE));
+    let headings = options.format == Format::Text && options.mode == Mode::Run;
+    let mut failures = Vec::new();
+
+    let mut expected = match options.mode {
+        Mode::Run => Answers::new(),
+        Mode::Verify | Mode::Record => match Answers::load(&answers_path) {
+            Ok(answers) => answers,
+            Err(err) => {
+                eprintln!("{}", err);
+                return cli::EXIT_FAILURE;
+            }
+        }
+        Mode::Bench => Answers::new(),
+    };
+    let mut tally = Tally::default();
+
+    let baseline = match &options.bench.baseline {
+        Some(path) => match Baseline::load(path) {
+            Ok(baseline) => baseline,
+            Err(err) => {
+                eprintln!("{}", err);
+                return cli::EXIT_FAILURE;
+            }
+        },
+        None => Baseline::new(),
+    };
+    let mut new_baseline = Baseline::new();
+
+    if options.format == Format::Tsv {
+        println!("{}", TSV_HEADER);
+    }
+
+    let threads = match options.jobs {
+        0 => thread::available_parallelism().map(|threads| threads.get()).unwrap_or(1),
+        jobs => jobs,
+    };
+    let resolved: Vec<(usize, Result<Vec<NamedInput>>)> = options.days.iter()
+        .map(|&day| (day, resolver.resolve(day, &options.input)))
+        .collect();
+    // benchmarks are run separately below, so that they aren't affected by anything else running
+    let jobs: Vec<Job> = match options.mode {
+        Mode::Bench => Vec::new(),
+        _ => resolved.iter()
+            .flat_map(|(day, inputs)| inputs.iter().flatten().map(move |input| (days[day].as_ref(), input)))
+            .collect(),
+    };
+    let mut critical_path: Option<(Duration, String)> = None;
+
+    with_outcomes(&jobs, &options.parts, threads, |outcomes| {
+        for (day, inputs) in &resolved {
+            let day = *day;
+            let mut day_elapsed = Duration::ZERO;
+
+            if headings && options.days.len() > 1 {
+                println!("==== Day {} ====", day);
+            }
+
+            let inputs = match inputs {
+                Ok(inputs) => inputs,
+                Err(err) => {
+                    eprintln!("{}", err.clone().in_day(day));
+                    failures.push(day);
+                    continue;
+                }
+            };
+
+            let mut succeeded = true;
+            for input in inputs {
+                if headings && (inputs.len() > 1 || input.name.is_some()) {
+                    println!("-- Input set: {} --", input_set_name(input));
+                }
+
+                if options.mode == Mode::Bench {
+                    let bench_start = Instant::now();
+                    succeeded &= bench_input(days[&day].as_ref(), day, input, options, &baseline, &mut new_baseline);
+                    day_elapsed += bench_start.elapsed();
+                    continue;
+                }
+
+                let outcome = outcomes.next().expect("An outcome for each job");
+                day_elapsed += outcome.elapsed;
+                if critical_path.as_ref().map(|(longest, _)| outcome.elapsed > *longest).unwrap_or(true) {
+                    critical_path = Some((outcome.elapsed, input_label(day, input)));
+                }
+
+                let run = match outcome.run {
+                    Ok(run) => run,
+                    Err(err) => {
+                        if let Some(err) = err {
+                            report_error(day, input, err);
+                        }
+                        succeeded = false;
+                        continue;
+                    }
+                };
+
+                match options.mode {
+                    Mode::Run => print_answers(day, input, &run, options.format),
+                    Mode::Verify => succeeded &= verify_answers(day, input, &run, &expected, &mut tally),
+                    Mode::Record => {
+                        record_answers(day, input, &run, &expected, &mut tally);
+                        for answer in &run.answers {
+                            expected.insert(day, input.name.as_deref(), part_number(answer.part), &answer.answer);
+                        }
+                    }
+                    Mode::Bench => (),
+                }
+            }
+
+            if !succeeded {
+                failures.push(day);
+            }
+
+            if headings && options.days.len() > 1 {
+                println!("-- took {:.2?}", day_elapsed);
+            }
+        }
+    });
+
+    match options.mode {
+        Mode::Run => (),
+        Mode::Verify => println!(
+            "\nVerified {} answers: {} passed, {} failed, {} missing",
+            tally.total(), tally.same, tally.different, tally.missing
+        ),
+        Mode::Record => {
+            println!(
+                "\nRecorded {} answers: {} unchanged, {} updated, {} new",
+                tally.total(), tally.same, tally.different, tally.missing
+            );
+            match expected.save(&answers_path) {
+                Ok(()) => println!("Saved the expected answers to '{}'", answers_path.display()),
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return cli::EXIT_FAILURE;
+                }
+            }
+        }
+        Mode::Bench => if let Some(path) = &options.bench.save_baseline {
+            match new_baseline.save(path) {
+                Ok(()) => println!("Saved the baseline to '{}'", path),
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return cli::EXIT_FAILURE;
+                }
+            }
+        },
+    }
+
+    if options.format == Format::Text {
+        println!();
+        if let (true, Some((elapsed, label))) = (threads > 1, &critical_path) {
+            println!("Critical path: {} took {:.2?}", label, elapsed);
+        }
+        println!("Finished in {:.2?} on {} thread{}", start.elapsed(), threads, if threads == 1 { "" } else { "s" });
+    }
+
+    if failures.is_empty() {
+        cli::EXIT_SUCCESS
+    } else {
+        eprintln!(
+            "Failed: {}",
+            failures.iter().map(|day| format!("Day {}", day)).collect::<Vec<String>>().join(", ")
+        );
+        cli::EXIT_FAILURE
+    }
+}
+
+/// Run a day against a single input, solving the parts on separate threads if `parallel_parts`.
+fn execute(solution: &dyn DaySolution, input: &NamedInput, parts: &[Part], parallel_parts: bool) -> Outcome {
+    let start = Instant::now();
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| -> Result<TimedRun> {
+        let raw_input = input.source.read()?;
+        if parallel_parts {
+            solution.run_timed_parallel(&raw_input, parts)
+        } else {
+            solution.run_timed(&raw_input, parts)
+        }
+    }));
+
+    let run = match result {
+        Ok(Ok(run)) => Ok(run),
+        Ok(Err(err)) => Err(Some(err)),
+        Err(_) => Err(None),
+    };
+
+    Outcome { run, elapsed: start.elapsed() }
+}
+
+/// Run each of the `jobs` on a pool of `threads` worker threads, passing an iterator over the
+/// outcomes to `handle` on the current thread. The outcomes are in the same order as `jobs`,
+/// whichever order they finish in.
+///
+/// With a single thread there's no pool, and each job is run when the iterator reaches it.
+fn with_outcomes<F>(jobs: &[Job], parts: &[Part], threads: usize, handle: F) where
+    F: FnOnce(&mut dyn Iterator<Item=Outcome>)
+{
+    if threads <= 1 {
+        return handle(&mut jobs.iter().map(|&(solution, input)| execute(solution, input, parts, false)));
+    }
+
+    let next_job = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+
+        for _ in 0..threads.min(jobs.len()) {
+            let sender = sender.clone();
+            let next_job = &next_job;
+            scope.spawn(move || loop {
+                let index = next_job.fetch_add(1, Ordering::SeqCst);
+                let (solution, input) = match jobs.get(index) {
+                    Some(&job) => job,
+                    None => break,
+                };
+
+                if sender.send((index, execute(solution, input, parts, true))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        handle(&mut InOrder { receiver, pending: BTreeMap::new(), next: 0 })
+    })
+}
+
+/// Reorders the outcomes sent by the worker threads back into the order of the jobs.
+struct InOrder {
+    receiver: Receiver<(usize, Outcome)>,
+    pending: BTreeMap<usize, Outcome>,
+    next: usize,
+}
+
+impl Iterator for InOrder {
+    type Item = Outcome;
+
+    fn next(&mut self) -> Option<Outcome> {
+        loop {
+            if let Some(outcome) = self.pending.remove(&self.next) {
+                self.next += 1;
+                return Some(outcome);
+            }
+
+            let (index, outcome) = self.receiver.recv().ok()?;
+            self.pending.insert(index, outcome);
+        }
+    }
+}
+
+/// Benchmark a day against a single input, printing the statistics for each step and comparing
+/// them with the `baseline`. The medians are added to `new_baseline`. Returns false if the day
+/// failed to run, or any step regressed.
+fn bench_input(
+    solution: &dyn DaySolution,
+    day: usize,
+    input: &NamedInput,
+    options: &RunOptions,
+    baseline: &Baseline,
+    new_baseline: &mut Baseline,
+) -> bool {
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| -> Result<bench::BenchResult> {
+        let raw_input = input.source.read()?;
+        bench::bench(solution, &raw_input, &options.parts, options.bench.warmup, options.bench.iterations)
+    }));
+
+    let result = match result {
+        Ok(Ok(result)) => result,
+        Ok(Err(err)) => {
+            report_error(day, input, err);
+            return false;
+        }
+        Err(_) => return false,
+    };
+
+    println!(
+        "{} - {} iterations after {} warm-up",
+        input_label(day, input), options.bench.iterations, options.bench.warmup
+    );
+    println!("  step           min      median        mean      stddev  baseline");
+
+    let mut regressed = false;
+    for (step, stats) in result.steps {
+        let comparison = baseline.compare(day, input.name.as_deref(), step, stats.median, options.bench.threshold);
+        regressed |= matches!(comparison, Comparison::Regressed(_));
+        new_baseline.insert(day, input.name.as_deref(), step, stats.median);
+
+        println!(
+            "  {:<8}{:>12}{:>12}{:>12}{:>12}  {}",
+            step.to_string(),
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.mean),
+            format!("{:.2?}", stats.stddev),
+            comparison
+        );
+    }
+
+    !regressed
+}
+
+/// Report a failure to run a day on stderr.
+fn report_error(day: usize, input: &NamedInput, err: Error) {
+    match &input.name {
+        Some(name) => eprintln!("Input set '{}': {}", name, err.in_day(day)),
+        None => eprintln!("{}", err.in_day(day)),
+    }
+}
+
+/// Print the answers to each of the requested parts in the chosen format.
+fn print_answers(day: usize, input: &NamedInput, run: &TimedRun, format: Format) {
+    let source = input.source.to_string();
+
+    for answer in &run.answers {
+        let record = Record {
+            day,
+            part: part_number(answer.part),
+            input_set: input.name.as_deref(),
+            input: &source,
+            answer: &answer.answer,
+            parse: run.parse,
+            elapsed: answer.elapsed,
+        };
+
+        match format {
+            Format::Text => println!("Part {}: {}", record.part, record.answer),
+            Format::Json => println!("{}", record.to_json()),
+            Format::Tsv => println!("{}", record.to_tsv()),
+        }
+    }
+}
+
+/// Compare each answer with the expected answer, printing the outcome. Returns false if any of the
+/// answers differed.
+fn verify_answers(day: usize, input: &NamedInput, run: &TimedRun, expected: &Answers, tally: &mut Tally) -> bool {
+    let mut passed = true;
+
+    for answer in &run.answers {
+        let part = part_number(answer.part);
+        let status = match expected.get(day, input.name.as_deref(), part) {
+            Some(expected) if expected == answer.answer => {
+                tally.same += 1;
+                "pass".to_string()
+            }
+            Some(expected) => {
+                tally.different += 1;
+                passed = false;
+                format!("FAIL - expected {}, got {}", expected, answer.answer)
+            }
+            None => {
+                tally.missing += 1;
+                format!("missing - got {}", answer.answer)
+            }
+        };
+
+        println!("{}: {}", answer_label(day, input, part), status);
+    }
+
+    passed
+}
+
+/// Compare each answer with the answer it is about to replace, printing the outcome.
+fn record_answers(day: usize, input: &NamedInput, run: &TimedRun, expected: &Answers, tally: &mut Tally) {
+    for answer in &run.answers {
+        let part = part_number(answer.part);
+        let status = match expected.get(day, input.name.as_deref(), part) {
+            Some(expected) if expected == answer.answer => {
+                tally.same += 1;
+                "unchanged".to_string()
+            }
+            Some(expected) => {
+                tally.different += 1;
+                format!("updated - was {}, now {}", expected, answer.answer)
+            }
+            None => {
+                tally.missing += 1;
+                format!("new - {}", answer.answer)
+            }
+        };
+
+        println!("{}: {}", answer_label(day, input, part), status);
+    }
+}
+
+/// e.g. `Day 7` for the default input set, or `Day 7 (alice)` for a named one.
+fn input_label(day: usize, input: &NamedInput) -> String {
+    match &input.name {
+        Some(name) => format!("Day {} ({})", day, name),
+        None => format!("Day {}", day),
+    }
+}
+
+/// e.g. `Day 7 part 1` for the default input set, or `Day 7 (alice) part 1` for a named one.
+fn answer_label(day: usize, input: &NamedInput, part: usize) -> String {
+    format!("{} part {}", input_label(day, input), part)
+}
+
+fn input_set_name(input: &NamedInput) -> &str {
+    input.name.as_deref().unwrap_or("default")
+}
+
+fn part_number(part: Part) -> usize {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use input::{InputSource, NamedInput};
+    use runner::{with_outcomes, Job};
+    use solution::{registry, Part};
+
+    #[test]
+    fn can_run_jobs_in_order() {
+        let dir = env::temp_dir().join(format!("advent-of-code-2020-runner-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let days = registry();
+        let inputs: Vec<NamedInput> = vec!("0,3,6", "1,3,2", "oops", "2,1,3").into_iter()
+            .enumerate()
+            .map(|(index, input)| {
+                let path = dir.join(format!("input-{}", index));
+                fs::write(&path, input).unwrap();
+                NamedInput { name: None, source: InputSource::File(path) }
+            })
+            .collect();
+        let jobs: Vec<Job> = inputs.iter().map(|input| (days[&15].as_ref(), input)).collect();
+
+        for &threads in &[1, 3] {
+            let mut answers = Vec::new();
+            with_outcomes(&jobs, &[Part::One], threads, |outcomes| {
+                for outcome in outcomes {
+                    answers.push(outcome.run.ok().map(|run| run.answers[0].answer.clone()));
+                }
+            });
+
+            assert_eq!(
+                vec!(Some("436".to_string()), Some("1".to_string()), None, Some("10".to_string())),
+                answers
+            );
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
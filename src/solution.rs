@@ -0,0 +1,259 @@
+//! The common interface implemented by each day's solution.
+//!
+//! Each day provides a struct (e.g. [`day_7::Day7`]) that implements [`Solution`], splitting
+//! the work into parsing the puzzle input, and then solving each of the two parts from that parsed
+//! input. This means the runner, tests and any other tooling can run the parts individually and
+//! inspect the answers, rather than having to read them back out of the printed output.
+//! Solutions are given the puzzle input as a string, so don't know where it came from. That is
+//! decided by the runner, by default `res/day-N-input`, see [`input`](crate::input) for the other
+//! options.
+//!
+//! As the parsed input and answers have different types for each day, [`DaySolution`] provides a
+//! type-erased view of a [`Solution`] so that they can all be stored in the [`registry`].
+//!
+//! Parsing and solving can both fail, e.g. on malformed input, or input that has no answer. These
+//! return an [`Error`](crate::error::Error) describing where and why, rather than panicking.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::panic;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+use error::Result;
+use input::InputResolver;
+use day_1;
+use day_2;
+use day_3;
+use day_4;
+use day_5;
+use day_6;
+use day_7;
+use day_8;
+use day_9;
+use day_10;
+use day_11;
+use day_12;
+use day_13;
+use day_14;
+use day_15;
+use day_16;
+use day_17;
+use day_18;
+use day_19;
+use day_20;
+use day_21;
+use day_22;
+use day_23;
+use day_24;
+use day_25;
+
+/// One of the two parts that each day's puzzle is split into.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Part {
+    One,
+    Two,
+}
+
+/// A solution to one day's puzzle.
+pub trait Solution {
+    /// The puzzle input once parsed, this may borrow from the raw input. This is shared between
+    /// threads when running the parts in parallel, so must be [`Sync`].
+    type Input<'a>: Sync;
+    /// The answer to part one.
+    type PartOne: Display;
+    /// The answer to part two.
+    type PartTwo: Display;
+
+    /// Turn the raw puzzle input into the structured data used by both parts.
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>>;
+
+    /// Solve part one from the parsed input.
+    fn part_one(&self, input: &Self::Input<'_>) -> Result<Self::PartOne>;
+
+    /// Solve part two from the parsed input.
+    fn part_two(&self, input: &Self::Input<'_>) -> Result<Self::PartTwo>;
+}
+
+/// The answers from [`DaySolution::run_timed`], along with how long each step took.
+#[derive(Debug, Clone)]
+pub struct TimedRun {
+    /// How long it took to parse the input.
+    pub parse: Duration,
+    /// The answer to each part, in the order requested.
+    pub answers: Vec<TimedAnswer>,
+}
+
+/// The displayed answer to one part, and how long it took to solve, excluding parsing.
+#[derive(Debug, Clone)]
+pub struct TimedAnswer {
+    pub part: Part,
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+/// A [`Solution`] with the parsed input and answer types erased, so that days can be stored
+/// together and run by number. This is implemented for every [`Solution`] that can be shared
+/// between threads.
+pub trait DaySolution: Send + Sync {
+    /// Parse the input and solve each of the requested `parts`, returning the displayed answers in
+    /// the same order as `parts`, or the first [`Error`](crate::error::Error) encountered.
+    fn run(&self, input: &str, parts: &[Part]) -> Result<Vec<(Part, String)>>;
+
+    /// As [`DaySolution::run`], but also times parsing and each part.
+    fn run_timed(&self, input: &str, parts: &[Part]) -> Result<TimedRun>;
+
+    /// As [`DaySolution::run_timed`], but solves each part on its own thread once the input has
+    /// been parsed.
+    fn run_timed_parallel(&self, input: &str, parts: &[Part]) -> Result<TimedRun>;
+}
+
+impl<S: Solution + Send + Sync> DaySolution for S {
+    fn run(&self, input: &str, parts: &[Part]) -> Result<Vec<(Part, String)>> {
+        let run = self.run_timed(input, parts)?;
+
+        Ok(run.answers.into_iter().map(|answer| (answer.part, answer.answer)).collect())
+    }
+
+    fn run_timed(&self, input: &str, parts: &[Part]) -> Result<TimedRun> {
+        let start = Instant::now();
+        let parsed = self.parse(input)?;
+        let parse = start.elapsed();
+
+        let answers = parts.iter()
+            .map(|&part| time_part(self, &parsed, part))
+            .collect::<Result<_>>()?;
+
+        Ok(TimedRun { parse, answers })
+    }
+
+    fn run_timed_parallel(&self, input: &str, parts: &[Part]) -> Result<TimedRun> {
+        let start = Instant::now();
+        let parsed = self.parse(input)?;
+        let parse = start.elapsed();
+
+        let answers = thread::scope(|scope| {
+            let parsed = &parsed;
+            let threads: Vec<_> = parts.iter()
+                .map(|&part| scope.spawn(move || time_part(self, parsed, part)))
+                .collect();
+
+            threads.into_iter()
+                .map(|thread| thread.join().unwrap_or_else(|panic| panic::resume_unwind(panic)))
+                .collect::<Result<_>>()
+        })?;
+
+        Ok(TimedRun { parse, answers })
+    }
+}
+
+/// Solve a single part, timing how long it took.
+fn time_part<S: Solution>(solution: &S, parsed: &S::Input<'_>, part: Part) -> Result<TimedAnswer> {
+    let start = Instant::now();
+    let answer = match part {
+        Part::One => solution.part_one(parsed)?.to_string(),
+        Part::Two => solution.part_two(parsed)?.to_string(),
+    };
+
+    Ok(TimedAnswer { part, answer, elapsed: start.elapsed() })
+}
+
+/// All of the implemented days' solutions, keyed by day number, reading any extra files they need
+/// from the default input directory, see [`registry_in`].
+pub fn registry() -> BTreeMap<usize, Box<dyn DaySolution>> {
+    registry_in(InputResolver::from_env().dir())
+}
+
+/// All of the implemented days' solutions, keyed by day number. Days that can be configured by a
+/// file, e.g. day 4's schema, look for it in `input_dir` alongside the puzzle inputs.
+pub fn registry_in<P: AsRef<Path>>(input_dir: P) -> BTreeMap<usize, Box<dyn DaySolution>> {
+    let mut days: BTreeMap<usize, Box<dyn DaySolution>> = BTreeMap::new();
+
+    days.insert(1, Box::new(day_1::Day1));
+    days.insert(2, Box::new(day_2::Day2));
+    days.insert(3, Box::new(day_3::Day3));
+    days.insert(4, Box::new(day_4::Day4::in_dir(input_dir)));
+    days.insert(5, Box::new(day_5::Day5));
+    days.insert(6, Box::new(day_6::Day6));
+    days.insert(7, Box::new(day_7::Day7));
+    days.insert(8, Box::new(day_8::Day8));
+    days.insert(9, Box::new(day_9::Day9));
+    days.insert(10, Box::new(day_10::Day10));
+    days.insert(11, Box::new(day_11::Day11));
+    days.insert(12, Box::new(day_12::Day12));
+    days.insert(13, Box::new(day_13::Day13));
+    days.insert(14, Box::new(day_14::Day14));
+    days.insert(15, Box::new(day_15::Day15));
+    days.insert(16, Box::new(day_16::Day16));
+    days.insert(17, Box::new(day_17::Day17));
+    days.insert(18, Box::new(day_18::Day18));
+    days.insert(19, Box::new(day_19::Day19));
+    days.insert(20, Box::new(day_20::Day20));
+    days.insert(21, Box::new(day_21::Day21));
+    days.insert(22, Box::new(day_22::Day22));
+    days.insert(23, Box::new(day_23::Day23));
+    days.insert(24, Box::new(day_24::Day24));
+    days.insert(25, Box::new(day_25::Day25));
+
+    days
+}
+
+#[cfg(test)]
+mod tests {
+    use solution::{registry, Part, Solution};
+    use day_1::Day1;
+
+    #[test]
+    fn can_look_up_days() {
+        let days = registry();
+
+        assert_eq!((1..=25).collect::<Vec<usize>>(), days.keys().copied().collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn can_run_parts_individually() {
+        let input = "1721\n979\n366\n299\n675\n1456";
+        let parsed = Day1.parse(input).unwrap();
+
+        assert_eq!(Ok(514579), Day1.part_one(&parsed));
+        assert_eq!(Ok(241861950), Day1.part_two(&parsed));
+    }
+
+    #[test]
+    fn can_run_by_day_number() {
+        let days = registry();
+
+        assert_eq!(
+            Ok(vec!((Part::Two, "241861950".to_string()), (Part::One, "514579".to_string()))),
+            days[&1].run("1721\n979\n366\n299\n675\n1456", &[Part::Two, Part::One])
+        );
+        assert_eq!(
+            Ok(vec!((Part::One, "436".to_string()))),
+            days[&15].run("0,3,6", &[Part::One])
+        );
+    }
+
+    #[test]
+    fn can_run_parts_in_parallel() {
+        let days = registry();
+        let input = "1721\n979\n366\n299\n675\n1456";
+
+        let run = days[&1].run_timed_parallel(input, &[Part::Two, Part::One]).unwrap();
+        assert_eq!(
+            vec!((Part::Two, "241861950".to_string()), (Part::One, "514579".to_string())),
+            run.answers.into_iter().map(|answer| (answer.part, answer.answer)).collect::<Vec<_>>()
+        );
+        assert!(days[&1].run_timed_parallel("1\n2\n3", &[Part::One, Part::Two]).is_err());
+    }
+
+    #[test]
+    fn reports_errors() {
+        let days = registry();
+
+        let err = days[&12].run("F10\nZ3", &[Part::One]).unwrap_err();
+        assert_eq!(Some(2), err.line);
+        assert_eq!(Some(1), err.column);
+
+        assert!(days[&1].run("1\n2\n3", &[Part::One]).is_err());
+    }
+}